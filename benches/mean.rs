@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 #[macro_use] extern crate bencher;
 extern crate rand;
@@ -30,6 +30,15 @@ fn bench_average(b: &mut Bencher) {
     });
 }
 
+fn bench_average_slice(b: &mut Bencher) {
+    let values = initialize_vec();
+    b.iter(|| {
+        let mut m = average::MeanWithError::new();
+        m.add_slice(&values);
+        m
+    });
+}
+
 fn bench_stats(b: &mut Bencher) {
     let values = initialize_vec();
     b.iter(|| {
@@ -38,5 +47,5 @@ fn bench_stats(b: &mut Bencher) {
     });
 }
 
-benchmark_group!(benches, bench_average, bench_stats);
+benchmark_group!(benches, bench_average, bench_average_slice, bench_stats);
 benchmark_main!(benches);
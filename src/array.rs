@@ -0,0 +1,46 @@
+use ndarray::{Array1, ArrayView2, Axis, RemoveAxis};
+
+use super::Estimate;
+
+/// Estimate statistics of a two-dimensional array along an axis.
+///
+/// Returns an array of estimators, one for each lane along `axis`: For
+/// `Axis(0)`, there is one estimator per column, which gets the observations
+/// of that column. For `Axis(1)`, there is one per row. The array is traversed
+/// by subviews along `axis`, so the observations are not copied. This is only
+/// available with the `ndarray` feature.
+///
+/// Panics if `axis` is out of bounds.
+///
+///
+/// ## Example
+///
+/// ```
+/// extern crate average;
+/// extern crate ndarray;
+///
+/// use average::{Variance, mean_axis};
+/// use ndarray::{arr2, Axis};
+///
+/// # fn main() {
+/// let a = arr2(&[[1., 10.],
+///                [2., 20.],
+///                [3., 30.]]);
+/// let columns = mean_axis::<Variance, _>(a.view(), Axis(0));
+/// assert_eq!(columns.len(), 2);
+/// assert_eq!(columns[0].mean(), 2.);
+/// assert_eq!(columns[1].sample_variance(), 100.);
+/// # }
+/// ```
+pub fn mean_axis<E, F>(array: ArrayView2<F>, axis: Axis) -> Array1<E>
+    where E: Estimate<F> + Default, F: Copy
+{
+    let len = array.raw_dim().remove_axis(axis);
+    let mut estimators = Array1::from_shape_fn(len, |_| E::default());
+    for lane in array.axis_iter(axis) {
+        for (e, &x) in estimators.iter_mut().zip(lane.iter()) {
+            e.add(x);
+        }
+    }
+    estimators
+}
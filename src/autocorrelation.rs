@@ -0,0 +1,121 @@
+use conv::{ConvUtil, ValueFrom};
+use std::vec::Vec;
+
+use super::Correlation;
+
+/// Estimate the autocorrelation of a sequence of numbers ("time series") for
+/// lags up to a maximum.
+///
+/// The autocorrelation at lag `k` is the Pearson correlation coefficient of
+/// the pairs `(x[t], x[t+k])`. This is useful for checking whether the
+/// observations (for example residuals) are independent.
+///
+/// The last `max_lag` observations are kept in a ring buffer and one
+/// [`Correlation`] is updated per lag, so the memory and the time per
+/// observation are O(`max_lag`). This type is only available with the `std`
+/// feature.
+///
+/// [`Correlation`]: ./struct.Correlation.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Autocorrelation;
+///
+/// let mut a = Autocorrelation::new(2);
+/// for &x in &[1., -1., 1., -1., 1., -1.] {
+///     a.add(x);
+/// }
+/// assert_eq!(a.autocorrelation(1), -1.);
+/// assert_eq!(a.autocorrelation(2), 1.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Autocorrelation {
+    /// Last observations, where `x[t]` is at index `t % max_lag`.
+    recent: Vec<f64>,
+    /// Estimators of the correlation at each lag, starting with lag 1.
+    correlations: Vec<Correlation>,
+    /// Sample size.
+    n: u64,
+}
+
+impl Autocorrelation {
+    /// Create a new autocorrelation estimator for the lags `1..=max_lag`.
+    ///
+    /// Panics if `max_lag` is 0.
+    #[inline]
+    pub fn new(max_lag: usize) -> Autocorrelation {
+        assert!(max_lag > 0, "max_lag must be at least 1");
+        Autocorrelation {
+            recent: Vec::with_capacity(max_lag),
+            correlations: vec![Correlation::new(); max_lag],
+            n: 0,
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The maximal lag is kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.recent.clear();
+        for c in &mut self.correlations {
+            c.clear();
+        }
+        self.n = 0;
+    }
+
+    /// Return the largest lag for which the autocorrelation is estimated.
+    #[inline]
+    pub fn max_lag(&self) -> usize {
+        self.correlations.len()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Add the next observation of the sequence.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        let max_lag = self.max_lag();
+        // This is `t % max_lag` for the new observation `x[t]`.
+        let index = usize::value_from(self.n % max_lag.value_as::<u64>().unwrap())
+            .unwrap();  // < max_lag
+        let len = self.recent.len();
+        for lag in 1..=len {
+            let previous = self.recent[(index + max_lag - lag) % max_lag];
+            self.correlations[lag - 1].add(previous, x);
+        }
+        if len < max_lag {
+            self.recent.push(x);
+        } else {
+            self.recent[index] = x;
+        }
+        self.n += 1;
+    }
+
+    /// Estimate the autocorrelation at the given lag.
+    ///
+    /// Returns NaN if it is not defined, because there are less than two
+    /// pairs of observations `lag` apart, or one of their variances is zero.
+    ///
+    /// Panics if `lag` is not in `1..=max_lag`.
+    #[inline]
+    pub fn autocorrelation(&self, lag: usize) -> f64 {
+        assert!(lag >= 1 && lag <= self.max_lag(), "lag must be in 1..=max_lag");
+        self.correlations[lag - 1].correlation()
+    }
+}
+
+impl_extend!(Autocorrelation);
@@ -0,0 +1,382 @@
+use core;
+
+#[cfg(any(feature = "std", feature = "libm"))]
+use num_traits::Float;
+
+use super::{Mean, Estimate, Merge};
+
+/// Estimate the arithmetic means and the covariance of a sequence of pairs of
+/// numbers ("population").
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Covariance;
+///
+/// let a: Covariance = [(1., 5.), (2., 4.), (3., 3.), (4., 2.), (5., 1.)]
+///     .iter().map(|&p| p).collect();
+/// assert_eq!(a.mean_x(), 3.);
+/// assert_eq!(a.mean_y(), 3.);
+/// assert_eq!(a.covariance(), -2.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Covariance {
+    /// Estimator of the average of `x`.
+    avg_x: Mean,
+    /// Estimator of the average of `y`.
+    avg_y: Mean,
+    /// Intermediate sum of the co-moment for calculating the covariance.
+    sum_xy: f64,
+}
+
+impl Covariance {
+    /// Create a new covariance estimator.
+    #[inline]
+    pub fn new() -> Covariance {
+        Covariance {
+            avg_x: Mean::new(),
+            avg_y: Mean::new(),
+            sum_xy: 0.,
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Covariance::new();
+    }
+
+    /// Add a pair of observations sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64, y: f64) {
+        // This generalizes the algorithm introduced by Welford in 1962.
+        //
+        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
+        let delta_x = x - self.avg_x.mean();
+        self.avg_x.add(x);
+        self.avg_y.add(y);
+        self.sum_xy += delta_x * (y - self.avg_y.mean());
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.avg_x.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.avg_x.len()
+    }
+
+    /// Estimate the mean of `x` of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean_x(&self) -> f64 {
+        self.avg_x.mean()
+    }
+
+    /// Estimate the mean of `y` of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean_y(&self) -> f64 {
+        self.avg_y.mean()
+    }
+
+    /// Calculate the sample covariance.
+    ///
+    /// This is an unbiased estimator of the covariance of the population.
+    #[inline]
+    pub fn sample_covariance(&self) -> f64 {
+        let n = self.len();
+        if n < 2 {
+            return 0.;
+        }
+        self.sum_xy / (n - 1) as f64
+    }
+
+    /// Calculate the population covariance of the sample.
+    ///
+    /// This is a biased estimator of the covariance of the population.
+    #[inline]
+    pub fn covariance(&self) -> f64 {
+        let n = self.len();
+        if n < 2 {
+            return 0.;
+        }
+        self.sum_xy / n as f64
+    }
+}
+
+impl core::default::Default for Covariance {
+    fn default() -> Covariance {
+        Covariance::new()
+    }
+}
+
+impl core::iter::FromIterator<(f64, f64)> for Covariance {
+    fn from_iter<T>(iter: T) -> Covariance
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        let mut a = Covariance::new();
+        for (x, y) in iter {
+            a.add(x, y);
+        }
+        a
+    }
+}
+
+impl core::iter::Extend<(f64, f64)> for Covariance {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        for (x, y) in iter {
+            self.add(x, y);
+        }
+    }
+}
+
+impl Merge for Covariance {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Covariance, Merge};
+    ///
+    /// let sequence: &[(f64, f64)] = &[
+    ///     (1., 2.), (2., 3.), (3., 5.), (4., 4.), (5., 6.),
+    ///     (6., 8.), (7., 7.), (8., 9.), (9., 9.)];
+    /// let (left, right) = sequence.split_at(3);
+    /// let cov_total: Covariance = sequence.iter().map(|&p| p).collect();
+    /// let mut cov_left: Covariance = left.iter().map(|&p| p).collect();
+    /// let cov_right: Covariance = right.iter().map(|&p| p).collect();
+    /// cov_left.merge(&cov_right);
+    /// assert!((cov_total.covariance() - cov_left.covariance()).abs() < 1e-14);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &Covariance) {
+        if other.is_empty() {
+            return;
+        }
+        // This generalizes the algorithm proposed by Chan et al. in 1979.
+        //
+        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
+        let len_self = self.len() as f64;
+        let len_other = other.len() as f64;
+        let len_total = len_self + len_other;
+        let delta_x = other.mean_x() - self.mean_x();
+        let delta_y = other.mean_y() - self.mean_y();
+        self.avg_x.merge(&other.avg_x);
+        self.avg_y.merge(&other.avg_y);
+        self.sum_xy += other.sum_xy
+            + delta_x * delta_y * len_self * len_other / len_total;
+    }
+}
+
+/// Estimate the Pearson correlation coefficient of a sequence of pairs of
+/// numbers ("population").
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Correlation;
+///
+/// let a: Correlation = [(1., 5.), (2., 4.), (3., 3.), (4., 2.), (5., 1.)]
+///     .iter().map(|&p| p).collect();
+/// assert_eq!(a.correlation(), -1.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Correlation {
+    /// Estimator of the covariance.
+    cov: Covariance,
+    /// Intermediate sum of squares of `x` for calculating the variance.
+    sum_x_2: f64,
+    /// Intermediate sum of squares of `y` for calculating the variance.
+    sum_y_2: f64,
+}
+
+impl Correlation {
+    /// Create a new correlation estimator.
+    #[inline]
+    pub fn new() -> Correlation {
+        Correlation {
+            cov: Covariance::new(),
+            sum_x_2: 0.,
+            sum_y_2: 0.,
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Correlation::new();
+    }
+
+    /// Add a pair of observations sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64, y: f64) {
+        let delta_x = x - self.cov.mean_x();
+        let delta_y = y - self.cov.mean_y();
+        self.cov.add(x, y);
+        self.sum_x_2 += delta_x * (x - self.cov.mean_x());
+        self.sum_y_2 += delta_y * (y - self.cov.mean_y());
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cov.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.cov.len()
+    }
+
+    /// Estimate the mean of `x` of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean_x(&self) -> f64 {
+        self.cov.mean_x()
+    }
+
+    /// Estimate the mean of `y` of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean_y(&self) -> f64 {
+        self.cov.mean_y()
+    }
+
+    /// Calculate the population variance of `x` of the sample.
+    ///
+    /// This is a biased estimator of the variance of the population.
+    #[inline]
+    pub fn variance_x(&self) -> f64 {
+        let n = self.len();
+        if n < 2 {
+            return 0.;
+        }
+        self.sum_x_2 / n as f64
+    }
+
+    /// Calculate the population variance of `y` of the sample.
+    ///
+    /// This is a biased estimator of the variance of the population.
+    #[inline]
+    pub fn variance_y(&self) -> f64 {
+        let n = self.len();
+        if n < 2 {
+            return 0.;
+        }
+        self.sum_y_2 / n as f64
+    }
+
+    /// Calculate the population covariance of the sample.
+    ///
+    /// This is a biased estimator of the covariance of the population.
+    #[inline]
+    pub fn covariance(&self) -> f64 {
+        self.cov.covariance()
+    }
+
+    /// Calculate the sample covariance.
+    ///
+    /// This is an unbiased estimator of the covariance of the population.
+    #[inline]
+    pub fn sample_covariance(&self) -> f64 {
+        self.cov.sample_covariance()
+    }
+
+    /// Calculate the Pearson correlation coefficient of the sample.
+    ///
+    /// The result is clamped to [-1, 1] to avoid overshooting due to rounding
+    /// errors. Returns NaN if the variance of `x` or `y` is zero (including
+    /// samples with less than two observations), because the correlation is
+    /// not defined in that case.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[inline]
+    pub fn correlation(&self) -> f64 {
+        if self.sum_x_2 == 0. || self.sum_y_2 == 0. {
+            return f64::NAN;
+        }
+        let r = self.cov.sum_xy / Float::sqrt(self.sum_x_2 * self.sum_y_2);
+        r.clamp(-1., 1.)
+    }
+}
+
+impl core::default::Default for Correlation {
+    fn default() -> Correlation {
+        Correlation::new()
+    }
+}
+
+impl core::iter::FromIterator<(f64, f64)> for Correlation {
+    fn from_iter<T>(iter: T) -> Correlation
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        let mut a = Correlation::new();
+        for (x, y) in iter {
+            a.add(x, y);
+        }
+        a
+    }
+}
+
+impl core::iter::Extend<(f64, f64)> for Correlation {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        for (x, y) in iter {
+            self.add(x, y);
+        }
+    }
+}
+
+impl Merge for Correlation {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Correlation, Merge};
+    ///
+    /// let sequence: &[(f64, f64)] = &[
+    ///     (1., 2.), (2., 3.), (3., 5.), (4., 4.), (5., 6.),
+    ///     (6., 8.), (7., 7.), (8., 9.), (9., 9.)];
+    /// let (left, right) = sequence.split_at(3);
+    /// let r_total: Correlation = sequence.iter().map(|&p| p).collect();
+    /// let mut r_left: Correlation = left.iter().map(|&p| p).collect();
+    /// let r_right: Correlation = right.iter().map(|&p| p).collect();
+    /// r_left.merge(&r_right);
+    /// assert!((r_total.correlation() - r_left.correlation()).abs() < 1e-14);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &Correlation) {
+        if other.is_empty() {
+            return;
+        }
+        let len_self = self.len() as f64;
+        let len_other = other.len() as f64;
+        let len_total = len_self + len_other;
+        let delta_x = other.mean_x() - self.mean_x();
+        let delta_y = other.mean_y() - self.mean_y();
+        self.cov.merge(&other.cov);
+        self.sum_x_2 += other.sum_x_2
+            + delta_x * delta_x * len_self * len_other / len_total;
+        self.sum_y_2 += other.sum_y_2
+            + delta_y * delta_y * len_self * len_other / len_total;
+    }
+}
@@ -0,0 +1,146 @@
+use core::hash::{Hash, Hasher};
+
+use conv::ConvUtil;
+use std::collections::hash_map::DefaultHasher;
+use std::vec::Vec;
+
+use super::Merge;
+
+/// Estimate the number of distinct elements of a sequence ("cardinality").
+///
+/// This uses the HyperLogLog algorithm by Flajolet et al. with the small-range
+/// correction, so it only needs `2^precision` bytes of memory, regardless of
+/// the number of elements. The relative standard error of the estimate is
+/// about `1.04/sqrt(2^precision)`. This type is only available with the `std`
+/// feature.
+///
+/// The elements are hashed with the hasher of the standard library, whose
+/// algorithm may change between Rust versions. Estimators should therefore
+/// only be merged if they were built by the same program.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::DistinctCount;
+///
+/// let mut a = DistinctCount::new(12);
+/// for i in 0..10_000 {
+///     a.add(&(i % 1000));
+/// }
+/// println!("There are about {} distinct elements.", a.estimate());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DistinctCount {
+    /// Number of bits of the hash used for choosing a register.
+    precision: u32,
+    /// Maximal number of leading zeros (plus one) observed for each register.
+    registers: Vec<u8>,
+}
+
+impl DistinctCount {
+    /// Create a new estimator of the number of distinct elements with
+    /// `2^precision` registers.
+    ///
+    /// Panics if `precision` is not between 4 and 18.
+    #[inline]
+    pub fn new(precision: u32) -> DistinctCount {
+        assert!((4..=18).contains(&precision), "precision must be between 4 and 18");
+        DistinctCount { precision, registers: vec![0; 1 << precision] }
+    }
+
+    /// Reset the estimator to an empty sequence.
+    ///
+    /// The precision is kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        for r in &mut self.registers {
+            *r = 0;
+        }
+    }
+
+    /// Return the number of bits used for choosing a register.
+    #[inline]
+    pub fn precision(&self) -> u32 {
+        self.precision
+    }
+
+    /// Determine whether no element was added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.registers.iter().all(|&r| r == 0)
+    }
+
+    /// Add an element of the sequence.
+    #[inline]
+    pub fn add<H: Hash + ?Sized>(&mut self, x: &H) {
+        let mut hasher = DefaultHasher::new();
+        x.hash(&mut hasher);
+        let hash = hasher.finish();
+        let index = (hash >> (64 - self.precision)) as usize;
+        // The guard bit limits the number of leading zeros, in case all
+        // remaining bits are zero.
+        let rest = (hash << self.precision) | (1 << (self.precision - 1));
+        let rank = (rest.leading_zeros() + 1) as u8;
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+    }
+
+    /// Estimate the number of distinct elements.
+    ///
+    /// Returns 0 if no element was added.
+    pub fn estimate(&self) -> f64 {
+        let m = self.registers.len().approx_as::<f64>().unwrap();
+        let alpha = match self.registers.len() {
+            16 => 0.673,
+            32 => 0.697,
+            64 => 0.709,
+            _ => 0.7213 / (1. + 1.079 / m),
+        };
+        let sum: f64 = self.registers.iter()
+            .map(|&r| 2f64.powi(-i32::from(r)))
+            .sum();
+        let estimate = alpha * m * m / sum;
+        let zeros = self.registers.iter().filter(|&&r| r == 0).count();
+        if estimate <= 2.5 * m && zeros > 0 {
+            // Small-range correction using linear counting.
+            return m * (m / zeros.approx_as::<f64>().unwrap()).ln();
+        }
+        estimate
+    }
+}
+
+impl Merge for DistinctCount {
+    /// Merge another sequence into this one.
+    ///
+    /// The result estimates the number of distinct elements of the union of
+    /// both sequences.
+    ///
+    /// Panics if the precisions differ.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{DistinctCount, Merge};
+    ///
+    /// let mut a = DistinctCount::new(10);
+    /// let mut b = DistinctCount::new(10);
+    /// a.add("foo");
+    /// b.add("foo");
+    /// b.add("bar");
+    /// a.merge(&b);
+    /// assert_eq!(a.estimate().round(), 2.);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &DistinctCount) {
+        assert_eq!(self.precision, other.precision, "precisions must be the same");
+        for (r, &other_r) in self.registers.iter_mut().zip(&other.registers) {
+            if other_r > *r {
+                *r = other_r;
+            }
+        }
+    }
+}
@@ -0,0 +1,291 @@
+#[cfg(any(feature = "std", feature = "libm"))]
+use num_traits::Float;
+
+use super::{Estimate, Merge};
+
+/// Estimate the exponentially weighted moving average of a sequence of numbers.
+///
+/// In contrast to the other estimators, recent observations are weighted more
+/// than older ones: Each new observation `x` updates the estimate to
+/// `alpha*x + (1 - alpha)*mean`. This is useful for non-stationary sequences.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{ExpMovingAverage, Estimate};
+///
+/// let mut a = ExpMovingAverage::new(0.5);
+/// a.add(1.);
+/// a.add(3.);
+/// assert_eq!(a.mean(), 2.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExpMovingAverage {
+    /// Weight of new observations.
+    alpha: f64,
+    /// Moving average.
+    avg: f64,
+    /// Sample size.
+    n: u64,
+}
+
+impl ExpMovingAverage {
+    /// Create a new exponentially weighted moving average estimator, where
+    /// `alpha` is the weight of new observations.
+    ///
+    /// Panics if `alpha` is not in the interval (0, 1].
+    #[inline]
+    pub fn new(alpha: f64) -> ExpMovingAverage {
+        assert!(0. < alpha && alpha <= 1., "alpha must be in (0, 1]");
+        ExpMovingAverage { alpha, avg: 0., n: 0 }
+    }
+
+    /// Create a new exponentially weighted moving average estimator, such that
+    /// the weight of an observation halves after `half_life` more observations.
+    ///
+    /// Panics if `half_life` is not positive.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[inline]
+    pub fn from_half_life(half_life: f64) -> ExpMovingAverage {
+        assert!(half_life > 0., "half-life must be positive");
+        ExpMovingAverage::new(1. - Float::exp(-core::f64::consts::LN_2 / half_life))
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = ExpMovingAverage::new(self.alpha);
+    }
+
+    /// Return the weight of new observations.
+    #[inline]
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Estimate the moving average.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.avg
+    }
+}
+
+impl Estimate for ExpMovingAverage {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        // The first observation initializes the average, otherwise it would be
+        // biased towards 0.
+        if self.n == 0 {
+            self.avg = x;
+        } else {
+            self.avg += self.alpha * (x - self.avg);
+        }
+        self.n += 1;
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
+
+/// Estimate the exponentially weighted moving average and variance of a
+/// sequence of numbers.
+///
+/// The observations are weighted like for [`ExpMovingAverage`], which is useful
+/// for estimating the volatility of non-stationary sequences.
+///
+/// [`ExpMovingAverage`]: ./struct.ExpMovingAverage.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{ExpMovingVariance, Estimate};
+///
+/// let mut a = ExpMovingVariance::new(0.5);
+/// a.add(1.);
+/// a.add(3.);
+/// assert_eq!(a.mean(), 2.);
+/// assert_eq!(a.variance(), 1.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ExpMovingVariance {
+    /// Estimator of the moving average.
+    avg: ExpMovingAverage,
+    /// Moving variance.
+    variance: f64,
+    /// Sum of the weights, relative to the weight of the last observation.
+    weight_sum: f64,
+}
+
+impl ExpMovingVariance {
+    /// Create a new exponentially weighted moving variance estimator, where
+    /// `alpha` is the weight of new observations.
+    ///
+    /// Panics if `alpha` is not in the interval (0, 1].
+    #[inline]
+    pub fn new(alpha: f64) -> ExpMovingVariance {
+        ExpMovingVariance {
+            avg: ExpMovingAverage::new(alpha),
+            variance: 0.,
+            weight_sum: 0.,
+        }
+    }
+
+    /// Create a new exponentially weighted moving variance estimator, such
+    /// that the weight of an observation halves after `half_life` more
+    /// observations.
+    ///
+    /// Panics if `half_life` is not positive.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[inline]
+    pub fn from_half_life(half_life: f64) -> ExpMovingVariance {
+        let avg = ExpMovingAverage::from_half_life(half_life);
+        ExpMovingVariance::new(avg.alpha())
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = ExpMovingVariance::new(self.alpha());
+    }
+
+    /// Return the weight of new observations.
+    #[inline]
+    pub fn alpha(&self) -> f64 {
+        self.avg.alpha()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.avg.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.avg.len()
+    }
+
+    /// Return the effective sample size.
+    ///
+    /// This is the sum of the weights of the observations, relative to the
+    /// weight of the latest one. It approaches `1/alpha` for large samples.
+    #[inline]
+    pub fn effective_len(&self) -> f64 {
+        self.weight_sum
+    }
+
+    /// Estimate the moving average.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.avg.mean()
+    }
+
+    /// Estimate the moving variance.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn variance(&self) -> f64 {
+        self.variance
+    }
+
+    /// Estimate the moving standard deviation.
+    ///
+    /// Returns 0 for an empty sample.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[inline]
+    pub fn std_dev(&self) -> f64 {
+        Float::sqrt(self.variance)
+    }
+}
+
+impl Estimate for ExpMovingVariance {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        // This algorithm was suggested by Finch in 2009.
+        //
+        // See http://people.ds.cam.ac.uk/fanf2/hermes/doc/antiforgery/stats.pdf.
+        let alpha = self.alpha();
+        if !self.is_empty() {
+            let delta = x - self.mean();
+            self.variance = (1. - alpha) * (self.variance + alpha * delta * delta);
+        }
+        self.weight_sum = (1. - alpha) * self.weight_sum + 1.;
+        self.avg.add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.variance()
+    }
+}
+
+impl Merge for ExpMovingVariance {
+    /// Merge another sample into this one.
+    ///
+    /// The samples are assumed to be drawn concurrently, so the estimates are
+    /// combined according to their effective sample sizes. The value of
+    /// `alpha` of `self` is kept.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{ExpMovingVariance, Estimate, Merge};
+    ///
+    /// let mut a = ExpMovingVariance::new(0.1);
+    /// let mut b = ExpMovingVariance::new(0.1);
+    /// for _ in 0..10 {
+    ///     a.add(1.);
+    ///     b.add(3.);
+    /// }
+    /// a.merge(&b);
+    /// assert_eq!(a.mean(), 2.);
+    /// assert_eq!(a.variance(), 1.);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &ExpMovingVariance) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            let alpha = self.alpha();
+            *self = other.clone();
+            self.avg.alpha = alpha;
+            return;
+        }
+        let weight_self = self.weight_sum;
+        let weight_other = other.weight_sum;
+        let weight_total = weight_self + weight_other;
+        let delta = other.mean() - self.mean();
+        self.variance = (weight_self * self.variance + weight_other * other.variance)
+            / weight_total
+            + weight_self * weight_other * delta * delta / (weight_total * weight_total);
+        self.avg.avg = (weight_self * self.mean() + weight_other * other.mean())
+            / weight_total;
+        self.avg.n += other.avg.n;
+        self.weight_sum = weight_total;
+    }
+}
@@ -0,0 +1,203 @@
+use core::hash::Hash;
+
+use std::collections::HashMap;
+use std::vec::Vec;
+
+use super::Merge;
+
+/// Estimate the most frequent items of a sequence ("heavy hitters").
+///
+/// This uses the Space-Saving algorithm by Metwally et al., which keeps at
+/// most `k` counters. If an item without a counter is added when all counters
+/// are in use, the counter with the smallest count is taken over, and the item
+/// inherits its count. Therefore the counts are upper bounds, overestimating
+/// by at most the returned error bound, which is at most `n/k` for `n`
+/// added items. Every item occurring more than `n/k` times is guaranteed to
+/// have a counter. This type is only available with the `std` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::FrequentItems;
+///
+/// let mut a = FrequentItems::new(2);
+/// for &word in &["a", "b", "a", "c", "a", "b"] {
+///     a.add(word);
+/// }
+/// let top = a.top_k();
+/// assert_eq!(top[0], ("a", 3, 0));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(bound(
+    serialize = "K: ::serde::Serialize + Hash + Eq",
+    deserialize = "K: ::serde::Deserialize<'de> + Hash + Eq")))]
+pub struct FrequentItems<K> {
+    /// Maximal number of counters.
+    k: usize,
+    /// Number of added items.
+    n: u64,
+    /// Counters of the monitored items.
+    counters: HashMap<K, Counter>,
+}
+
+/// The counter of an item monitored by `FrequentItems`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Counter {
+    /// Upper bound of the number of occurrences.
+    count: u64,
+    /// Maximal overestimation of the number of occurrences.
+    error: u64,
+}
+
+impl<K: Hash + Eq + Clone> FrequentItems<K> {
+    /// Create a new estimator of the most frequent items with `k` counters.
+    ///
+    /// Panics if `k` is 0.
+    #[inline]
+    pub fn new(k: usize) -> FrequentItems<K> {
+        assert!(k > 0, "there must be at least one counter");
+        FrequentItems { k, n: 0, counters: HashMap::with_capacity(k) }
+    }
+
+    /// Reset the estimator to an empty sequence.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.counters.clear();
+        self.n = 0;
+    }
+
+    /// Return the maximal number of counters.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.k
+    }
+
+    /// Return the number of added items.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Determine whether the sequence is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Add an item of the sequence.
+    pub fn add(&mut self, key: K) {
+        self.n += 1;
+        if let Some(counter) = self.counters.get_mut(&key) {
+            counter.count += 1;
+            return;
+        }
+        if self.counters.len() < self.k {
+            self.counters.insert(key, Counter { count: 1, error: 0 });
+            return;
+        }
+        let min_key = self.counters.iter()
+            .min_by_key(|&(_, c)| c.count)
+            .map(|(k, _)| k.clone())
+            .unwrap();  // `k > 0` counters are in use
+        let min = self.counters.remove(&min_key).unwrap().count;
+        self.counters.insert(key, Counter { count: min + 1, error: min });
+    }
+
+    /// Estimate the number of occurrences of an item.
+    ///
+    /// This is an upper bound. Returns 0 if the item is not monitored, in
+    /// which case it occurred at most as often as the smallest count.
+    #[inline]
+    pub fn count(&self, key: &K) -> u64 {
+        self.counters.get(key).map_or(0, |c| c.count)
+    }
+
+    /// Return the monitored items with their estimated number of occurrences
+    /// and the maximal overestimation, sorted by decreasing count.
+    ///
+    /// Items with the same count are returned in arbitrary order.
+    pub fn top_k(&self) -> Vec<(K, u64, u64)> {
+        let mut top: Vec<(K, u64, u64)> = self.counters.iter()
+            .map(|(k, c)| (k.clone(), c.count, c.error))
+            .collect();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+        top
+    }
+
+    /// Return the smallest count if all counters are in use, otherwise 0.
+    ///
+    /// This is an upper bound for the number of occurrences of any item
+    /// without a counter.
+    #[inline]
+    fn min_count(&self) -> u64 {
+        if self.counters.len() < self.k {
+            return 0;
+        }
+        self.counters.values().map(|c| c.count).min().unwrap_or(0)
+    }
+}
+
+impl<K: Hash + Eq + Clone> Merge for FrequentItems<K> {
+    /// Merge another sequence into this one.
+    ///
+    /// The counts of both estimators are added. If an item is only monitored
+    /// by one of them, the smallest count of the other one is added to its
+    /// count and error bound, because the item may have occurred that often.
+    /// Finally, only the `k` largest counts of `self` are kept, so the counts
+    /// remain upper bounds.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{FrequentItems, Merge};
+    ///
+    /// let mut a = FrequentItems::new(3);
+    /// let mut b = FrequentItems::new(3);
+    /// for &x in &[1, 1, 2, 3] {
+    ///     a.add(x);
+    /// }
+    /// for &x in &[1, 2, 2] {
+    ///     b.add(x);
+    /// }
+    /// a.merge(&b);
+    /// assert_eq!(a.count(&1), 3);
+    /// assert_eq!(a.count(&2), 3);
+    /// ```
+    fn merge(&mut self, other: &FrequentItems<K>) {
+        if other.is_empty() {
+            return;
+        }
+        let (min_self, min_other) = (self.min_count(), other.min_count());
+        for counter in self.counters.values_mut() {
+            counter.count += min_other;
+            counter.error += min_other;
+        }
+        for (key, other_counter) in &other.counters {
+            match self.counters.get_mut(key) {
+                Some(counter) => {
+                    // `min_other` was added above, but the exact counter of
+                    // `other` is known.
+                    counter.count = counter.count - min_other + other_counter.count;
+                    counter.error = counter.error - min_other + other_counter.error;
+                }
+                None => {
+                    self.counters.insert(key.clone(), Counter {
+                        count: other_counter.count + min_self,
+                        error: other_counter.error + min_self,
+                    });
+                }
+            }
+        }
+        if self.counters.len() > self.k {
+            let mut counters: Vec<(K, Counter)> = self.counters.drain().collect();
+            counters.sort_by_key(|c| core::cmp::Reverse(c.1.count));
+            counters.truncate(self.k);
+            self.counters.extend(counters);
+        }
+        self.n += other.n;
+    }
+}
@@ -0,0 +1,164 @@
+use core;
+
+use num_traits::Float;
+
+use super::{Estimate, Merge, Variance};
+
+/// Estimate the geometric mean of a sequence of positive numbers
+/// ("population").
+///
+/// The logarithms of the observations are accumulated, which avoids the
+/// overflow of multiplying many factors directly. This is useful for averaging
+/// ratios and growth factors. The variance of the logarithms is estimated as
+/// well, giving the geometric standard deviation.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::GeometricMean;
+///
+/// let a: GeometricMean = [2., 8.].iter().map(|x| *x).collect();
+/// assert!((a.mean() - 4.).abs() < 1e-15);
+/// println!("The geometric standard deviation is {}.", a.geometric_std_dev());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GeometricMean {
+    /// Estimator of the mean and the variance of the logarithms of the
+    /// observations.
+    logs: Variance,
+    /// Number of observations equal to 0, which have no finite logarithm.
+    zeros: u64,
+}
+
+impl GeometricMean {
+    /// Create a new geometric mean estimator.
+    #[inline]
+    pub fn new() -> GeometricMean {
+        GeometricMean { logs: Variance::new(), zeros: 0 }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = GeometricMean::new();
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.logs.len() + self.zeros
+    }
+
+    /// Estimate the geometric mean of the population.
+    ///
+    /// Returns NaN if a negative number was observed, because the geometric
+    /// mean is not defined in that case. Otherwise, observing 0 results in 0,
+    /// as expected for a product with a zero factor. Returns 0 for an empty
+    /// sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        let mean = self.logs.mean();
+        if mean.is_nan() {
+            return f64::NAN;
+        }
+        if self.zeros > 0 {
+            return 0.;
+        }
+        Float::exp(mean)
+    }
+
+    /// Estimate the geometric standard deviation of the population.
+    ///
+    /// This is the exponential of the sample standard deviation of the
+    /// logarithms of the observations, which is what is usually reported for
+    /// log-normal distributions. It is a factor: About two thirds of the
+    /// observations of a log-normal distribution lie between the geometric
+    /// mean divided and multiplied by it.
+    ///
+    /// Returns 1, the multiplicative identity, for samples with less than two
+    /// observations. Otherwise, returns NaN if 0 or a negative number was
+    /// observed.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::GeometricMean;
+    ///
+    /// let a: GeometricMean = [1., 10., 100.].iter().map(|x| *x).collect();
+    /// assert!((a.geometric_std_dev() - 10.).abs() < 1e-12);
+    /// ```
+    #[inline]
+    pub fn geometric_std_dev(&self) -> f64 {
+        if self.len() < 2 {
+            return 1.;
+        }
+        if self.zeros > 0 {
+            return f64::NAN;
+        }
+        Float::exp(self.logs.sample_std_dev())
+    }
+}
+
+impl core::default::Default for GeometricMean {
+    fn default() -> GeometricMean {
+        GeometricMean::new()
+    }
+}
+
+impl Estimate for GeometricMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        // The logarithm is NaN for negative observations, so they propagate
+        // to the mean as documented. Zeros are only counted, because an
+        // infinite logarithm would turn the running variance into NaN.
+        if x == 0. {
+            self.zeros += 1;
+        } else {
+            self.logs.add(Float::ln(x));
+        }
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
+
+impl Merge for GeometricMean {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{GeometricMean, Merge};
+    ///
+    /// let sequence: &[f64] = &[1., 2., 4., 8., 16.];
+    /// let (left, right) = sequence.split_at(2);
+    /// let total: GeometricMean = sequence.iter().map(|x| *x).collect();
+    /// let mut avg_left: GeometricMean = left.iter().map(|x| *x).collect();
+    /// let avg_right: GeometricMean = right.iter().map(|x| *x).collect();
+    /// avg_left.merge(&avg_right);
+    /// assert!((total.mean() - avg_left.mean()).abs() < 1e-14);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &GeometricMean) {
+        self.logs.merge(&other.logs);
+        self.zeros += other.zeros;
+    }
+}
+
+impl_from_iterator!(GeometricMean);
+impl_extend!(GeometricMean);
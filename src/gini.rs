@@ -0,0 +1,159 @@
+use conv::ConvUtil;
+
+use super::{DynHistogram, Estimate, Merge};
+
+/// Estimate the Gini coefficient of a sequence of non-negative numbers.
+///
+/// The Gini coefficient measures the inequality of a distribution, for
+/// example of incomes or of the load of servers. It is 0 if all observations
+/// are equal and approaches 1 if a single observation accounts for the whole
+/// sum.
+///
+/// Calculating it exactly requires all observations, so they are counted in
+/// the bins of a [`DynHistogram`] instead, and the Gini coefficient is
+/// calculated from the Lorenz curve of the binned distribution. This type is
+/// only available with the `std` feature.
+///
+/// [`DynHistogram`]: ./struct.DynHistogram.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Gini;
+///
+/// let mut a = Gini::with_bounds(0., 100., 10);
+/// a.extend([5., 5., 5., 65.].iter().map(|x| *x));
+/// assert!((a.gini() - 0.5625).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Gini {
+    histogram: DynHistogram,
+}
+
+impl Gini {
+    /// Create a new Gini coefficient estimator with `n_bins` bins of equal
+    /// width covering the interval `[min, max]`.
+    ///
+    /// Panics if `min` is negative, if `min` is not smaller than `max` or if
+    /// `n_bins` is 0.
+    #[inline]
+    pub fn with_bounds(min: f64, max: f64, n_bins: usize) -> Gini {
+        assert!(min >= 0., "min must not be negative");
+        Gini { histogram: DynHistogram::with_bounds(min, max, n_bins) }
+    }
+
+    /// Create a new Gini coefficient estimator with `n_bins` logarithmically
+    /// spaced bins covering the interval `[min, max]`.
+    ///
+    /// This is useful for heavy-tailed distributions, like incomes.
+    ///
+    /// Panics if `min` is not positive, if `min` is not smaller than `max` or
+    /// if `n_bins` is 0.
+    #[inline]
+    pub fn with_log_bounds(min: f64, max: f64, n_bins: usize) -> Gini {
+        Gini { histogram: DynHistogram::with_log_bounds(min, max, n_bins) }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The bin edges are kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.histogram.clear();
+    }
+
+    /// Return the sample size, including the observations outside of the
+    /// bins.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.histogram.total()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the histogram of the observations.
+    #[inline]
+    pub fn histogram(&self) -> &DynHistogram {
+        &self.histogram
+    }
+
+    /// Estimate the Gini coefficient of the observations inside the bins.
+    ///
+    /// All observations in a bin are assumed to be at its midpoint, so the
+    /// inequality within the bins is neglected, and the Lorenz curve is
+    /// interpolated linearly between the bin edges. The estimate is therefore
+    /// biased low, and the bias depends on the resolution of the bins: For a
+    /// uniform distribution over `k` bins of equal width, the estimate is
+    /// `1/3 - 1/(3 k^2)` instead of `1/3`. Observations outside of the range
+    /// of the bins are ignored, so the range should cover all of them.
+    ///
+    /// The result is the population Gini coefficient of the binned
+    /// distribution, which is between 0 and 1. Returns 0 if all bins are
+    /// empty.
+    pub fn gini(&self) -> f64 {
+        // The Gini coefficient is half of the mean absolute difference
+        // divided by the mean. Sorting the bins by their midpoint, the sum
+        // over all pairs of bins is accumulated in a single pass.
+        let mut count = 0.;
+        let mut sum = 0.;
+        let mut sum_differences = 0.;
+        for (lower, upper, n) in self.histogram.bins() {
+            if n == 0 {
+                continue;
+            }
+            let n = n.approx_as::<f64>().unwrap();
+            let midpoint = 0.5 * (lower + upper);
+            sum_differences += n * (midpoint * count - sum);
+            count += n;
+            sum += n * midpoint;
+        }
+        if count == 0. || sum == 0. {
+            return 0.;
+        }
+        sum_differences / (count * sum)
+    }
+}
+
+impl Estimate for Gini {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.histogram.add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.gini()
+    }
+}
+
+impl Merge for Gini {
+    /// Merge another sample into this one.
+    ///
+    /// Panics if the bin edges of the estimators differ.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Gini, Merge};
+    ///
+    /// let mut a = Gini::with_bounds(0., 10., 10);
+    /// let mut b = Gini::with_bounds(0., 10., 10);
+    /// a.extend([5.5, 5.5].iter().map(|x| *x));
+    /// b.extend([5.5, 5.5].iter().map(|x| *x));
+    /// a.merge(&b);
+    /// assert_eq!(a.gini(), 0.);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &Gini) {
+        self.histogram.merge(&other.histogram);
+    }
+}
+
+impl_extend!(Gini);
@@ -0,0 +1,179 @@
+use conv::{ConvAsUtil, ConvUtil};
+use std::vec::Vec;
+
+/// An observation kept by `GkSummary`, with information about its rank.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Tuple {
+    /// The observation.
+    value: f64,
+    /// Minimal rank of this tuple minus the minimal rank of the previous one.
+    g: u64,
+    /// Maximal rank minus minimal rank of this tuple.
+    delta: u64,
+}
+
+/// Estimate arbitrary quantiles of a sequence of numbers ("population") with
+/// a guaranteed error bound.
+///
+/// This uses the algorithm by Greenwald and Khanna. For a sample of size `n`,
+/// the estimate of the p-quantile is one of the observations, and its rank in
+/// the sorted sample differs from `ceil(p*n)` by at most `epsilon*n`. In
+/// contrast to [`Quantile`] and [`TDigest`], this holds for any distribution
+/// and any order of the observations.
+///
+/// The observations are summarized by a list of tuples, which is compressed
+/// periodically. Its length typically grows logarithmically with the sample
+/// size and is proportional to `1/epsilon`. It is returned by
+/// `tuple_count()`. This type is only available with the `std` feature.
+///
+/// [`Quantile`]: ./struct.Quantile.html
+/// [`TDigest`]: ./struct.TDigest.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::GkSummary;
+///
+/// let mut a = GkSummary::new(0.01);
+/// for i in 1..=1000 {
+///     a.add(f64::from(i));
+/// }
+/// // The rank of the estimate is within 0.01*1000 = 10 of 500.
+/// assert!((a.quantile(0.5) - 500.).abs() <= 10.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GkSummary {
+    /// Maximal rank error relative to the sample size.
+    epsilon: f64,
+    /// Tuples, sorted by their observations.
+    tuples: Vec<Tuple>,
+    /// Sample size.
+    n: u64,
+}
+
+impl GkSummary {
+    /// Create a new summary with the maximal rank error `epsilon`, relative
+    /// to the sample size.
+    ///
+    /// Panics if `epsilon` is not between 0 and 1 (exclusive).
+    #[inline]
+    pub fn new(epsilon: f64) -> GkSummary {
+        assert!(epsilon > 0. && epsilon < 1., "epsilon must be between 0 and 1");
+        GkSummary { epsilon, tuples: Vec::new(), n: 0 }
+    }
+
+    /// Reset the summary to an empty sample.
+    ///
+    /// The value of `epsilon` is kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.tuples.clear();
+        self.n = 0;
+    }
+
+    /// Return the maximal rank error relative to the sample size.
+    #[inline]
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the number of stored tuples, which determines the memory used.
+    #[inline]
+    pub fn tuple_count(&self) -> usize {
+        self.tuples.len()
+    }
+
+    /// Return `floor(2*epsilon*n)`, the maximal uncertainty of the rank of a
+    /// tuple.
+    #[inline]
+    fn threshold(&self) -> u64 {
+        (2. * self.epsilon * self.n.approx_as::<f64>().unwrap())
+            .floor().approx().unwrap()
+    }
+
+    /// Add an observation.
+    ///
+    /// NaN is ignored, because it cannot be ranked.
+    // `u64::is_multiple_of` is only stable since Rust 1.87.
+    #[allow(unknown_lints, clippy::manual_is_multiple_of)]
+    pub fn add(&mut self, x: f64) {
+        if x.is_nan() {
+            return;
+        }
+        let i = self.tuples.partition_point(|t| t.value <= x);
+        // The rank of a new minimum or maximum is known exactly. Otherwise it
+        // is at most as uncertain as the rank of the next tuple.
+        let delta = if i == 0 || i == self.tuples.len() {
+            0
+        } else {
+            let next = self.tuples[i];
+            next.g + next.delta - 1
+        };
+        self.tuples.insert(i, Tuple { value: x, g: 1, delta });
+        self.n += 1;
+        let period: u64 = (1. / (2. * self.epsilon)).floor().approx().unwrap();
+        if self.n % period.max(1) == 0 {
+            self.compress();
+        }
+    }
+
+    /// Merge adjacent tuples, as long as the uncertainty of the ranks stays
+    /// within the threshold.
+    ///
+    /// The first and the last tuple are kept, so the ranks of the smallest and
+    /// the largest observation remain exact.
+    fn compress(&mut self) {
+        let threshold = self.threshold();
+        let mut i = self.tuples.len().saturating_sub(2);
+        while i >= 1 {
+            let (current, next) = (self.tuples[i], self.tuples[i + 1]);
+            if current.g + next.g + next.delta <= threshold {
+                self.tuples[i + 1].g += current.g;
+                self.tuples.remove(i);
+            }
+            i -= 1;
+        }
+    }
+
+    /// Estimate the p-quantile of the population.
+    ///
+    /// The result is an observation whose rank differs from `ceil(p*n)` by
+    /// at most `epsilon*n`. Returns 0 for an empty sample. Panics if `p` is
+    /// not between 0 and 1.
+    pub fn quantile(&self, p: f64) -> f64 {
+        assert!((0. ..=1.).contains(&p), "p must be between 0 and 1");
+        if self.is_empty() {
+            return 0.;
+        }
+        let n = self.n.approx_as::<f64>().unwrap();
+        let bound = (p * n).ceil().max(1.) + self.epsilon * n;
+        let mut min_rank = 0;
+        let mut previous = self.tuples[0].value;
+        for t in &self.tuples {
+            min_rank += t.g;
+            let max_rank = (min_rank + t.delta).approx_as::<f64>().unwrap();
+            if max_rank > bound {
+                return previous;
+            }
+            previous = t.value;
+        }
+        previous
+    }
+}
+
+impl_extend!(GkSummary);
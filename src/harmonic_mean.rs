@@ -0,0 +1,111 @@
+use core;
+
+use super::{Estimate, Merge};
+
+/// Estimate the harmonic mean of a sequence of positive numbers
+/// ("population").
+///
+/// This is useful for averaging rates, like speeds or throughputs.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::HarmonicMean;
+///
+/// let a: HarmonicMean = [1., 4., 4.].iter().map(|x| *x).collect();
+/// assert_eq!(a.mean(), 2.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HarmonicMean {
+    /// Sum of the reciprocals of the observations.
+    sum_reciprocal: f64,
+    /// Sample size.
+    n: u64,
+}
+
+impl HarmonicMean {
+    /// Create a new harmonic mean estimator.
+    #[inline]
+    pub fn new() -> HarmonicMean {
+        HarmonicMean { sum_reciprocal: 0., n: 0 }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = HarmonicMean::new();
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Estimate the harmonic mean of the population.
+    ///
+    /// Returns 0 if 0 was observed, which is the limit of the harmonic mean
+    /// when an observation approaches 0. Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        if self.n == 0 {
+            return 0.;
+        }
+        // The reciprocal of 0 is infinite, so the mean becomes 0.
+        self.n as f64 / self.sum_reciprocal
+    }
+}
+
+impl core::default::Default for HarmonicMean {
+    fn default() -> HarmonicMean {
+        HarmonicMean::new()
+    }
+}
+
+impl Estimate for HarmonicMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.sum_reciprocal += 1. / x;
+        self.n += 1;
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
+
+impl Merge for HarmonicMean {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{HarmonicMean, Merge};
+    ///
+    /// let sequence: &[f64] = &[1., 2., 4., 8., 16.];
+    /// let (left, right) = sequence.split_at(2);
+    /// let total: HarmonicMean = sequence.iter().map(|x| *x).collect();
+    /// let mut avg_left: HarmonicMean = left.iter().map(|x| *x).collect();
+    /// let avg_right: HarmonicMean = right.iter().map(|x| *x).collect();
+    /// avg_left.merge(&avg_right);
+    /// assert_eq!(total.mean(), avg_left.mean());
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &HarmonicMean) {
+        self.sum_reciprocal += other.sum_reciprocal;
+        self.n += other.n;
+    }
+}
+
+impl_from_iterator!(HarmonicMean);
+impl_extend!(HarmonicMean);
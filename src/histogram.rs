@@ -0,0 +1,1059 @@
+use core;
+
+use conv::{ConvAsUtil, ConvUtil};
+use num_traits::{PrimInt, Unsigned};
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use super::{Merge, WeightedVariance};
+
+/// Count the observations of a sequence of numbers falling into bins.
+///
+/// In contrast to the other estimators, the number of bins is chosen at
+/// runtime, so the memory used grows with it. This type is only available
+/// with the `std` feature.
+///
+/// Bin `i` covers the interval `[edges[i], edges[i + 1])`, except for the last
+/// bin, which also includes its upper edge. Observations outside of the range
+/// of the bins are counted separately as underflow or overflow, and NaN is
+/// ignored. Statistics like the quantiles are calculated from the observations
+/// inside the bins only. Accordingly, [`len`] is the number of observations
+/// inside the bins, while [`total`] also includes underflow and overflow.
+///
+/// The counts are stored as `u64` by default. To save memory, a smaller
+/// unsigned integer type `C` can be chosen using [`with_counter`]. The counts
+/// saturate at the maximum of `C` instead of overflowing, so the statistics
+/// are no longer exact once a count has saturated.
+///
+/// [`len`]: #method.len
+/// [`total`]: #method.total
+/// [`with_counter`]: #method.with_counter
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::DynHistogram;
+///
+/// let mut h = DynHistogram::with_bounds(0., 10., 5);
+/// for &x in &[1., 3., 3.5, 9., 12.] {
+///     h.add(x);
+/// }
+/// assert_eq!(h.counts(), &[1, 2, 0, 0, 1]);
+/// assert_eq!(h.overflow(), 1);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DynHistogram<C = u64> {
+    /// Bin edges, in increasing order. There is one more edge than bins.
+    edges: Vec<f64>,
+    /// Number of observations in each bin.
+    counts: Vec<C>,
+    /// Number of observations below the first edge.
+    underflow: C,
+    /// Number of observations above the last edge.
+    overflow: C,
+    /// How the bin edges are spaced.
+    spacing: Spacing,
+}
+
+/// How the bin edges of a histogram are spaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+enum Spacing {
+    /// The bins have equal width.
+    Linear,
+    /// The ratio of the edges of each bin is the same.
+    Logarithmic,
+    /// The edges were given explicitly.
+    Arbitrary,
+}
+
+impl DynHistogram {
+    /// Create a new histogram with `n_bins` bins of equal width covering the
+    /// interval `[min, max]`.
+    ///
+    /// Panics if `min` is not smaller than `max` or if `n_bins` is 0.
+    pub fn with_bounds(min: f64, max: f64, n_bins: usize) -> DynHistogram {
+        DynHistogram::from_spacing(linear_edges(min, max, n_bins), Spacing::Linear)
+    }
+
+    /// Create a new histogram with `n_bins` logarithmically spaced bins
+    /// covering the interval `[min, max]`.
+    ///
+    /// The ratio of the upper and lower edge is the same for every bin, which
+    /// is useful for observations spanning several orders of magnitude.
+    ///
+    /// Panics if `min` is not positive, if `min` is not smaller than `max` or
+    /// if `n_bins` is 0.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::DynHistogram;
+    ///
+    /// let mut h = DynHistogram::with_log_bounds(1., 1000., 3);
+    /// for &x in &[2., 20., 50., 200.] {
+    ///     h.add(x);
+    /// }
+    /// assert_eq!(h.counts(), &[1, 2, 1]);
+    /// ```
+    pub fn with_log_bounds(min: f64, max: f64, n_bins: usize) -> DynHistogram {
+        DynHistogram::from_spacing(log_edges(min, max, n_bins), Spacing::Logarithmic)
+    }
+
+    /// Create a new histogram with the given bin edges.
+    ///
+    /// There is one bin less than edges. This is useful if the bin edges are
+    /// not given by a formula, but by domain-specific thresholds.
+    ///
+    /// Panics if there are less than two edges or if the edges are not
+    /// strictly increasing.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::DynHistogram;
+    ///
+    /// let mut h = DynHistogram::from_edges(&[0., 100., 250., 1000.]);
+    /// for &x in &[20., 90., 240., 999., 1500.] {
+    ///     h.add(x);
+    /// }
+    /// assert_eq!(h.counts(), &[2, 1, 1]);
+    /// assert_eq!(h.overflow(), 1);
+    /// ```
+    pub fn from_edges(edges: &[f64]) -> DynHistogram {
+        check_edges(edges);
+        DynHistogram::from_spacing(edges.to_vec(), Spacing::Arbitrary)
+    }
+
+    /// Create a new histogram with `n_bins` bins of equal width between two
+    /// percentiles of `data`, and add `data` to it.
+    ///
+    /// The bins cover the interval from the `low_pct`th to the `high_pct`th
+    /// percentile, so the observations in the tails are counted as underflow
+    /// or overflow. This keeps a few outliers from stretching the bins, such
+    /// that almost all observations end up in the same bin. In contrast to the
+    /// other constructors, all observations are required up front, because
+    /// they are sorted to calculate the percentiles in a first pass.
+    ///
+    /// The percentiles are interpolated linearly between the sorted
+    /// observations, ignoring NaN. If they are equal, for example because all
+    /// observations are, the bins cover the interval `[x - 0.5, x + 0.5]`.
+    ///
+    /// Panics if `data` contains no numbers, if the percentiles are not
+    /// between 0 and 100, if `low_pct` is not smaller than `high_pct` or if
+    /// `n_bins` is 0.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::DynHistogram;
+    ///
+    /// let mut data: Vec<f64> = (0..100).map(f64::from).collect();
+    /// data.push(1e9);
+    /// let h = DynHistogram::from_data_percentiles(&data, 10, 0., 99.);
+    /// assert_eq!(h.edges()[0], 0.);
+    /// assert_eq!(h.edges()[10], 99.);
+    /// assert_eq!(h.overflow(), 1);
+    /// ```
+    pub fn from_data_percentiles(data: &[f64], n_bins: usize, low_pct: f64, high_pct: f64)
+        -> DynHistogram
+    {
+        assert!((0. ..=100.).contains(&low_pct) && (0. ..=100.).contains(&high_pct),
+            "percentiles must be between 0 and 100");
+        assert!(low_pct < high_pct, "low_pct must be smaller than high_pct");
+        let mut sorted: Vec<f64> = data.iter().cloned().filter(|x| !x.is_nan()).collect();
+        assert!(!sorted.is_empty(), "data must contain at least one number");
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let mut min = sorted_percentile(&sorted, low_pct);
+        let mut max = sorted_percentile(&sorted, high_pct);
+        if min == max {
+            min -= 0.5;
+            max += 0.5;
+        }
+        let mut h = DynHistogram::with_bounds(min, max, n_bins);
+        h.extend(data.iter().cloned());
+        h
+    }
+
+    /// Create a new empty histogram with the given edges.
+    fn from_spacing(edges: Vec<f64>, spacing: Spacing) -> DynHistogram {
+        DynHistogram {
+            counts: vec![0; edges.len() - 1],
+            edges,
+            underflow: 0,
+            overflow: 0,
+            spacing,
+        }
+    }
+
+    /// Convert the histogram to one storing the counts as `D`.
+    ///
+    /// Counts that are too large for `D` saturate at its maximum. This is
+    /// usually called right after creating the histogram, to choose a
+    /// smaller integer type than `u64` for the counts.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::DynHistogram;
+    ///
+    /// let mut h = DynHistogram::with_bounds(0., 1., 4).with_counter::<u8>();
+    /// for _ in 0..300 {
+    ///     h.add(0.1);
+    /// }
+    /// assert_eq!(h.counts(), &[255, 0, 0, 0]);
+    /// ```
+    pub fn with_counter<D: PrimInt + Unsigned>(self) -> DynHistogram<D> {
+        let convert = |count: u64| D::from(count).unwrap_or_else(D::max_value);
+        DynHistogram {
+            counts: self.counts.into_iter().map(convert).collect(),
+            edges: self.edges,
+            underflow: convert(self.underflow),
+            overflow: convert(self.overflow),
+            spacing: self.spacing,
+        }
+    }
+}
+
+impl<C: PrimInt + Unsigned> DynHistogram<C> {
+    /// Reset the histogram to an empty sample.
+    ///
+    /// The bin edges are kept and no memory is reallocated.
+    #[inline]
+    pub fn clear(&mut self) {
+        for count in &mut self.counts {
+            *count = C::zero();
+        }
+        self.underflow = C::zero();
+        self.overflow = C::zero();
+    }
+
+    /// Add an observation to the histogram.
+    ///
+    /// The count saturates at the maximum of `C`.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        if let Some(count) = self.count_mut(x) {
+            *count = count.saturating_add(C::one());
+        }
+    }
+
+    /// Add `n` observations of the same value to the histogram.
+    ///
+    /// This is equivalent to calling `add(x)` `n` times, but takes constant
+    /// time, which is useful for data that was already aggregated into
+    /// `(value, count)` pairs. The count saturates at the maximum of `C`.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::DynHistogram;
+    ///
+    /// let mut h = DynHistogram::with_bounds(0., 10., 2);
+    /// h.add_n(3., 1000);
+    /// h.add_n(12., 5);
+    /// assert_eq!(h.counts(), &[1000, 0]);
+    /// assert_eq!(h.overflow(), 5);
+    /// assert_eq!(h.total(), 1005);
+    /// ```
+    #[inline]
+    pub fn add_n(&mut self, x: f64, n: u64) {
+        let n = C::from(n).unwrap_or_else(C::max_value);
+        if let Some(count) = self.count_mut(x) {
+            *count = count.saturating_add(n);
+        }
+    }
+
+    /// Find the count of the bin containing `x`, the underflow or the
+    /// overflow.
+    ///
+    /// Returns `None` for NaN.
+    #[inline]
+    fn count_mut(&mut self, x: f64) -> Option<&mut C> {
+        if x.is_nan() {
+            return None;
+        }
+        Some(match find_bin(&self.edges, self.spacing, x) {
+            Ok(i) => &mut self.counts[i],
+            Err(BinError::Underflow) => &mut self.underflow,
+            Err(BinError::Overflow) => &mut self.overflow,
+        })
+    }
+
+    /// Return the bin edges, in increasing order.
+    ///
+    /// There is one more edge than bins.
+    #[inline]
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// Return the number of observations in each bin.
+    #[inline]
+    pub fn counts(&self) -> &[C] {
+        &self.counts
+    }
+
+    /// Iterate over the bins, yielding their lower edge, upper edge and count.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::DynHistogram;
+    ///
+    /// let mut h = DynHistogram::with_bounds(0., 3., 3);
+    /// h.extend([0.5, 1.5, 1.7, 2.9].iter().map(|x| *x));
+    /// let mut total = 0;
+    /// for (lower, upper, count) in h.bins() {
+    ///     println!("[{}, {}): {}", lower, upper, count);
+    ///     total += count;
+    /// }
+    /// assert_eq!(total, h.len());
+    /// ```
+    #[inline]
+    pub fn bins<'a>(&'a self) -> impl Iterator<Item=(f64, f64, C)> + 'a {
+        self.edges.windows(2)
+            .zip(&self.counts)
+            .map(|(w, &count)| (w[0], w[1], count))
+    }
+
+    /// Return the number of bins.
+    #[inline]
+    pub fn n_bins(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// Return the number of observations below the first edge.
+    #[inline]
+    pub fn underflow(&self) -> C {
+        self.underflow
+    }
+
+    /// Return the number of observations above the last edge.
+    #[inline]
+    pub fn overflow(&self) -> C {
+        self.overflow
+    }
+
+    /// Return the number of observations inside the bins.
+    ///
+    /// Underflow and overflow are not included, like in the statistics
+    /// calculated from the bins. See [`total`] for the full sample size.
+    ///
+    /// Saturates at `u64::MAX`, like the counts.
+    ///
+    /// [`total`]: #method.total
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.counts.iter()
+            .fold(0, |len, &count| len.saturating_add(count_to_u64(count)))
+    }
+
+    /// Determine whether there are no observations inside the bins.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the sample size, including underflow and overflow.
+    ///
+    /// Saturates at `u64::MAX`, like the counts.
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self.len()
+            .saturating_add(count_to_u64(self.underflow))
+            .saturating_add(count_to_u64(self.overflow))
+    }
+
+    /// Iterate over the counts of the bins, converted to `f64`.
+    #[inline]
+    fn masses<'a>(&'a self) -> impl Iterator<Item=f64> + Clone + 'a {
+        self.counts.iter().map(|count| count.to_f64().unwrap())
+    }
+
+    /// Estimate the p-quantile of the observations inside the bins.
+    ///
+    /// The position inside the bin containing the p-quantile is interpolated
+    /// linearly, assuming the observations are spread uniformly over the bin.
+    /// For `p = 0` this is the lower edge of the first non-empty bin and for
+    /// `p = 1` the upper edge of the last non-empty bin.
+    ///
+    /// Returns NaN if all bins are empty. Panics if `p` is not between 0 and 1.
+    pub fn quantile(&self, p: f64) -> f64 {
+        quantile(&self.edges, self.masses(), p)
+    }
+
+    /// Estimate the fraction of the observations inside the bins that is
+    /// below `x`.
+    ///
+    /// This is the inverse of [`quantile`]: The counts of the bins below `x`
+    /// are summed, and the position of `x` inside its bin is interpolated
+    /// linearly. Returns 0 if `x` is below the first edge and 1 if it is above
+    /// the last edge. Returns NaN if all bins are empty or if `x` is NaN.
+    ///
+    /// [`quantile`]: #method.quantile
+    pub fn percentile_rank(&self, x: f64) -> f64 {
+        percentile_rank(&self.edges, self.masses(), x)
+    }
+
+    /// Estimate the mean of the observations inside the bins.
+    ///
+    /// All observations in a bin are assumed to be at its midpoint, so this is
+    /// only an approximation, whose error depends on the width of the bins.
+    /// Returns 0 if all bins are empty.
+    pub fn mean(&self) -> f64 {
+        moments(&self.edges, self.masses()).mean()
+    }
+
+    /// Estimate the population variance of the observations inside the bins.
+    ///
+    /// All observations in a bin are assumed to be at its midpoint, so this is
+    /// only an approximation, whose error depends on the width of the bins.
+    /// For bins of equal width `h`, the bias can be reduced by subtracting
+    /// `h*h/12` (Sheppard's correction). Returns 0 if all bins are empty.
+    pub fn variance(&self) -> f64 {
+        moments(&self.edges, self.masses()).population_variance()
+    }
+
+    /// Estimate the Shannon entropy of the distribution of the observations
+    /// over the bins, in bits.
+    ///
+    /// This is `-Σ p_i log2(p_i)`, where `p_i` is the fraction of the
+    /// observations inside the bins falling into bin `i`. Empty bins are
+    /// skipped. Returns 0 if all bins are empty.
+    pub fn entropy(&self) -> f64 {
+        entropy(self.masses())
+    }
+
+    /// Calculate the probability density in each bin.
+    ///
+    /// This is the fraction of the observations inside the bins falling into
+    /// the bin, divided by its width, so the density integrates to 1. Returns
+    /// zeros if all bins are empty.
+    pub fn density(&self) -> Vec<f64> {
+        density(&self.edges, self.masses())
+    }
+
+    /// Calculate the cumulative distribution at the upper edge of each bin.
+    ///
+    /// This is the fraction of the observations inside the bins falling into
+    /// the bin or any bin before it, so the last value is 1. Returns zeros if
+    /// all bins are empty.
+    pub fn cdf(&self) -> Vec<f64> {
+        cdf(self.masses())
+    }
+
+    /// Render the histogram as a text bar chart.
+    ///
+    /// There is one line for each bin, with its range, its count and a bar of
+    /// `#` characters, and additional lines for the underflow and the
+    /// overflow if they are not 0. The longest bar is `width` characters long,
+    /// and the others are scaled accordingly. The columns are padded, so they
+    /// are aligned with a fixed-width font.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::DynHistogram;
+    ///
+    /// let mut h = DynHistogram::with_bounds(0., 10., 4);
+    /// h.extend([1., 3., 3.5, 4., 9., 12.].iter().map(|x| *x));
+    /// assert_eq!(h.render_ascii(6), "\
+    /// [0, 2.5)  1 ##
+    /// [2.5, 5)  3 ######
+    /// [5, 7.5)  0
+    /// [7.5, 10] 1 ##
+    /// > 10      1 ##
+    /// ");
+    /// ```
+    pub fn render_ascii(&self, width: usize) -> String {
+        let n_bins = self.n_bins();
+        let mut rows = Vec::with_capacity(n_bins + 2);
+        if !self.underflow.is_zero() {
+            rows.push((format!("< {}", self.edges[0]), self.underflow));
+        }
+        for (i, (lower, upper, count)) in self.bins().enumerate() {
+            let close = if i + 1 == n_bins { ']' } else { ')' };
+            rows.push((format!("[{}, {}{}", lower, upper, close), count));
+        }
+        if !self.overflow.is_zero() {
+            rows.push((format!("> {}", self.edges[n_bins]), self.overflow));
+        }
+        let counts: Vec<u64> = rows.iter()
+            .map(|&(_, count)| count_to_u64(count))
+            .collect();
+        let label_width = rows.iter().map(|row| row.0.len()).max().unwrap();
+        let count_width = counts.iter().map(|count| count.to_string().len()).max().unwrap();
+        let max = counts.iter().cloned().max().unwrap();
+        let mut chart = String::new();
+        for ((label, _), &count) in rows.iter().zip(&counts) {
+            let bar: usize = if max == 0 {
+                0
+            } else {
+                (count.approx_as::<f64>().unwrap() / max.approx_as::<f64>().unwrap()
+                    * width.approx_as::<f64>().unwrap()).round().approx().unwrap()
+            };
+            let line = format!("{:<lw$} {:>cw$} {}", label, count, "#".repeat(bar),
+                lw = label_width, cw = count_width);
+            chart.push_str(line.trim_end());
+            chart.push('\n');
+        }
+        chart
+    }
+}
+
+/// The reason an observation does not fall into any bin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinError {
+    /// The observation is below the first edge.
+    Underflow,
+    /// The observation is above the last edge.
+    Overflow,
+}
+
+impl<C: PrimInt + Unsigned> core::iter::Extend<f64> for DynHistogram<C> {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=f64>
+    {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+impl<C: PrimInt + Unsigned> Merge for DynHistogram<C> {
+    /// Merge another histogram into this one.
+    ///
+    /// The counts saturate at the maximum of `C`. Panics if the bin edges of
+    /// the histograms differ.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{DynHistogram, Merge};
+    ///
+    /// let mut a = DynHistogram::with_bounds(0., 4., 4);
+    /// let mut b = DynHistogram::with_bounds(0., 4., 4);
+    /// a.extend([0.5, 1.5, 5.].iter().map(|x| *x));
+    /// b.extend([1.5, 3.5, -1.].iter().map(|x| *x));
+    /// a.merge(&b);
+    /// assert_eq!(a.counts(), &[1, 2, 0, 1]);
+    /// assert_eq!(a.underflow(), 1);
+    /// assert_eq!(a.overflow(), 1);
+    /// ```
+    fn merge(&mut self, other: &DynHistogram<C>) {
+        assert!(self.edges == other.edges, "bin edges must be the same");
+        for (count, &other_count) in self.counts.iter_mut().zip(&other.counts) {
+            *count = count.saturating_add(other_count);
+        }
+        self.underflow = self.underflow.saturating_add(other.underflow);
+        self.overflow = self.overflow.saturating_add(other.overflow);
+    }
+}
+
+/// Convert a count to `u64`, saturating for wider counters.
+#[inline]
+fn count_to_u64<C: PrimInt>(count: C) -> u64 {
+    count.to_u64().unwrap_or(u64::MAX)
+}
+
+/// Calculate `n_bins + 1` edges of equal distance from `min` to `max`.
+fn linear_edges(min: f64, max: f64, n_bins: usize) -> Vec<f64> {
+    assert!(min < max, "min must be smaller than max");
+    assert!(n_bins > 0, "there must be at least one bin");
+    let n = n_bins.approx_as::<f64>().unwrap();
+    let mut edges: Vec<f64> = (0..n_bins)
+        .map(|i| min + (max - min) * i.approx_as::<f64>().unwrap() / n)
+        .collect();
+    edges.push(max);
+    edges
+}
+
+/// Calculate the `pct`th percentile of sorted numbers, interpolating linearly
+/// between them.
+fn sorted_percentile(sorted: &[f64], pct: f64) -> f64 {
+    let rank = pct / 100. * (sorted.len() - 1).approx_as::<f64>().unwrap();
+    let i: usize = rank.floor().approx().unwrap();
+    if i + 1 >= sorted.len() {
+        return sorted[sorted.len() - 1];
+    }
+    let t = rank - rank.floor();
+    sorted[i] + t * (sorted[i + 1] - sorted[i])
+}
+
+/// Calculate `n_bins + 1` edges with equal ratio from `min` to `max`.
+fn log_edges(min: f64, max: f64, n_bins: usize) -> Vec<f64> {
+    assert!(min > 0., "min must be positive");
+    assert!(min < max, "min must be smaller than max");
+    assert!(n_bins > 0, "there must be at least one bin");
+    let n = n_bins.approx_as::<f64>().unwrap();
+    let mut edges: Vec<f64> = (0..n_bins)
+        .map(|i| min * (max / min).powf(i.approx_as::<f64>().unwrap() / n))
+        .collect();
+    edges.push(max);
+    edges
+}
+
+/// Check that there are at least two edges and that they are strictly
+/// increasing.
+fn check_edges(edges: &[f64]) {
+    assert!(edges.len() >= 2, "there must be at least two edges");
+    assert!(edges.windows(2).all(|w| w[0] < w[1]),
+            "edges must be strictly increasing");
+}
+
+/// Find the index of the bin containing `x`.
+fn find_bin(edges: &[f64], spacing: Spacing, x: f64) -> Result<usize, BinError> {
+    let n_bins = edges.len() - 1;
+    let (min, max) = (edges[0], edges[n_bins]);
+    if x < min {
+        return Err(BinError::Underflow);
+    }
+    if x > max {
+        return Err(BinError::Overflow);
+    }
+    let t = match spacing {
+        Spacing::Linear => (x - min) / (max - min),
+        Spacing::Logarithmic => (x / min).ln() / (max / min).ln(),
+        Spacing::Arbitrary => {
+            let i = edges.partition_point(|&edge| edge <= x) - 1;
+            return Ok(core::cmp::min(i, n_bins - 1));
+        }
+    } * n_bins.approx_as::<f64>().unwrap();
+    let mut i: usize = t.floor().approx().unwrap();
+    i = core::cmp::min(i, n_bins - 1);
+    // Correct for rounding errors in the calculation of the edges.
+    while i > 0 && x < edges[i] {
+        i -= 1;
+    }
+    while i < n_bins - 1 && x >= edges[i + 1] {
+        i += 1;
+    }
+    Ok(i)
+}
+
+/// Estimate the p-quantile of the masses of the bins, interpolating linearly
+/// inside the bins.
+fn quantile<I>(edges: &[f64], masses: I, p: f64) -> f64
+    where I: Iterator<Item=f64> + Clone
+{
+    assert!((0. ..=1.).contains(&p), "p must be between 0 and 1");
+    let target = p * masses.clone().sum::<f64>();
+    let mut cumulative = 0.;
+    for (i, mass) in masses.enumerate() {
+        if mass == 0. {
+            continue;
+        }
+        if cumulative + mass >= target {
+            let (lower, upper) = (edges[i], edges[i + 1]);
+            let fraction = (target - cumulative) / mass;
+            return lower + fraction * (upper - lower);
+        }
+        cumulative += mass;
+    }
+    f64::NAN
+}
+
+/// Estimate the fraction of the masses of the bins below `x`, interpolating
+/// linearly inside the bins.
+fn percentile_rank<I>(edges: &[f64], masses: I, x: f64) -> f64
+    where I: Iterator<Item=f64> + Clone
+{
+    let total: f64 = masses.clone().sum();
+    if total == 0. || x.is_nan() {
+        return f64::NAN;
+    }
+    let mut cumulative = 0.;
+    for (w, mass) in edges.windows(2).zip(masses) {
+        if x < w[0] {
+            break;
+        }
+        if x < w[1] {
+            cumulative += mass * (x - w[0]) / (w[1] - w[0]);
+            break;
+        }
+        cumulative += mass;
+    }
+    cumulative / total
+}
+
+/// Calculate the moments of the masses of the bins, assuming all of the mass
+/// of a bin is at its midpoint.
+fn moments<I>(edges: &[f64], masses: I) -> WeightedVariance
+    where I: Iterator<Item=f64>
+{
+    edges.windows(2)
+        .zip(masses)
+        .map(|(w, mass)| (0.5 * (w[0] + w[1]), mass))
+        .filter(|&(_, mass)| mass > 0.)
+        .collect()
+}
+
+/// Calculate the Shannon entropy of the distribution of the masses over the
+/// bins, in bits.
+fn entropy<I>(masses: I) -> f64
+    where I: Iterator<Item=f64> + Clone
+{
+    let total: f64 = masses.clone().sum();
+    if total == 0. {
+        return 0.;
+    }
+    -masses
+        .filter(|&mass| mass > 0.)
+        .map(|mass| {
+            let p = mass / total;
+            p * p.log2()
+        })
+        .sum::<f64>()
+}
+
+/// Calculate the probability density in each bin.
+fn density<I>(edges: &[f64], masses: I) -> Vec<f64>
+    where I: Iterator<Item=f64> + Clone
+{
+    let total: f64 = masses.clone().sum();
+    if total == 0. {
+        return vec![0.; edges.len() - 1];
+    }
+    edges.windows(2)
+        .zip(masses)
+        .map(|(w, mass)| mass / total / (w[1] - w[0]))
+        .collect()
+}
+
+/// Calculate the cumulative distribution at the upper edge of each bin.
+fn cdf<I>(masses: I) -> Vec<f64>
+    where I: Iterator<Item=f64> + Clone
+{
+    let total: f64 = masses.clone().sum();
+    if total == 0. {
+        return masses.map(|_| 0.).collect();
+    }
+    let mut cumulative = 0.;
+    masses
+        .map(|mass| {
+            cumulative += mass;
+            cumulative / total
+        })
+        .collect()
+}
+
+/// Sum the weights of the observations of a sequence of numbers falling into
+/// bins.
+///
+/// This is like [`DynHistogram`], but each observation has a non-negative
+/// weight, which is added to its bin instead of incrementing a count. This is
+/// useful for Monte Carlo simulations with importance sampling. The
+/// statistics are calculated from the weighted masses of the bins. This type
+/// is only available with the `std` feature.
+///
+/// [`DynHistogram`]: ./struct.DynHistogram.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::WeightedHistogram;
+///
+/// let mut h = WeightedHistogram::with_bounds(0., 10., 5);
+/// for &(x, w) in &[(1., 0.5), (3., 2.), (3.5, 1.), (9., 0.25), (12., 1.)] {
+///     h.add_weighted(x, w);
+/// }
+/// assert_eq!(h.weights(), &[0.5, 3., 0., 0., 0.25]);
+/// assert_eq!(h.overflow(), 1.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeightedHistogram {
+    /// Bin edges, in increasing order. There is one more edge than bins.
+    edges: Vec<f64>,
+    /// Sum of the weights of the observations in each bin.
+    weights: Vec<f64>,
+    /// Sum of the weights of the observations below the first edge.
+    underflow: f64,
+    /// Sum of the weights of the observations above the last edge.
+    overflow: f64,
+    /// How the bin edges are spaced.
+    spacing: Spacing,
+    /// Sample size, including underflow and overflow.
+    n: u64,
+    /// Number of observations inside the bins.
+    n_binned: u64,
+}
+
+impl WeightedHistogram {
+    /// Create a new weighted histogram with `n_bins` bins of equal width
+    /// covering the interval `[min, max]`.
+    ///
+    /// Panics if `min` is not smaller than `max` or if `n_bins` is 0.
+    pub fn with_bounds(min: f64, max: f64, n_bins: usize) -> WeightedHistogram {
+        WeightedHistogram::from_spacing(linear_edges(min, max, n_bins), Spacing::Linear)
+    }
+
+    /// Create a new weighted histogram with `n_bins` logarithmically spaced
+    /// bins covering the interval `[min, max]`.
+    ///
+    /// Panics if `min` is not positive, if `min` is not smaller than `max` or
+    /// if `n_bins` is 0.
+    pub fn with_log_bounds(min: f64, max: f64, n_bins: usize) -> WeightedHistogram {
+        WeightedHistogram::from_spacing(log_edges(min, max, n_bins), Spacing::Logarithmic)
+    }
+
+    /// Create a new weighted histogram with the given bin edges.
+    ///
+    /// Panics if there are less than two edges or if the edges are not
+    /// strictly increasing.
+    pub fn from_edges(edges: &[f64]) -> WeightedHistogram {
+        check_edges(edges);
+        WeightedHistogram::from_spacing(edges.to_vec(), Spacing::Arbitrary)
+    }
+
+    /// Create a new empty weighted histogram with the given edges.
+    fn from_spacing(edges: Vec<f64>, spacing: Spacing) -> WeightedHistogram {
+        WeightedHistogram {
+            weights: vec![0.; edges.len() - 1],
+            edges,
+            underflow: 0.,
+            overflow: 0.,
+            spacing,
+            n: 0,
+            n_binned: 0,
+        }
+    }
+
+    /// Reset the histogram to an empty sample.
+    ///
+    /// The bin edges are kept and no memory is reallocated.
+    #[inline]
+    pub fn clear(&mut self) {
+        for weight in &mut self.weights {
+            *weight = 0.;
+        }
+        self.underflow = 0.;
+        self.overflow = 0.;
+        self.n = 0;
+        self.n_binned = 0;
+    }
+
+    /// Add an observation with unit weight to the histogram.
+    ///
+    /// `add(x)` is equivalent to `add_weighted(x, 1.)`.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        self.add_weighted(x, 1.);
+    }
+
+    /// Add an observation with the given weight to the histogram.
+    ///
+    /// NaN observations are ignored. Panics if the weight is negative or NaN.
+    #[inline]
+    pub fn add_weighted(&mut self, x: f64, weight: f64) {
+        assert!(weight >= 0., "weight must not be negative");
+        if x.is_nan() {
+            return;
+        }
+        match find_bin(&self.edges, self.spacing, x) {
+            Ok(i) => {
+                self.weights[i] += weight;
+                self.n_binned += 1;
+            }
+            Err(BinError::Underflow) => self.underflow += weight,
+            Err(BinError::Overflow) => self.overflow += weight,
+        }
+        self.n += 1;
+    }
+
+    /// Return the bin edges, in increasing order.
+    ///
+    /// There is one more edge than bins.
+    #[inline]
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// Return the sum of the weights of the observations in each bin.
+    #[inline]
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Iterate over the bins, yielding their lower edge, upper edge and sum
+    /// of weights.
+    #[inline]
+    pub fn bins<'a>(&'a self) -> impl Iterator<Item=(f64, f64, f64)> + 'a {
+        self.edges.windows(2)
+            .zip(&self.weights)
+            .map(|(w, &weight)| (w[0], w[1], weight))
+    }
+
+    /// Return the number of bins.
+    #[inline]
+    pub fn n_bins(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Return the sum of the weights of the observations below the first
+    /// edge.
+    #[inline]
+    pub fn underflow(&self) -> f64 {
+        self.underflow
+    }
+
+    /// Return the sum of the weights of the observations above the last edge.
+    #[inline]
+    pub fn overflow(&self) -> f64 {
+        self.overflow
+    }
+
+    /// Return the number of observations inside the bins, regardless of
+    /// their weights.
+    ///
+    /// Like for [`DynHistogram::len`], underflow and overflow are not
+    /// included. See [`total`] for the full sample size.
+    ///
+    /// [`DynHistogram::len`]: ./struct.DynHistogram.html#method.len
+    /// [`total`]: #method.total
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n_binned
+    }
+
+    /// Determine whether there are no observations inside the bins.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n_binned == 0
+    }
+
+    /// Return the sample size, including underflow and overflow.
+    ///
+    /// This is the number of observations, regardless of their weights.
+    #[inline]
+    pub fn total(&self) -> u64 {
+        self.n
+    }
+
+    /// Return the sum of the weights, including underflow and overflow.
+    #[inline]
+    pub fn sum_weights(&self) -> f64 {
+        self.weights.iter().sum::<f64>() + self.underflow + self.overflow
+    }
+
+    /// Estimate the p-quantile of the weighted observations inside the bins.
+    ///
+    /// This is the point below which the fraction `p` of the weights inside
+    /// the bins lies, interpolating linearly inside the bin like
+    /// [`DynHistogram::quantile`].
+    ///
+    /// Returns NaN if all bins are empty. Panics if `p` is not between 0 and 1.
+    ///
+    /// [`DynHistogram::quantile`]: ./struct.DynHistogram.html#method.quantile
+    pub fn quantile(&self, p: f64) -> f64 {
+        quantile(&self.edges, self.weights.iter().cloned(), p)
+    }
+
+    /// Estimate the fraction of the weights inside the bins that is below
+    /// `x`.
+    ///
+    /// This is the inverse of [`quantile`], interpolating linearly inside the
+    /// bin like [`DynHistogram::percentile_rank`].
+    ///
+    /// Returns NaN if all bins are empty or if `x` is NaN.
+    ///
+    /// [`quantile`]: #method.quantile
+    /// [`DynHistogram::percentile_rank`]: ./struct.DynHistogram.html#method.percentile_rank
+    pub fn percentile_rank(&self, x: f64) -> f64 {
+        percentile_rank(&self.edges, self.weights.iter().cloned(), x)
+    }
+
+    /// Estimate the weighted mean of the observations inside the bins.
+    ///
+    /// All observations in a bin are assumed to be at its midpoint. Returns 0
+    /// if all bins are empty.
+    pub fn mean(&self) -> f64 {
+        moments(&self.edges, self.weights.iter().cloned()).mean()
+    }
+
+    /// Estimate the weighted population variance of the observations inside
+    /// the bins.
+    ///
+    /// All observations in a bin are assumed to be at its midpoint. Returns 0
+    /// if all bins are empty.
+    pub fn variance(&self) -> f64 {
+        moments(&self.edges, self.weights.iter().cloned()).population_variance()
+    }
+
+    /// Estimate the Shannon entropy of the distribution of the weights over
+    /// the bins, in bits.
+    ///
+    /// Returns 0 if all bins are empty.
+    pub fn entropy(&self) -> f64 {
+        entropy(self.weights.iter().cloned())
+    }
+
+    /// Calculate the probability density in each bin.
+    ///
+    /// This is the fraction of the weights inside the bins falling into the
+    /// bin, divided by its width. Returns zeros if all bins are empty.
+    pub fn density(&self) -> Vec<f64> {
+        density(&self.edges, self.weights.iter().cloned())
+    }
+
+    /// Calculate the cumulative distribution at the upper edge of each bin.
+    ///
+    /// This is the fraction of the weights inside the bins falling into the
+    /// bin or any bin before it. Returns zeros if all bins are empty.
+    pub fn cdf(&self) -> Vec<f64> {
+        cdf(self.weights.iter().cloned())
+    }
+}
+
+impl core::iter::Extend<(f64, f64)> for WeightedHistogram {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        for (x, weight) in iter {
+            self.add_weighted(x, weight);
+        }
+    }
+}
+
+impl Merge for WeightedHistogram {
+    /// Merge another histogram into this one.
+    ///
+    /// Panics if the bin edges of the histograms differ.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{WeightedHistogram, Merge};
+    ///
+    /// let mut a = WeightedHistogram::with_bounds(0., 4., 4);
+    /// let mut b = WeightedHistogram::with_bounds(0., 4., 4);
+    /// a.extend([(0.5, 1.), (1.5, 0.5)].iter().map(|&x| x));
+    /// b.extend([(1.5, 0.25), (3.5, 2.)].iter().map(|&x| x));
+    /// a.merge(&b);
+    /// assert_eq!(a.weights(), &[1., 0.75, 0., 2.]);
+    /// assert_eq!(a.len(), 4);
+    /// ```
+    fn merge(&mut self, other: &WeightedHistogram) {
+        assert!(self.edges == other.edges, "bin edges must be the same");
+        for (weight, other_weight) in self.weights.iter_mut().zip(&other.weights) {
+            *weight += *other_weight;
+        }
+        self.underflow += other.underflow;
+        self.overflow += other.overflow;
+        self.n += other.n;
+        self.n_binned += other.n_binned;
+    }
+}
@@ -30,12 +30,31 @@
 //! ## Estimators
 //!
 //! * Mean ([`Mean`]) and its error ([`MeanWithError`]).
-//! * Weighted mean ([`WeightedMean`]) and its error
-//!   ([`WeightedMeanWithError`]).
+//! * Geometric mean ([`GeometricMean`]) and harmonic mean ([`HarmonicMean`]).
+//! * Sum ([`Sum`]) and product ([`Product`]).
+//! * Weighted mean ([`WeightedMean`]), its error
+//!   ([`WeightedMeanWithError`]) and weighted variance ([`WeightedVariance`]).
 //! * Variance ([`Variance`]), skewness ([`Skewness`]) and kurtosis
 //!   ([`Kurtosis`]).
-//! * Quantiles ([`Quantile`]).
-//! * Minimum ([`Min`]) and maximum ([`Max`]).
+//! * Central moments of arbitrary order ([`Moments`]).
+//! * Covariance ([`Covariance`]), correlation ([`Correlation`]), linear
+//!   regression ([`LinearRegression`]) and autocorrelation
+//!   ([`Autocorrelation`]).
+//! * Quantiles ([`Quantile`], [`Quantiles`], [`TDigest`], [`GkSummary`]),
+//!   interquartile range ([`InterquartileRange`]) and median absolute
+//!   deviation ([`MedianAbsoluteDeviation`]).
+//! * Minimum ([`Min`]), maximum ([`Max`]) and range ([`Range`]).
+//! * Trimmed mean ([`TrimmedMean`]) and Winsorized mean ([`WinsorizedMean`]).
+//! * Exponentially weighted moving average ([`ExpMovingAverage`]) and
+//!   variance ([`ExpMovingVariance`]).
+//! * Histogram with a number of bins chosen at runtime ([`DynHistogram`]) and
+//!   its weighted variant ([`WeightedHistogram`]).
+//! * Gini coefficient ([`Gini`]).
+//! * Statistics over a sliding window of the last observations
+//!   ([`Sliding`]).
+//! * Uniform random sample of fixed size ([`ReservoirSample`]).
+//! * Number of distinct elements ([`DistinctCount`]) and most frequent items
+//!   ([`FrequentItems`]).
 //!
 //! ## Estimating several statistics at once
 //!
@@ -50,36 +69,206 @@
 //! Note that calculating moments requires calculating the lower moments, so you
 //! only need to include the highest moment in your struct.
 //!
+//! ## Floating-point types
+//!
+//! The moment estimators ([`Mean`], [`Variance`], [`Skewness`], [`Kurtosis`]
+//! and [`Moments`]) are generic over the floating-point type of the
+//! observations, so they can be used with `f32` as well. The type defaults to
+//! `f64`, e.g. `Mean` is the same as `Mean<f64>`. All other estimators use
+//! `f64`.
+//!
+//! The sample size is counted as `u64`, but the calculations convert it to the
+//! floating-point type. Sample sizes above 2^53 for `f64` (or 2^24 for `f32`)
+//! cannot be represented exactly, so the estimates degrade: The mean stops
+//! changing, because the increments of the observations are lost to rounding.
+//! In debug builds, the moment estimators panic when this limit is exceeded.
+//! For longer sequences, split them into parts, for example per time period,
+//! and estimate the statistics for each of them separately.
+//!
+//! The moment estimators also implement `PartialEq`. Note that this compares
+//! their internal states using `==` on the floating-point fields, so it checks
+//! for exact equality and not for equality within some tolerance (see
+//! [`assert_almost_eq`] for the latter).
+//!
+//! ## Optional features
+//!
+//! * `std`: Enabled by default. Provide the estimators that need to allocate
+//!   memory, like [`DynHistogram`], [`WeightedHistogram`], [`Gini`],
+//!   [`TrimmedMean`], [`WinsorizedMean`], [`ReservoirSample`],
+//!   [`DistinctCount`], [`FrequentItems`], [`Quantiles`], [`WarmupQuantile`],
+//!   [`TDigest`], [`GkSummary`], [`Autocorrelation`] and [`Sliding`]. Without
+//!   it, the crate is `no_std`.
+//! * `libm`: Use the `libm` crate for the floating-point functions `sqrt`,
+//!   `ln` and `exp`, which are not available in `core`. This is only needed
+//!   without `std`. The following items require either `std` or `libm`, all
+//!   others are also available without both of them:
+//!   * [`GeometricMean`], [`Product`] and [`welch_t`].
+//!   * The standard deviations, coefficients of variation and standard errors
+//!     of [`Variance`], [`Skewness`] and [`Kurtosis`], as well as
+//!     `confidence_interval`, `confidence_interval_t`, `z_score` and
+//!     `standardize_slice` of `Variance`, and their `Display`
+//!     implementations.
+//!   * `skewness`, `sample_skewness` and `error_skewness` of `Skewness` and
+//!     `Kurtosis`, as well as `error_kurtosis` and `summary` of `Kurtosis`.
+//!     `Skewness` only implements `Estimate`, `FromIterator` and `Extend`
+//!     with one of the features, because its estimate is the skewness.
+//!   * `standardized_moment` of [`Moments`] and [`RawMoments`], and therefore
+//!     the [`assert_estimator_almost_eq`] macro.
+//!   * `error` of [`WeightedMeanWithError`] and [`WeightedVariance`].
+//!   * `correlation` of [`Correlation`] and `r_squared` of
+//!     [`LinearRegression`].
+//!   * `from_half_life` of [`ExpMovingAverage`] and [`ExpMovingVariance`],
+//!     and `std_dev` of `ExpMovingVariance`.
+//! * `serde`: Implement `Serialize` and `Deserialize` for all estimators, so
+//!   partial results can be sent to another process and merged there.
+//! * `rayon`: Implement `FromParallelIterator` for [`Mean`], [`Variance`],
+//!   [`Skewness`] and [`Kurtosis`], so they can be collected from a parallel
+//!   iterator. The chunks of each thread are combined using `merge()`.
+//! * `ndarray`: Provide `from_array` for [`Mean`], [`Variance`], [`Skewness`]
+//!   and [`Kurtosis`], to estimate the statistics of an `ndarray::ArrayView1`
+//!   without copying it, and [`mean_axis`] for reducing a two-dimensional
+//!   array to one estimator per column or row.
+//!
 //!
 //! [`Mean`]: ./struct.Mean.html
 //! [`MeanWithError`]: ./type.MeanWithError.html
+//! [`GeometricMean`]: ./struct.GeometricMean.html
+//! [`HarmonicMean`]: ./struct.HarmonicMean.html
+//! [`Sum`]: ./struct.Sum.html
+//! [`Product`]: ./struct.Product.html
 //! [`WeightedMean`]: ./struct.WeightedMean.html
 //! [`WeightedMeanWithError`]: ./struct.WeightedMeanWithError.html
+//! [`WeightedVariance`]: ./struct.WeightedVariance.html
 //! [`Variance`]: ./struct.Variance.html
 //! [`Skewness`]: ./struct.Skewness.html
 //! [`Kurtosis`]: ./struct.Kurtosis.html
+//! [`Moments`]: ./struct.Moments.html
+//! [`Covariance`]: ./struct.Covariance.html
+//! [`Correlation`]: ./struct.Correlation.html
+//! [`LinearRegression`]: ./struct.LinearRegression.html
+//! [`Autocorrelation`]: ./struct.Autocorrelation.html
 //! [`Quantile`]: ./struct.Quantile.html
+//! [`Quantiles`]: ./struct.Quantiles.html
+//! [`WarmupQuantile`]: ./struct.WarmupQuantile.html
+//! [`TDigest`]: ./struct.TDigest.html
+//! [`GkSummary`]: ./struct.GkSummary.html
+//! [`InterquartileRange`]: ./struct.InterquartileRange.html
+//! [`MedianAbsoluteDeviation`]: ./struct.MedianAbsoluteDeviation.html
 //! [`Min`]: ./struct.Min.html
 //! [`Max`]: ./struct.Max.html
+//! [`Range`]: ./struct.Range.html
+//! [`TrimmedMean`]: ./struct.TrimmedMean.html
+//! [`WinsorizedMean`]: ./struct.WinsorizedMean.html
+//! [`ExpMovingAverage`]: ./struct.ExpMovingAverage.html
+//! [`ExpMovingVariance`]: ./struct.ExpMovingVariance.html
+//! [`DynHistogram`]: ./struct.DynHistogram.html
+//! [`WeightedHistogram`]: ./struct.WeightedHistogram.html
+//! [`Gini`]: ./struct.Gini.html
+//! [`Sliding`]: ./struct.Sliding.html
+//! [`ReservoirSample`]: ./struct.ReservoirSample.html
+//! [`DistinctCount`]: ./struct.DistinctCount.html
+//! [`FrequentItems`]: ./struct.FrequentItems.html
 //! [`concatenate`]: ./macro.concatenate.html
+//! [`assert_estimator_almost_eq`]: ./macro.assert_estimator_almost_eq.html
+//! [`welch_t`]: ./fn.welch_t.html
+//! [`mean_axis`]: ./fn.mean_axis.html
+//! [`RawMoments`]: ./struct.RawMoments.html
+//! [`assert_almost_eq`]: ./macro.assert_almost_eq.html
 
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 #![no_std]
 
 extern crate conv;
+extern crate num_traits;
 extern crate quickersort;
+#[cfg(feature = "std")]
+#[macro_use] extern crate std;
+#[cfg(feature = "serde")]
+#[macro_use] extern crate serde;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
 
 #[macro_use] mod macros;
 mod moments;
 mod weighted_mean;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod geometric_mean;
+mod harmonic_mean;
+mod sum;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod product;
+mod covariance;
+mod linear_regression;
 mod minmax;
 mod reduce;
 mod quantile;
 mod traits;
+mod exp_moving;
+#[cfg(any(feature = "std", feature = "libm"))]
+mod special;
+#[cfg(feature = "std")]
+mod histogram;
+#[cfg(feature = "std")]
+mod gini;
+#[cfg(feature = "std")]
+mod trimmed_mean;
+#[cfg(feature = "std")]
+mod reservoir;
+#[cfg(feature = "std")]
+mod distinct_count;
+#[cfg(feature = "std")]
+mod frequent_items;
+#[cfg(feature = "std")]
+mod tdigest;
+#[cfg(feature = "std")]
+mod gk_summary;
+#[cfg(feature = "std")]
+mod autocorrelation;
+#[cfg(feature = "std")]
+mod sliding;
+#[cfg(feature = "ndarray")]
+mod array;
 
-pub use moments::{Mean, Variance, Skewness, Kurtosis, MeanWithError};
-pub use weighted_mean::{WeightedMean, WeightedMeanWithError};
-pub use minmax::{Min, Max};
-pub use quantile::Quantile;
-pub use traits::{Estimate, Merge};
+pub use moments::{Mean, Variance, Skewness, Kurtosis, Moments, MeanWithError, Summary, RawMoments};
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use moments::welch_t;
+pub use weighted_mean::{WeightedMean, WeightedMeanWithError, WeightedVariance};
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use geometric_mean::GeometricMean;
+pub use harmonic_mean::HarmonicMean;
+pub use sum::Sum;
+#[cfg(any(feature = "std", feature = "libm"))]
+pub use product::Product;
+pub use covariance::{Covariance, Correlation};
+pub use linear_regression::LinearRegression;
+pub use minmax::{Min, Max, Range};
+pub use quantile::{Quantile, InterquartileRange, MedianAbsoluteDeviation};
+#[cfg(feature = "std")]
+pub use quantile::{Quantiles, WarmupQuantile};
+pub use exp_moving::{ExpMovingAverage, ExpMovingVariance};
+#[cfg(feature = "std")]
+pub use histogram::{DynHistogram, WeightedHistogram};
+#[cfg(feature = "std")]
+pub use gini::Gini;
+#[cfg(feature = "std")]
+pub use trimmed_mean::{TrimmedMean, WinsorizedMean};
+#[cfg(feature = "std")]
+pub use reservoir::ReservoirSample;
+#[cfg(feature = "std")]
+pub use distinct_count::DistinctCount;
+#[cfg(feature = "std")]
+pub use frequent_items::FrequentItems;
+#[cfg(feature = "std")]
+pub use tdigest::TDigest;
+#[cfg(feature = "std")]
+pub use gk_summary::GkSummary;
+#[cfg(feature = "std")]
+pub use autocorrelation::Autocorrelation;
+#[cfg(feature = "std")]
+pub use sliding::Sliding;
+#[cfg(feature = "ndarray")]
+pub use array::mean_axis;
+pub use traits::{Estimate, Merge, Remove, Statistics, NanError, skip_nan};
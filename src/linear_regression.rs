@@ -0,0 +1,143 @@
+use core;
+
+use super::{Correlation, Merge};
+
+/// Estimate a linear fit `y = a + b*x` of a sequence of pairs of numbers
+/// ("population") using ordinary least squares.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::LinearRegression;
+///
+/// let a: LinearRegression = [(1., 3.), (2., 5.), (3., 7.)]
+///     .iter().map(|&p| p).collect();
+/// assert_eq!(a.slope(), 2.);
+/// assert_eq!(a.intercept(), 1.);
+/// assert_eq!(a.r_squared(), 1.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LinearRegression {
+    /// Estimator of the correlation, including the covariance and variances.
+    corr: Correlation,
+}
+
+impl LinearRegression {
+    /// Create a new linear regression estimator.
+    #[inline]
+    pub fn new() -> LinearRegression {
+        LinearRegression { corr: Correlation::new() }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = LinearRegression::new();
+    }
+
+    /// Add a pair of observations sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64, y: f64) {
+        self.corr.add(x, y);
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.corr.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.corr.len()
+    }
+
+    /// Estimate the slope `b` of the fit.
+    ///
+    /// Returns NaN if the variance of `x` is zero (including samples with less
+    /// than two observations), because the fit is not defined in that case.
+    #[inline]
+    pub fn slope(&self) -> f64 {
+        let variance_x = self.corr.variance_x();
+        if variance_x == 0. {
+            return f64::NAN;
+        }
+        self.corr.covariance() / variance_x
+    }
+
+    /// Estimate the intercept `a` of the fit.
+    ///
+    /// Returns NaN if the slope is not defined.
+    #[inline]
+    pub fn intercept(&self) -> f64 {
+        self.corr.mean_y() - self.slope() * self.corr.mean_x()
+    }
+
+    /// Calculate the coefficient of determination of the fit.
+    ///
+    /// This is the square of the correlation coefficient. Returns NaN if the
+    /// variance of `x` or `y` is zero.
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[inline]
+    pub fn r_squared(&self) -> f64 {
+        let r = self.corr.correlation();
+        r * r
+    }
+}
+
+impl core::default::Default for LinearRegression {
+    fn default() -> LinearRegression {
+        LinearRegression::new()
+    }
+}
+
+impl core::iter::FromIterator<(f64, f64)> for LinearRegression {
+    fn from_iter<T>(iter: T) -> LinearRegression
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        let mut a = LinearRegression::new();
+        for (x, y) in iter {
+            a.add(x, y);
+        }
+        a
+    }
+}
+
+impl core::iter::Extend<(f64, f64)> for LinearRegression {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        for (x, y) in iter {
+            self.add(x, y);
+        }
+    }
+}
+
+impl Merge for LinearRegression {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{LinearRegression, Merge};
+    ///
+    /// let sequence: &[(f64, f64)] = &[
+    ///     (1., 2.), (2., 3.), (3., 5.), (4., 4.), (5., 6.),
+    ///     (6., 8.), (7., 7.), (8., 9.), (9., 9.)];
+    /// let (left, right) = sequence.split_at(3);
+    /// let fit_total: LinearRegression = sequence.iter().map(|&p| p).collect();
+    /// let mut fit_left: LinearRegression = left.iter().map(|&p| p).collect();
+    /// let fit_right: LinearRegression = right.iter().map(|&p| p).collect();
+    /// fit_left.merge(&fit_right);
+    /// assert!((fit_total.slope() - fit_left.slope()).abs() < 1e-14);
+    /// assert!((fit_total.intercept() - fit_left.intercept()).abs() < 1e-14);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &LinearRegression) {
+        self.corr.merge(&other.corr);
+    }
+}
@@ -7,14 +7,66 @@ macro_rules! assert_almost_eq {
     ($a:expr, $b:expr, $prec:expr) => (
         let diff = ($a - $b).abs();
         if diff > $prec {
-            panic!(format!(
+            panic!(
                 "assertion failed: `abs(left - right) = {:.1e} < {:e}`, \
                  (left: `{}`, right: `{}`)",
-                diff, $prec, $a, $b));
+                diff, $prec, $a, $b);
         }
     );
 }
 
+/// Assert that two moment estimators are almost equal to each other.
+///
+/// The estimators must have a `raw_moments` method, like `Mean`, `Variance`,
+/// `Skewness` and `Kurtosis`. Their sample sizes must be equal, and their
+/// means, population variances and third and fourth standardized moments must
+/// not differ by more than `$prec`. The standardized moments are NaN if the
+/// variance is zero, in which case they must be NaN for both estimators.
+///
+/// On panic, this macro will print the name of the first statistic that
+/// differs, with the values of both estimators.
+///
+///
+/// ## Example
+///
+/// ```
+/// #[macro_use] extern crate average;
+/// use average::{Kurtosis, Merge};
+///
+/// # fn main() {
+/// let total: Kurtosis = (1..11).map(f64::from).collect();
+/// let mut a: Kurtosis = (1..4).map(f64::from).collect();
+/// let b: Kurtosis = (4..11).map(f64::from).collect();
+/// a.merge(&b);
+/// assert_estimator_almost_eq!(a, total, 1e-12);
+/// # }
+/// ```
+#[macro_export]
+macro_rules! assert_estimator_almost_eq {
+    ($a:expr, $b:expr, $prec:expr) => ({
+        let (a, b) = ($a.raw_moments(), $b.raw_moments());
+        if a.count != b.count {
+            panic!("assertion failed: estimators differ in `len`, \
+                    (left: `{}`, right: `{}`)", a.count, b.count);
+        }
+        let statistics = [
+            ("mean", a.mean, b.mean),
+            ("variance", a.central_moment(2), b.central_moment(2)),
+            ("standardized_moment(3)", a.standardized_moment(3), b.standardized_moment(3)),
+            ("standardized_moment(4)", a.standardized_moment(4), b.standardized_moment(4)),
+        ];
+        for &(name, x, y) in statistics.iter() {
+            let diff = (x - y).abs();
+            if !(diff <= $prec) && !(x.is_nan() && y.is_nan()) {
+                panic!(
+                    "assertion failed: estimators differ in `{}`, \
+                     `abs(left - right) = {:.1e} < {:e}`, (left: `{}`, right: `{}`)",
+                    name, diff, $prec, x, y);
+            }
+        }
+    });
+}
+
 /// Concatenate several iterative estimators into one.
 ///
 /// `$name` is the name of the new struct. `$statistic` is the name of a
@@ -31,7 +83,8 @@ macro_rules! assert_almost_eq {
 ///
 /// The following methods will be implemented: `new`, `add`, `$statistic`.
 ///
-/// The following traits will be implemented: `Default`, `FromIterator<f64>`.
+/// The following traits will be implemented: `Default`, `FromIterator<f64>`
+/// and, if all estimators implement it, `Merge`.
 ///
 ///
 /// # Examples
@@ -139,6 +192,20 @@ macro_rules! concatenate {
         }
 
         impl_from_iterator!($name);
+
+        // The higher-ranked bounds make the impl conditional: If some
+        // estimator does not implement `Merge`, the impl is skipped instead of
+        // failing to compile.
+        impl $crate::Merge for $name
+            where $( for<'a> $estimator: $crate::Merge ),*
+        {
+            #[inline]
+            fn merge(&mut self, other: &$name) {
+                $(
+                    $crate::Merge::merge(&mut self.$field, &other.$field);
+                )*
+            }
+        }
     };
 }
 
@@ -159,3 +226,156 @@ macro_rules! impl_from_iterator {
         }
     };
 }
+
+/// Implement `FromIterator<F>` for an iterative estimator that is generic over
+/// the float type `F`.
+///
+/// The bounds on `F` default to `FloatCore`.
+macro_rules! impl_generic_from_iterator {
+    ( $name:ident ) => {
+        impl_generic_from_iterator!($name, ::num_traits::float::FloatCore);
+    };
+    ( $name:ident, $($bound:tt)+ ) => {
+        impl<F: $($bound)+> ::core::iter::FromIterator<F> for $name<F> {
+            fn from_iter<T>(iter: T) -> $name<F>
+                where T: IntoIterator<Item=F>
+            {
+                let mut e = $name::new();
+                for i in iter {
+                    e.add(i);
+                }
+                e
+            }
+        }
+    };
+}
+
+/// Implement `Sum` for an iterative estimator that is generic over the float
+/// type `F`, by merging the summed estimators.
+macro_rules! impl_generic_sum {
+    ( $name:ident ) => {
+        impl<F: ::num_traits::float::FloatCore> ::core::iter::Sum for $name<F> {
+            fn sum<I>(iter: I) -> $name<F>
+                where I: Iterator<Item=$name<F>>
+            {
+                let mut e = $name::new();
+                for i in iter {
+                    $crate::Merge::merge(&mut e, &i);
+                }
+                e
+            }
+        }
+
+        impl<'a, F: ::num_traits::float::FloatCore> ::core::iter::Sum<&'a $name<F>> for $name<F> {
+            fn sum<I>(iter: I) -> $name<F>
+                where I: Iterator<Item=&'a $name<F>>
+            {
+                let mut e = $name::new();
+                for i in iter {
+                    $crate::Merge::merge(&mut e, i);
+                }
+                e
+            }
+        }
+    };
+}
+
+/// Implement `FromParallelIterator<F>` for an iterative estimator that is
+/// generic over the float type `F`, by merging the estimators of the chunks
+/// processed by each thread.
+///
+/// The bounds on `F` default to `FloatCore`.
+macro_rules! impl_generic_from_par_iter {
+    ( $name:ident ) => {
+        impl_generic_from_par_iter!($name, ::num_traits::float::FloatCore);
+    };
+    ( $name:ident, $($bound:tt)+ ) => {
+        #[cfg(feature = "rayon")]
+        impl<F: $($bound)+ + Send> ::rayon::iter::FromParallelIterator<F> for $name<F> {
+            fn from_par_iter<I>(par_iter: I) -> $name<F>
+                where I: ::rayon::iter::IntoParallelIterator<Item=F>
+            {
+                use ::rayon::iter::ParallelIterator;
+                par_iter.into_par_iter()
+                    .fold($name::new, |mut e, x| {
+                        e.add(x);
+                        e
+                    })
+                    .reduce($name::new, |mut a, b| {
+                        $crate::Merge::merge(&mut a, &b);
+                        a
+                    })
+            }
+        }
+    };
+}
+
+/// Implement `from_array` for an iterative estimator that is generic over the
+/// float type `F` and has an `add_slice` method.
+///
+/// The bounds on `F` default to `FloatCore`.
+macro_rules! impl_generic_from_array {
+    ( $name:ident ) => {
+        impl_generic_from_array!($name, ::num_traits::float::FloatCore);
+    };
+    ( $name:ident, $($bound:tt)+ ) => {
+        #[cfg(feature = "ndarray")]
+        impl<F: $($bound)+> $name<F> {
+            /// Create a new estimator from the observations in a
+            /// one-dimensional array.
+            ///
+            /// Contiguous arrays are added in bulk like with `add_slice`,
+            /// others are added one by one. The observations are not copied.
+            /// This is only available with the `ndarray` feature.
+            #[inline]
+            pub fn from_array(view: ::ndarray::ArrayView1<F>) -> $name<F> {
+                let mut e = $name::new();
+                match view.as_slice() {
+                    Some(xs) => e.add_slice(xs),
+                    None => {
+                        for &x in view.iter() {
+                            $crate::Estimate::add(&mut e, x);
+                        }
+                    }
+                }
+                e
+            }
+        }
+    };
+}
+
+/// Implement `Extend<f64>` for an iterative estimator.
+macro_rules! impl_extend {
+    ( $name:ident ) => {
+        impl ::core::iter::Extend<f64> for $name {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=f64>
+            {
+                for i in iter {
+                    self.add(i);
+                }
+            }
+        }
+    };
+}
+
+/// Implement `Extend<F>` for an iterative estimator that is generic over the
+/// float type `F`.
+///
+/// The bounds on `F` default to `FloatCore`.
+macro_rules! impl_generic_extend {
+    ( $name:ident ) => {
+        impl_generic_extend!($name, ::num_traits::float::FloatCore);
+    };
+    ( $name:ident, $($bound:tt)+ ) => {
+        impl<F: $($bound)+> ::core::iter::Extend<F> for $name<F> {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=F>
+            {
+                for i in iter {
+                    self.add(i);
+                }
+            }
+        }
+    };
+}
@@ -1,5 +1,8 @@
 use core;
 
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+
 use super::reduce::Reduce;
 use super::{Estimate, Merge};
 
@@ -27,6 +30,10 @@ fn max(a: f64, b: f64) -> f64 {
 #[derive(Debug, Clone)]
 pub struct Min {
     r: Reduce<fn(f64, f64) -> f64>,
+    /// Sample size.
+    n: u64,
+    /// Index of the first observation equal to the minimum, if any.
+    index: Option<u64>,
 }
 
 impl Min {
@@ -35,13 +42,21 @@ impl Min {
     pub fn from_value(x: f64) -> Min {
         Min {
             r: Reduce::from_value_and_fn(x, min),
+            n: 0,
+            index: None,
         }
     }
 
     /// Create a new minimum estimator.
     #[inline]
     pub fn new() -> Min {
-        Min::from_value(::core::f64::INFINITY)
+        Min::from_value(f64::INFINITY)
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Min::new();
     }
 
     /// Estimate the minium of the population.
@@ -49,6 +64,28 @@ impl Min {
     pub fn min(&self) -> f64 {
         self.r.reduction()
     }
+
+    /// Return the zero-based index of the observation that is the minimum.
+    ///
+    /// If several observations are equal to the minimum, the index of the
+    /// first one is returned. Returns `None` for an empty sample, or if no
+    /// observation reached the value given to `from_value`.
+    #[inline]
+    pub fn arg_min(&self) -> Option<u64> {
+        self.index
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
 }
 
 impl core::default::Default for Min {
@@ -58,11 +95,37 @@ impl core::default::Default for Min {
 }
 
 impl_from_iterator!(Min);
+impl_extend!(Min);
+
+#[cfg(feature = "serde")]
+impl Serialize for Min {
+    /// Serialize the estimator as a tuple of its current minimum, the sample
+    /// size and the index of the minimum.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.min(), self.n, self.index).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Min {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Min, D::Error> {
+        <(f64, u64, Option<u64>)>::deserialize(deserializer).map(|(x, n, index)| {
+            let mut e = Min::from_value(x);
+            e.n = n;
+            e.index = index;
+            e
+        })
+    }
+}
 
 impl Estimate for Min {
     #[inline]
     fn add(&mut self, x: f64) {
+        if x < self.min() || self.index.is_none() && x == self.min() {
+            self.index = Some(self.n);
+        }
         self.r.add(x);
+        self.n += 1;
     }
 
     #[inline]
@@ -87,10 +150,22 @@ impl Merge for Min {
     /// let min_right: Min = right.iter().map(|x| *x).collect();
     /// min_left.merge(&min_right);
     /// assert_eq!(min_total.min(), min_left.min());
+    /// assert_eq!(min_total.arg_min(), min_left.arg_min());
     /// ```
+    ///
+    /// The observations of `other` are assumed to come after the ones of
+    /// `self`, so their indices are offset by the sample size of `self`.
+    /// An empty sample has a minimum of +∞, so merging it is a no-op.
     #[inline]
     fn merge(&mut self, other: &Min) {
+        if let Some(index) = other.index {
+            let x = other.min();
+            if x < self.min() || self.index.is_none() && x == self.min() {
+                self.index = Some(self.n + index);
+            }
+        }
         self.r.merge(&other.r);
+        self.n += other.n;
     }
 }
 
@@ -108,6 +183,10 @@ impl Merge for Min {
 #[derive(Debug, Clone)]
 pub struct Max {
     r: Reduce<fn(f64, f64) -> f64>,
+    /// Sample size.
+    n: u64,
+    /// Index of the first observation equal to the maximum, if any.
+    index: Option<u64>,
 }
 
 impl Max {
@@ -116,13 +195,21 @@ impl Max {
     pub fn from_value(x: f64) -> Max {
         Max {
             r: Reduce::from_value_and_fn(x, max),
+            n: 0,
+            index: None,
         }
     }
 
     /// Create a new maximum estimator.
     #[inline]
     pub fn new() -> Max {
-        Max::from_value(::core::f64::NEG_INFINITY)
+        Max::from_value(f64::NEG_INFINITY)
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Max::new();
     }
 
     /// Estimate the maxium of the population.
@@ -130,6 +217,28 @@ impl Max {
     pub fn max(&self) -> f64 {
         self.r.reduction()
     }
+
+    /// Return the zero-based index of the observation that is the maximum.
+    ///
+    /// If several observations are equal to the maximum, the index of the
+    /// first one is returned. Returns `None` for an empty sample, or if no
+    /// observation reached the value given to `from_value`.
+    #[inline]
+    pub fn arg_max(&self) -> Option<u64> {
+        self.index
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
 }
 
 impl core::default::Default for Max {
@@ -139,11 +248,37 @@ impl core::default::Default for Max {
 }
 
 impl_from_iterator!(Max);
+impl_extend!(Max);
+
+#[cfg(feature = "serde")]
+impl Serialize for Max {
+    /// Serialize the estimator as a tuple of its current maximum, the sample
+    /// size and the index of the maximum.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.max(), self.n, self.index).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Max {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Max, D::Error> {
+        <(f64, u64, Option<u64>)>::deserialize(deserializer).map(|(x, n, index)| {
+            let mut e = Max::from_value(x);
+            e.n = n;
+            e.index = index;
+            e
+        })
+    }
+}
 
 impl Estimate for Max {
     #[inline]
     fn add(&mut self, x: f64) {
+        if x > self.max() || self.index.is_none() && x == self.max() {
+            self.index = Some(self.n);
+        }
         self.r.add(x);
+        self.n += 1;
     }
 
     #[inline]
@@ -168,9 +303,144 @@ impl Merge for Max {
     /// let max_right: Max = right.iter().map(|x| *x).collect();
     /// max_left.merge(&max_right);
     /// assert_eq!(max_total.max(), max_left.max());
+    /// assert_eq!(max_total.arg_max(), max_left.arg_max());
     /// ```
+    ///
+    /// The observations of `other` are assumed to come after the ones of
+    /// `self`, so their indices are offset by the sample size of `self`.
+    /// An empty sample has a maximum of -∞, so merging it is a no-op.
     #[inline]
     fn merge(&mut self, other: &Max) {
+        if let Some(index) = other.index {
+            let x = other.max();
+            if x > self.max() || self.index.is_none() && x == self.max() {
+                self.index = Some(self.n + index);
+            }
+        }
         self.r.merge(&other.r);
+        self.n += other.n;
+    }
+}
+
+/// Estimate the minimum, the maximum and the range of a sequence of numbers
+/// ("population").
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Range;
+///
+/// let a: Range = (1..6).map(Into::into).collect();
+/// assert_eq!(a.min(), 1.);
+/// assert_eq!(a.max(), 5.);
+/// assert_eq!(a.range(), 4.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Range {
+    /// Estimator of the minimum.
+    min: Min,
+    /// Estimator of the maximum.
+    max: Max,
+}
+
+impl Range {
+    /// Create a new range estimator.
+    #[inline]
+    pub fn new() -> Range {
+        Range { min: Min::new(), max: Max::new() }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Range::new();
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.min.len()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.min.is_empty()
+    }
+
+    /// Estimate the minimum of the population.
+    ///
+    /// Returns `+inf` for an empty sample.
+    #[inline]
+    pub fn min(&self) -> f64 {
+        self.min.min()
+    }
+
+    /// Estimate the maximum of the population.
+    ///
+    /// Returns `-inf` for an empty sample.
+    #[inline]
+    pub fn max(&self) -> f64 {
+        self.max.max()
+    }
+
+    /// Estimate the range of the population, i.e. the difference between the
+    /// maximum and the minimum.
+    ///
+    /// Returns NaN for an empty sample, or if all observations are NaN.
+    #[inline]
+    pub fn range(&self) -> f64 {
+        if self.min() > self.max() {
+            return f64::NAN;
+        }
+        self.max() - self.min()
+    }
+}
+
+impl core::default::Default for Range {
+    fn default() -> Range {
+        Range::new()
+    }
+}
+
+impl_from_iterator!(Range);
+impl_extend!(Range);
+
+impl Estimate for Range {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.min.add(x);
+        self.max.add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.range()
+    }
+}
+
+impl Merge for Range {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Range, Merge};
+    ///
+    /// let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    /// let (left, right) = sequence.split_at(3);
+    /// let range_total: Range = sequence.iter().map(|x| *x).collect();
+    /// let mut range_left: Range = left.iter().map(|x| *x).collect();
+    /// let range_right: Range = right.iter().map(|x| *x).collect();
+    /// range_left.merge(&range_right);
+    /// assert_eq!(range_total.range(), range_left.range());
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &Range) {
+        self.min.merge(&other.min);
+        self.max.merge(&other.max);
     }
 }
@@ -3,23 +3,112 @@
 ///
 /// This can be used to estimate the standard error of the mean.
 #[derive(Debug, Clone)]
-pub struct Kurtosis {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Kurtosis<F = f64> {
     /// Estimator of mean, variance and skewness.
-    avg: Skewness,
+    avg: Skewness<F>,
     /// Intermediate sum of terms to the fourth for calculating the skewness.
-    sum_4: f64,
+    sum_4: F,
+    /// Running compensation for the rounding errors in the sum of the cubed
+    /// terms of `avg`, which is only updated by `add_precise`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    compensation_3: F,
+    /// Running compensation for the rounding errors in `sum_4`, which is only
+    /// updated by `add_precise`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    compensation_4: F,
 }
 
-impl Kurtosis {
+impl<F: FloatCore> Kurtosis<F> {
     /// Create a new kurtosis estimator.
     #[inline]
-    pub fn new() -> Kurtosis {
+    pub fn new() -> Kurtosis<F> {
         Kurtosis {
             avg: Skewness::new(),
-            sum_4: 0.,
+            sum_4: F::zero(),
+            compensation_3: F::zero(),
+            compensation_4: F::zero(),
         }
     }
 
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Kurtosis::new();
+    }
+
+    /// Create a new kurtosis estimator from the sample size, the mean and the
+    /// sums of the second, third and fourth powers of the differences from
+    /// the mean.
+    ///
+    /// The caller must ensure that the statistics are consistent, see
+    /// [`Variance::from_raw`]. `sum_3` is `Σ(x - mean)³` and `sum_4` is
+    /// `Σ(x - mean)⁴`.
+    ///
+    /// Panics if `sum_2` or `sum_4` is negative or NaN, or if `count` is 0 but
+    /// one of the other statistics is not.
+    ///
+    /// [`Variance::from_raw`]: ./struct.Variance.html#method.from_raw
+    #[inline]
+    pub fn from_raw(count: u64, mean: F, sum_2: F, sum_3: F, sum_4: F) -> Kurtosis<F> {
+        assert!(sum_4 >= F::zero(), "sum_4 must not be negative");
+        assert!(count > 0 || sum_4 == F::zero(), "sum_4 of an empty sample must be 0");
+        Kurtosis {
+            avg: Skewness::from_raw(count, mean, sum_2, sum_3),
+            sum_4,
+            compensation_3: F::zero(),
+            compensation_4: F::zero(),
+        }
+    }
+
+    /// Return the sufficient statistics, to be restored with `from_raw`.
+    #[inline]
+    pub fn raw_moments(&self) -> RawMoments<F> {
+        RawMoments { sum_4: self.sum_4, ..self.avg.raw_moments() }
+    }
+
+    /// Merge a sample of which only the mean and the variance are known into
+    /// this one.
+    ///
+    /// The third and fourth central moments of `other` are assumed to be 0,
+    /// and the shift terms for combining them with the moments of this sample
+    /// are applied as in `merge`. The sample size, the mean and the variance of
+    /// the result are therefore exact. The skewness is exact only if the
+    /// sample of `other` is symmetric about its mean. The fourth central
+    /// moment of `other` is positive if its variance is, so the kurtosis is
+    /// underestimated.
+    ///
+    /// Only unweighted samples can be merged, because this estimator does not
+    /// support weights.
+    ///
+    /// Panics if `other` has observations with weights other than 1.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Kurtosis, Variance};
+    ///
+    /// let mut a: Kurtosis = [1., 2., 3.].iter().map(|x| *x).collect();
+    /// let b: Variance = [4., 5., 6.].iter().map(|x| *x).collect();
+    /// a.merge_lower(&b);
+    /// let total: Kurtosis = (1..7).map(f64::from).collect();
+    /// assert_eq!(a.len(), total.len());
+    /// assert!((a.sample_variance() - total.sample_variance()).abs() < 1e-14);
+    /// assert!(a.kurtosis() < total.kurtosis());
+    /// ```
+    #[inline]
+    pub fn merge_lower(&mut self, other: &Variance<F>) {
+        assert!(other.sum_weights() == F::from(other.len()).unwrap(),
+                "merge_lower only supports unweighted samples");
+        self.merge(&Kurtosis {
+            avg: Skewness { avg: other.clone(), sum_3: F::zero() },
+            sum_4: F::zero(),
+            compensation_3: F::zero(),
+            compensation_4: F::zero(),
+        });
+    }
+
     /// Increment the sample size.
     ///
     /// This does not update anything else.
@@ -34,19 +123,110 @@ impl Kurtosis {
     ///
     /// This is useful for avoiding unnecessary divisions in the inner loop.
     #[inline]
-    fn add_inner(&mut self, delta: f64, delta_n: f64) {
+    fn add_inner(&mut self, delta: F, delta_n: F) {
         // This algorithm was suggested by Terriberry.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let n = f64::approx_from(self.len()).unwrap();
-        let term = delta * delta_n * (n - 1.);
+        //
+        // The sample size is maintained as `F` by `Mean`, which avoids
+        // converting it in the inner loop.
+        let n = self.avg.avg.avg.count;
+        let three = F::from(3).unwrap();
+        let four = F::from(4).unwrap();
+        let six = F::from(6).unwrap();
+        let term = delta * delta_n * (n - F::one());
         let delta_n_sq = delta_n*delta_n;
-        self.sum_4 += term * delta_n_sq * (n*n - 3.*n + 3.)
-            + 6. * delta_n_sq * self.avg.avg.sum_2
-            - 4. * delta_n * self.avg.sum_3;
+        self.sum_4 = self.sum_4 + term * delta_n_sq * (n*n - three*n + three)
+            + six * delta_n_sq * self.avg.avg.sum_2
+            - four * delta_n * self.avg.sum_3;
         self.avg.add_inner(delta, delta_n);
     }
 
+    /// Add an observation, using compensated summation for the higher moments.
+    ///
+    /// This is like `add`, but the rounding errors of the sums of the cubed
+    /// and fourth powers of the differences from the mean are compensated
+    /// using Kahan summation. Over long sequences, these errors accumulate
+    /// faster than the ones of the lower moments, so this reduces the drift of
+    /// the skewness and the kurtosis, at the cost of a few more operations per
+    /// observation. It can be mixed with `add`, but only the observations
+    /// added with `add_precise` are compensated.
+    ///
+    /// The compensations are applied to the sums when merging, but they are
+    /// not serialized and not compared by `==`.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::Kurtosis;
+    ///
+    /// let mut a = Kurtosis::new();
+    /// for i in 1..6 {
+    ///     a.add_precise(f64::from(i));
+    /// }
+    /// assert!((a.kurtosis() + 1.3).abs() < 1e-15);
+    /// ```
+    #[inline]
+    pub fn add_precise(&mut self, x: F) {
+        let delta = x - self.mean();
+        self.increment();
+        let n = self.avg.avg.avg.count;
+        let delta_n = delta / n;
+        // The same updates as in `add_inner` and `Skewness::add_inner`, with
+        // the increments of the sums calculated from their previous values.
+        let three = F::from(3).unwrap();
+        let four = F::from(4).unwrap();
+        let six = F::from(6).unwrap();
+        let two = F::from(2).unwrap();
+        let term = delta * delta_n * (n - F::one());
+        let delta_n_sq = delta_n*delta_n;
+        let sum_2 = self.avg.avg.sum_2;
+        let increment_4 = term * delta_n_sq * (n*n - three*n + three)
+            + six * delta_n_sq * sum_2
+            - four * delta_n * self.avg.sum_3;
+        let increment_3 = term * delta_n * (n - two) - three*delta_n * sum_2;
+        kahan_add(&mut self.sum_4, &mut self.compensation_4, increment_4);
+        kahan_add(&mut self.avg.sum_3, &mut self.compensation_3, increment_3);
+        self.avg.avg.add_inner(delta_n);
+    }
+
+    /// Apply the compensations of `add_precise` to the sums and reset them.
+    #[inline]
+    fn apply_compensation(&mut self) {
+        self.avg.sum_3 = self.avg.sum_3 - self.compensation_3;
+        self.sum_4 = self.sum_4 - self.compensation_4;
+        self.compensation_3 = F::zero();
+        self.compensation_4 = F::zero();
+    }
+
+    /// Create a new kurtosis estimator from a slice of observations.
+    #[inline]
+    fn from_slice(xs: &[F]) -> Kurtosis<F> {
+        let avg = Skewness::from_slice(xs);
+        let mean = avg.mean();
+        let sum_4 = pairwise_sum(xs, move |x| {
+            let d = x - mean;
+            let d_sq = d*d;
+            d_sq*d_sq
+        });
+        Kurtosis { avg, sum_4, compensation_3: F::zero(), compensation_4: F::zero() }
+    }
+
+    /// Add a slice of observations.
+    ///
+    /// This is faster than calling `add` for each element, because the slice
+    /// is summarized in bulk and then merged into the estimator. The result
+    /// agrees with repeated calls to `add` up to rounding errors, which are
+    /// usually smaller for `add_slice`.
+    #[inline]
+    pub fn add_slice(&mut self, xs: &[F]) {
+        if xs.is_empty() {
+            return;
+        }
+        self.merge(&Kurtosis::from_slice(xs));
+    }
+
     /// Determine whether the sample is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -57,7 +237,7 @@ impl Kurtosis {
     ///
     /// Returns 0 for an empty sample.
     #[inline]
-    pub fn mean(&self) -> f64 {
+    pub fn mean(&self) -> F {
         self.avg.mean()
     }
 
@@ -71,7 +251,7 @@ impl Kurtosis {
     ///
     /// This is an unbiased estimator of the variance of the population.
     #[inline]
-    pub fn sample_variance(&self) -> f64 {
+    pub fn sample_variance(&self) -> F {
         self.avg.sample_variance()
     }
 
@@ -79,65 +259,326 @@ impl Kurtosis {
     ///
     /// This is a biased estimator of the variance of the population.
     #[inline]
-    pub fn population_variance(&self) -> f64 {
+    pub fn population_variance(&self) -> F {
         self.avg.population_variance()
     }
 
+    /// Calculate the second central moment of the sample.
+    ///
+    /// This is equal to the population variance. Returns 0 for an empty
+    /// sample.
+    #[inline]
+    pub fn central_moment_2(&self) -> F {
+        self.avg.central_moment_2()
+    }
+
+    /// Calculate the third central moment of the sample.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn central_moment_3(&self) -> F {
+        self.avg.central_moment_3()
+    }
+
+    /// Calculate the fourth central moment of the sample.
+    ///
+    /// This is the mean of the fourth powers of the differences from the mean.
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn central_moment_4(&self) -> F {
+        let n = self.len();
+        if n == 0 {
+            return F::zero();
+        }
+        self.sum_4 / F::from(n).unwrap()
+    }
+
+    /// Estimate the excess kurtosis of the population.
+    ///
+    /// This is the fourth standardized moment minus 3, so that it is 0 for a
+    /// normal distribution. See `kurtosis_raw` for the value without the
+    /// subtraction.
+    ///
+    /// This is the biased estimator `g2` of the sample. Statistics packages and
+    /// spreadsheets usually report the bias-corrected excess kurtosis, which
+    /// is calculated by `sample_kurtosis`.
+    ///
+    /// Returns NaN if the variance of the sample is zero (including samples
+    /// with less than two observations), because the kurtosis is not defined
+    /// in that case.
+    #[inline]
+    pub fn kurtosis(&self) -> F {
+        self.kurtosis_raw() - F::from(3).unwrap()
+    }
+
+    /// Estimate the raw (non-excess) kurtosis of the population.
+    ///
+    /// This is the fourth standardized moment, which is 3 for a normal
+    /// distribution. It is always equal to `kurtosis() + 3`.
+    ///
+    /// Returns NaN if the variance of the sample is zero.
+    #[inline]
+    pub fn kurtosis_raw(&self) -> F {
+        let sum_2 = self.avg.avg.sum_2;
+        if sum_2 == F::zero() {
+            return F::nan();
+        }
+        let n = self.avg.avg.avg.count;
+        n * self.sum_4 / (sum_2 * sum_2)
+    }
+
+    /// Calculate the sample excess kurtosis.
+    ///
+    /// This is the bias-corrected estimator
+    /// `G2 = ((n + 1) g2 + 6)(n - 1)/((n - 2)(n - 3))`, where `g2` is the
+    /// excess kurtosis returned by `kurtosis`. Returns NaN for samples with
+    /// less than four observations or with zero variance.
+    #[inline]
+    pub fn sample_kurtosis(&self) -> F {
+        let n = self.len();
+        if n < 4 {
+            return F::nan();
+        }
+        let n = F::from(n).unwrap();
+        let two = F::from(2).unwrap();
+        let three = F::from(3).unwrap();
+        let six = F::from(6).unwrap();
+        ((n + F::one()) * self.kurtosis() + six) * (n - F::one())
+            / ((n - two) * (n - three))
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<F: FloatCore + Float> Kurtosis<F> {
+    /// Calculate the sample standard deviation.
+    ///
+    /// This is the square root of the sample variance.
+    #[inline]
+    pub fn sample_std_dev(&self) -> F {
+        self.avg.sample_std_dev()
+    }
+
+    /// Calculate the population standard deviation of the sample.
+    ///
+    /// This is the square root of the population variance.
+    #[inline]
+    pub fn std_dev(&self) -> F {
+        self.avg.std_dev()
+    }
+
+    /// Calculate the coefficient of variation of the sample.
+    ///
+    /// Returns NaN if the mean is zero.
+    #[inline]
+    pub fn coefficient_of_variation(&self) -> F {
+        self.avg.coefficient_of_variation()
+    }
+
+    /// Calculate the sample coefficient of variation.
+    ///
+    /// Returns NaN if the mean is zero.
+    #[inline]
+    pub fn sample_coefficient_of_variation(&self) -> F {
+        self.avg.sample_coefficient_of_variation()
+    }
+
     /// Estimate the standard error of the mean of the population.
     #[inline]
-    pub fn error_mean(&self) -> f64 {
+    pub fn error_mean(&self) -> F {
         self.avg.error_mean()
     }
 
     /// Estimate the skewness of the population.
+    ///
+    /// Returns NaN if the variance of the sample is zero.
     #[inline]
-    pub fn skewness(&self) -> f64 {
+    pub fn skewness(&self) -> F {
         self.avg.skewness()
     }
 
-    /// Estimate the kurtosis of the population.
+    /// Calculate the sample skewness.
+    ///
+    /// This is the adjusted Fisher-Pearson coefficient of skewness. Returns
+    /// NaN for samples with less than three observations or with zero
+    /// variance.
     #[inline]
-    pub fn kurtosis(&self) -> f64 {
-        if self.sum_4 == 0. {
-            return 0.;
+    pub fn sample_skewness(&self) -> F {
+        self.avg.sample_skewness()
+    }
+
+    /// Estimate the standard error of the skewness of the population.
+    ///
+    /// Returns NaN for samples with less than three observations.
+    #[inline]
+    pub fn error_skewness(&self) -> F {
+        self.avg.error_skewness()
+    }
+
+    /// Estimate the standard error of the kurtosis of the population.
+    ///
+    /// This uses the large-sample formula
+    /// `2 SES sqrt((n^2 - 1)/((n - 3)(n + 5)))`, where `SES` is the standard
+    /// error of the skewness. It applies to both the excess and the raw
+    /// kurtosis. Returns NaN for samples with less than four observations.
+    #[inline]
+    pub fn error_kurtosis(&self) -> F {
+        let n = self.len();
+        if n < 4 {
+            return FloatCore::nan();
         }
-        let n = f64::approx_from(self.len()).unwrap();
-        n * self.sum_4 / (self.avg.avg.sum_2 * self.avg.avg.sum_2) - 3.
+        let n = F::from(n).unwrap();
+        let two = F::from(2).unwrap();
+        let three = F::from(3).unwrap();
+        let five = F::from(5).unwrap();
+        two * self.error_skewness() * ((n*n - F::one()) / ((n - three) * (n + five))).sqrt()
     }
 
+    /// Return a snapshot of all statistics of the sample.
+    ///
+    /// This is convenient for logging or serializing the results.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::Kurtosis;
+    ///
+    /// let a: Kurtosis = [1., 2., 3., 4., 5.].iter().map(|x| *x).collect();
+    /// let summary = a.summary();
+    /// assert_eq!(summary.n, 5);
+    /// assert_eq!(summary.mean, 3.);
+    /// assert_eq!(summary.variance, 2.5);
+    /// ```
+    #[inline]
+    pub fn summary(&self) -> Summary<F> {
+        let n = self.len();
+        let variance = self.sample_variance();
+        let error_mean = if n == 0 {
+            F::zero()
+        } else {
+            (variance / F::from(n).unwrap()).sqrt()
+        };
+        Summary {
+            n,
+            mean: self.mean(),
+            variance,
+            std_dev: variance.sqrt(),
+            skewness: self.skewness(),
+            kurtosis: self.kurtosis(),
+            error_mean,
+        }
+    }
 }
 
-impl Estimate for Kurtosis {
+/// A snapshot of the statistics estimated by `Kurtosis`.
+///
+/// This is returned by `Kurtosis::summary`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Summary<F = f64> {
+    /// Sample size.
+    pub n: u64,
+    /// Mean of the sample.
+    pub mean: F,
+    /// Sample variance, which is an unbiased estimator of the variance of the
+    /// population.
+    pub variance: F,
+    /// Sample standard deviation, the square root of `variance`.
+    pub std_dev: F,
+    /// Skewness of the sample, as returned by `Kurtosis::skewness`.
+    pub skewness: F,
+    /// Excess kurtosis of the sample, as returned by `Kurtosis::kurtosis`.
+    pub kurtosis: F,
+    /// Standard error of the mean.
+    pub error_mean: F,
+}
+
+impl<F: FloatCore> core::default::Default for Kurtosis<F> {
+    fn default() -> Kurtosis<F> {
+        Kurtosis::new()
+    }
+}
+
+impl<F: FloatCore> Estimate<F> for Kurtosis<F> {
     #[inline]
-    fn add(&mut self, x: f64) {
+    fn add(&mut self, x: F) {
         let delta = x - self.mean();
         self.increment();
-        let n = f64::approx_from(self.len()).unwrap();
+        let n = self.avg.avg.avg.count;
         self.add_inner(delta, delta/n);
     }
 
     #[inline]
-    fn estimate(&self) -> f64 {
+    fn estimate(&self) -> F {
         self.kurtosis()
     }
 }
 
-impl Merge for Kurtosis {
+impl<F: PartialEq> PartialEq for Kurtosis<F> {
+    /// Compare the statistics, ignoring the compensations of `add_precise`.
+    #[inline]
+    fn eq(&self, other: &Kurtosis<F>) -> bool {
+        self.avg == other.avg && self.sum_4 == other.sum_4
+    }
+}
+
+impl<F: FloatCore> Merge for Kurtosis<F> {
     #[inline]
-    fn merge(&mut self, other: &Kurtosis) {
-        let len_self = f64::approx_from(self.len()).unwrap();
-        let len_other = f64::approx_from(other.len()).unwrap();
+    fn merge(&mut self, other: &Kurtosis<F>) {
+        if other.is_empty() {
+            return;
+        }
+        // The rounding errors of the merge itself are not compensated.
+        self.apply_compensation();
+        let mut other = other.clone();
+        other.apply_compensation();
+        let len_self = self.avg.avg.avg.count;
+        let len_other = other.avg.avg.avg.count;
         let len_total = len_self + len_other;
         let delta = other.mean() - self.mean();
         let delta_n = delta / len_total;
         let delta_n_sq = delta_n * delta_n;
-        self.sum_4 += other.sum_4
+        let four = F::from(4).unwrap();
+        let six = F::from(6).unwrap();
+        self.sum_4 = self.sum_4 + other.sum_4
             + delta * delta_n*delta_n_sq * len_self*len_other
               * (len_self*len_self - len_self*len_other + len_other*len_other)
-            + 6.*delta_n_sq * (len_self*len_self * other.avg.avg.sum_2 + len_other*len_other * self.avg.avg.sum_2)
-            + 4.*delta_n * (len_self * other.avg.sum_3 - len_other * self.avg.sum_3);
+            + six*delta_n_sq * (len_self*len_self * other.avg.avg.sum_2 + len_other*len_other * self.avg.avg.sum_2)
+            + four*delta_n * (len_self * other.avg.sum_3 - len_other * self.avg.sum_3);
         self.avg.merge(&other.avg);
     }
 }
 
-impl_from_iterator!(Kurtosis);
+impl<F: FloatCore> From<Kurtosis<F>> for Skewness<F> {
+    /// Discard the kurtosis of the sample, keeping only the mean, the variance and the skewness.
+    ///
+    /// This is cheap, because `Kurtosis` already contains a `Skewness`.
+    #[inline]
+    fn from(mut x: Kurtosis<F>) -> Skewness<F> {
+        x.apply_compensation();
+        x.avg
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<F: FloatCore + Float + fmt::Display> fmt::Display for Kurtosis<F> {
+    /// Format the sample size, the mean, the sample standard
+    /// deviation, the skewness and the excess kurtosis on one line.
+    ///
+    /// The precision of the formatter is used for the statistics, e.g.
+    /// `Kurtosis { n: 4, mean: 2.50, std: 1.29, skewness: 0.00, kurtosis: -1.36 }` for `{:.2}`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_summary(f, "Kurtosis", self.len(), &[
+            ("mean", self.mean()),
+            ("std", self.sample_std_dev()),
+            ("skewness", self.skewness()),
+            ("kurtosis", self.kurtosis()),
+        ])
+    }
+}
+
+impl_generic_from_iterator!(Kurtosis);
+impl_generic_from_par_iter!(Kurtosis);
+impl_generic_from_array!(Kurtosis);
+impl_generic_extend!(Kurtosis);
+impl_generic_sum!(Kurtosis);
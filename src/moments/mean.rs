@@ -1,5 +1,7 @@
 /// Estimate the arithmetic mean of a sequence of numbers ("population").
 ///
+/// The type of the observations `F` defaults to `f64`.
+///
 ///
 /// ## Example
 ///
@@ -9,19 +11,78 @@
 /// let a: Mean = (1..6).map(Into::into).collect();
 /// println!("The mean is {}.", a.mean());
 /// ```
-#[derive(Debug, Clone)]
-pub struct Mean {
-    /// Mean value.
-    avg: f64,
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Mean<F = f64> {
+    /// Mean value, up to the compensation for rounding errors.
+    avg: F,
+    /// Running compensation for the rounding errors in `avg`.
+    compensation: F,
     /// Sample size.
     n: u64,
+    /// Sample size as `F`, kept in step with `n`, which avoids converting it
+    /// in the updates of the higher moments.
+    count: F,
+    /// Sum of the weights.
+    weight_sum: F,
 }
 
-impl Mean {
+impl<F: FloatCore> Mean<F> {
     /// Create a new mean estimator.
     #[inline]
-    pub fn new() -> Mean {
-        Mean { avg: 0., n: 0 }
+    pub fn new() -> Mean<F> {
+        Mean { avg: F::zero(), compensation: F::zero(), n: 0, count: F::zero(), weight_sum: F::zero() }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Mean::new();
+    }
+
+    /// Create a new mean estimator from the sample size and the mean.
+    ///
+    /// This is useful for restoring an estimator from statistics calculated
+    /// elsewhere, without adding the observations again. The observations are
+    /// assumed to have unit weight.
+    ///
+    /// Panics if `count` is 0 but `mean` is not.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Mean, Estimate};
+    ///
+    /// let mut a = Mean::from_raw(3, 2.);
+    /// a.add(6.);
+    /// assert_eq!(a.mean(), 3.);
+    /// ```
+    #[inline]
+    pub fn from_raw(count: u64, mean: F) -> Mean<F> {
+        assert!(count > 0 || mean == F::zero(), "mean of an empty sample must be 0");
+        Mean {
+            avg: mean,
+            compensation: F::zero(),
+            n: checked_len::<F>(count),
+            count: F::from(count).unwrap(),
+            weight_sum: F::from(count).unwrap(),
+        }
+    }
+
+    /// Return the sample size and the mean, to be restored with `from_raw`.
+    ///
+    /// Restoring them gives the same statistics, as long as all observations
+    /// had unit weight.
+    #[inline]
+    pub fn raw_moments(&self) -> RawMoments<F> {
+        RawMoments {
+            count: self.n,
+            mean: self.mean(),
+            sum_2: F::zero(),
+            sum_3: F::zero(),
+            sum_4: F::zero(),
+        }
     }
 
     /// Increment the sample size.
@@ -29,7 +90,9 @@ impl Mean {
     /// This does not update anything else.
     #[inline]
     fn increment(&mut self) {
-        self.n += 1;
+        self.n = checked_len::<F>(self.n + 1);
+        self.count = self.count + F::one();
+        self.weight_sum = self.weight_sum + F::one();
     }
 
     /// Add an observation given an already calculated difference from the mean
@@ -38,12 +101,33 @@ impl Mean {
     ///
     /// This is useful for avoiding unnecessary divisions in the inner loop.
     #[inline]
-    fn add_inner(&mut self, delta_n: f64) {
+    fn add_inner(&mut self, delta_n: F) {
         // This algorithm introduced by Welford in 1962 trades numerical
         // stability for a division inside the loop.
         //
-        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        self.avg += delta_n;
+        // The increments are accumulated with Neumaier's variant of Kahan
+        // summation, keeping track of the lost low-order bits separately.
+        //
+        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance
+        // and https://en.wikipedia.org/wiki/Kahan_summation_algorithm.
+        let t = self.avg + delta_n;
+        if self.avg.abs() >= delta_n.abs() {
+            self.compensation = self.compensation + ((self.avg - t) + delta_n);
+        } else {
+            self.compensation = self.compensation + ((delta_n - t) + self.avg);
+        }
+        self.avg = t;
+    }
+
+    /// Create a new mean estimator from a slice of observations.
+    #[inline]
+    fn from_slice(xs: &[F]) -> Mean<F> {
+        let n = F::from(xs.len()).unwrap();
+        let avg = pairwise_sum(xs, |x| x) / n;
+        // Correct for the rounding error of the first pass.
+        let avg = avg + pairwise_sum(xs, move |x| x - avg) / n;
+        let len = checked_len::<F>(xs.len() as u64);
+        Mean { avg, compensation: F::zero(), n: len, count: n, weight_sum: n }
     }
 
     /// Determine whether the sample is empty.
@@ -56,39 +140,161 @@ impl Mean {
     ///
     /// Returns 0 for an empty sample.
     #[inline]
-    pub fn mean(&self) -> f64 {
-        self.avg
+    pub fn mean(&self) -> F {
+        self.avg + self.compensation
     }
 
     /// Return the sample size.
+    ///
+    /// This is the number of observations, regardless of their weights.
     #[inline]
     pub fn len(&self) -> u64 {
         self.n
     }
 
+    /// Return the sum of the weights.
+    ///
+    /// This is equal to the sample size if all observations were added with
+    /// unit weight. Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum_weights(&self) -> F {
+        self.weight_sum
+    }
+
+    /// Add an observation with the given weight.
+    ///
+    /// `add(x)` is equivalent to `add_weighted(x, 1.)`.
+    ///
+    /// Panics if the weight is not positive.
+    #[inline]
+    pub fn add_weighted(&mut self, x: F, weight: F) {
+        assert!(weight > F::zero(), "weight must be positive");
+        self.n = checked_len::<F>(self.n + 1);
+        self.count = self.count + F::one();
+        self.weight_sum = self.weight_sum + weight;
+        let delta_n = (weight / self.weight_sum) * (x - self.mean());
+        self.add_inner(delta_n);
+    }
+
+    /// Add a slice of observations.
+    ///
+    /// This is faster than calling `add` for each element, because the slice
+    /// is summarized in bulk and then merged into the estimator. The result
+    /// agrees with repeated calls to `add` up to rounding errors, which are
+    /// usually smaller for `add_slice`.
+    #[inline]
+    pub fn add_slice(&mut self, xs: &[F]) {
+        if xs.is_empty() {
+            return;
+        }
+        self.merge(&Mean::from_slice(xs));
+    }
+
+    /// Merge another sample into this one, scaling its contribution by
+    /// `weight`.
+    ///
+    /// This is useful for stratified sampling, where each sample is drawn
+    /// from a stratum of known size: The other sample is treated as if each
+    /// of its observations had been added with its weight multiplied by
+    /// `weight`, so it represents `weight` times its sum of weights. For
+    /// example, if a shard was downsampled by keeping every tenth
+    /// observation, its estimator can be merged with `weight = 10`.
+    ///
+    /// The sample size `len()` still counts the actual observations, while
+    /// `sum_weights()` includes the scaling. `merge(other)` is equivalent to
+    /// `merge_weighted(other, 1.)`.
+    ///
+    /// Panics if the weight is not positive.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::Mean;
+    ///
+    /// let mut a: Mean = [1., 2., 3.].iter().map(|x| *x).collect();
+    /// let b: Mean = [7.].iter().map(|x| *x).collect();
+    /// a.merge_weighted(&b, 3.);
+    /// assert_eq!(a.mean(), 4.5);
+    /// assert_eq!(a.len(), 4);
+    /// assert_eq!(a.sum_weights(), 6.);
+    /// ```
+    #[inline]
+    pub fn merge_weighted(&mut self, other: &Mean<F>, weight: F) {
+        assert!(weight > F::zero(), "weight must be positive");
+        if other.is_empty() {
+            return;
+        }
+        // This algorithm was proposed by Chan et al. in 1979.
+        //
+        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
+        let len_self = self.weight_sum;
+        let len_other = weight * other.weight_sum;
+        let len_total = len_self + len_other;
+        self.n = checked_len::<F>(self.n + other.n);
+        self.count = self.count + other.count;
+        self.weight_sum = len_total;
+        self.avg = (len_self * self.mean() + len_other * other.mean()) / len_total;
+        self.compensation = F::zero();
+        // Chan et al. use
+        //
+        //     self.avg += delta * len_other / len_total;
+        //
+        // instead but this results in cancelation if the number of samples are similar.
+    }
+
+    /// Remove an observation from the sample.
+    ///
+    /// This reverses a previous `add` of the same value, so the estimator ends
+    /// up in the state it would be in if `x` had never been added (up to
+    /// floating-point error). The observation is assumed to have unit weight.
+    ///
+    /// Panics if the sample is empty.
+    #[inline]
+    pub fn remove(&mut self, x: F) {
+        assert!(!self.is_empty(), "cannot remove from an empty sample");
+        self.n -= 1;
+        self.count = self.count - F::one();
+        self.weight_sum = self.weight_sum - F::one();
+        if self.n == 0 {
+            self.avg = F::zero();
+            self.compensation = F::zero();
+            self.count = F::zero();
+            self.weight_sum = F::zero();
+            return;
+        }
+        let delta_n = (x - self.mean()) / self.weight_sum;
+        self.add_inner(-delta_n);
+    }
 }
 
-impl core::default::Default for Mean {
-    fn default() -> Mean {
+impl<F: FloatCore> core::default::Default for Mean<F> {
+    fn default() -> Mean<F> {
         Mean::new()
     }
 }
 
-impl Estimate for Mean {
+impl<F: FloatCore> Estimate<F> for Mean<F> {
     #[inline]
-    fn add(&mut self, sample: f64) {
+    fn add(&mut self, sample: F) {
         self.increment();
-        let delta_n = (sample - self.avg)
-            / f64::approx_from(self.n).unwrap();
+        let delta_n = (sample - self.mean()) / self.weight_sum;
         self.add_inner(delta_n);
     }
 
-    fn estimate(&self) -> f64 {
+    fn estimate(&self) -> F {
         self.mean()
     }
 }
 
-impl Merge for Mean {
+impl<F: FloatCore> Remove<F> for Mean<F> {
+    #[inline]
+    fn remove(&mut self, x: F) {
+        Mean::remove(self, x);
+    }
+}
+
+impl<F: FloatCore> Merge for Mean<F> {
     /// Merge another sample into this one.
     ///
     ///
@@ -106,21 +312,23 @@ impl Merge for Mean {
     /// assert_eq!(avg_total.mean(), avg_left.mean());
     /// ```
     #[inline]
-    fn merge(&mut self, other: &Mean) {
-        // This algorithm was proposed by Chan et al. in 1979.
-        //
-        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let len_self = f64::approx_from(self.n).unwrap();
-        let len_other = f64::approx_from(other.n).unwrap();
-        let len_total = len_self + len_other;
-        self.n += other.n;
-        self.avg = (len_self * self.avg + len_other * other.avg) / len_total;
-        // Chan et al. use
-        //
-        //     self.avg += delta * len_other / len_total;
-        //
-        // instead but this results in cancelation if the number of samples are similar.
+    fn merge(&mut self, other: &Mean<F>) {
+        self.merge_weighted(other, F::one());
+    }
+}
+
+impl<F: FloatCore + fmt::Display> fmt::Display for Mean<F> {
+    /// Format the sample size and the mean on one line.
+    ///
+    /// The precision of the formatter is used for the statistics, e.g.
+    /// `Mean { n: 3, mean: 2.00 }` for `{:.2}`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_summary(f, "Mean", self.len(), &[("mean", self.mean())])
     }
 }
 
-impl_from_iterator!(Mean);
+impl_generic_from_iterator!(Mean);
+impl_generic_from_par_iter!(Mean);
+impl_generic_from_array!(Mean);
+impl_generic_extend!(Mean);
+impl_generic_sum!(Mean);
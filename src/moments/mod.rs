@@ -1,13 +1,165 @@
 use core;
+use core::fmt;
 
-use conv::ApproxFrom;
+#[cfg(any(feature = "std", feature = "libm"))]
+use conv::ConvUtil;
+#[cfg(any(feature = "std", feature = "libm"))]
+use num_traits::Float;
+use num_traits::float::FloatCore;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
 
-use super::{Estimate, Merge};
+use super::{Estimate, Merge, Remove};
+#[cfg(any(feature = "std", feature = "libm"))]
+use super::special::{normal_quantile, student_t_quantile};
+
+/// Sum `f(x)` over all elements `x` of a slice, using pairwise summation.
+///
+/// The rounding error grows only logarithmically with the length of the slice,
+/// and the inner loop over small blocks can be vectorized by the compiler.
+fn pairwise_sum<F, G>(xs: &[F], f: G) -> F
+    where F: FloatCore, G: Fn(F) -> F + Copy
+{
+    const BLOCK_SIZE: usize = 64;
+    if xs.len() <= BLOCK_SIZE {
+        return xs.iter().fold(F::zero(), |sum, &x| sum + f(x));
+    }
+    let (left, right) = xs.split_at(xs.len() / 2);
+    pairwise_sum(left, f) + pairwise_sum(right, f)
+}
+
+/// Add `x` to `sum` using Kahan summation.
+///
+/// `compensation` is the running compensation for the rounding errors, which
+/// is applied to the next summand. `sum` itself is the best estimate of the
+/// sum, so it can be used without the compensation.
+#[inline]
+fn kahan_add<F: FloatCore>(sum: &mut F, compensation: &mut F, x: F) {
+    let y = x - *compensation;
+    let t = *sum + y;
+    *compensation = (t - *sum) - y;
+    *sum = t;
+}
+
+/// Check a new sample size before it is stored.
+///
+/// All updates of the sample sizes of the moment estimators go through this.
+/// In debug builds, this panics once the sample size can no longer be
+/// represented exactly as `F` (beyond 2^53 for `f64` and 2^24 for `f32`),
+/// because the estimates would silently stop improving afterwards. Only the
+/// number of observations is checked, so large weights are fine.
+#[inline]
+fn checked_len<F: FloatCore>(len: u64) -> u64 {
+    debug_assert!(F::from(len).and_then(|n| n.to_u64()) == Some(len),
+        "sample size exceeds the precision of the floating-point type");
+    len
+}
+
+/// Format the sample size and some statistics of an estimator as
+/// `Name { n: 3, mean: 1.5 }`.
+///
+/// The precision of the formatter is used for the statistics.
+fn fmt_summary<F>(f: &mut fmt::Formatter, name: &str, n: u64, statistics: &[(&str, F)])
+    -> fmt::Result
+    where F: fmt::Display
+{
+    write!(f, "{} {{ n: {}", name, n)?;
+    for &(label, ref x) in statistics {
+        match f.precision() {
+            Some(precision) => write!(f, ", {}: {:.*}", label, precision, x)?,
+            None => write!(f, ", {}: {}", label, x)?,
+        }
+    }
+    write!(f, " }}")
+}
+
+/// The sufficient statistics of the moment estimators.
+///
+/// This is returned by `raw_moments` of `Mean`, `Variance`, `Skewness` and
+/// `Kurtosis`, and can be passed to their `from_raw` constructors to restore
+/// them. Unlike the estimators themselves, its layout is part of the public
+/// interface, so it is suitable for checkpointing them. The sums of the
+/// moments not tracked by an estimator are 0.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Kurtosis, Estimate};
+///
+/// let a: Kurtosis = (1..6).map(f64::from).collect();
+/// let r = a.raw_moments();
+/// assert_eq!((r.count, r.mean, r.sum_2, r.sum_4), (5, 3., 10., 34.));
+/// let b = Kurtosis::from_raw(r.count, r.mean, r.sum_2, r.sum_3, r.sum_4);
+/// assert_eq!(a.kurtosis(), b.kurtosis());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RawMoments<F = f64> {
+    /// Sample size.
+    pub count: u64,
+    /// Mean of the sample.
+    pub mean: F,
+    /// Sum of the squared differences from the mean.
+    pub sum_2: F,
+    /// Sum of the cubed differences from the mean.
+    pub sum_3: F,
+    /// Sum of the fourth powers of the differences from the mean.
+    pub sum_4: F,
+}
+
+impl<F: FloatCore> RawMoments<F> {
+    /// Calculate the `k`th central moment of the sample.
+    ///
+    /// This is the mean of the `k`th powers of the differences from the mean.
+    /// Returns 0 for an empty sample (except for `k = 0`).
+    ///
+    /// Panics if `k > 4`.
+    #[inline]
+    pub fn central_moment(&self, k: usize) -> F {
+        let sum = match k {
+            0 => return F::one(),
+            1 => return F::zero(),
+            2 => self.sum_2,
+            3 => self.sum_3,
+            4 => self.sum_4,
+            _ => panic!("moment order exceeds 4"),
+        };
+        if self.count == 0 {
+            return F::zero();
+        }
+        sum / F::from(self.count).unwrap()
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<F: FloatCore + Float> RawMoments<F> {
+    /// Calculate the `k`th standardized moment of the sample.
+    ///
+    /// This is the `k`th central moment divided by the `k`th power of the
+    /// population standard deviation. Returns NaN for `k >= 2` if the variance
+    /// of the sample is zero.
+    ///
+    /// Panics if `k > 4`.
+    #[inline]
+    pub fn standardized_moment(&self, k: usize) -> F {
+        let moment = self.central_moment(k);
+        if k < 2 {
+            return moment;
+        }
+        let variance = self.central_moment(2);
+        if variance == F::zero() {
+            return FloatCore::nan();
+        }
+        moment / FloatCore::powi(variance.sqrt(), k as i32)
+    }
+}
 
 include!("mean.rs");
 include!("variance.rs");
 include!("skewness.rs");
 include!("kurtosis.rs");
+include!("moments.rs");
 
 /// Alias for `Variance`.
-pub type MeanWithError = Variance;
+pub type MeanWithError<F = f64> = Variance<F>;
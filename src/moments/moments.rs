@@ -0,0 +1,256 @@
+/// Estimate the arithmetic mean and the central moments up to order `N` of a
+/// sequence of numbers ("population").
+///
+/// This generalizes `Variance`, `Skewness` and `Kurtosis` for higher moments.
+/// For moments up to order 4, the specialized estimators are faster.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Moments;
+///
+/// let a: Moments<6> = (1..6).map(Into::into).collect();
+/// assert_eq!(a.central_moment(2), 2.);
+/// assert_eq!(a.central_moment(6), 26.);
+/// println!("The 5th standardized moment is {}.", a.standardized_moment(5));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Moments<const N: usize, F = f64> {
+    /// Sample size.
+    n: u64,
+    /// Mean value.
+    avg: F,
+    /// Intermediate sums of powers of the differences from the mean, where
+    /// `sum[k - 1]` is the sum of the `k`th powers.
+    sum: [F; N],
+}
+
+impl<const N: usize, F: FloatCore> Moments<N, F> {
+    /// Create a new estimator of the central moments.
+    #[inline]
+    pub fn new() -> Moments<N, F> {
+        Moments { n: 0, avg: F::zero(), sum: [F::zero(); N] }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Moments::new();
+    }
+
+    /// Merge the summary of a sample given by its size, mean and intermediate
+    /// sums into this one, assuming both samples are not empty.
+    #[inline]
+    fn merge_inner(&mut self, len_other: u64, avg_other: F, sum_other: &[F; N]) {
+        // This algorithm was suggested by Pébay in 2008.
+        //
+        // See https://www.osti.gov/servlets/purl/1028931.
+        let len_self_f = F::from(self.n).unwrap();
+        let len_other_f = F::from(len_other).unwrap();
+        let len_total = len_self_f + len_other_f;
+        let delta = avg_other - self.avg;
+        let sum_self = self.sum;
+        for p in 2..=N {
+            let mut sum = sum_self[p - 1] + sum_other[p - 1];
+            let mut binom = F::one();
+            for k in 1..=(p - 2) {
+                binom = binom * F::from(p + 1 - k).unwrap() / F::from(k).unwrap();
+                let k_i = k as i32;
+                sum = sum + binom * delta.powi(k_i)
+                    * ((-len_other_f / len_total).powi(k_i) * sum_self[p - k - 1]
+                       + (len_self_f / len_total).powi(k_i) * sum_other[p - k - 1]);
+            }
+            let p_i = p as i32;
+            sum = sum + (len_self_f * len_other_f * delta / len_total).powi(p_i)
+                * (F::one() / len_other_f.powi(p_i - 1)
+                   - (-F::one() / len_self_f).powi(p_i - 1));
+            self.sum[p - 1] = sum;
+        }
+        self.avg = self.avg + delta * len_other_f / len_total;
+        self.n = checked_len::<F>(self.n + len_other);
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> F {
+        self.avg
+    }
+
+    /// Calculate the `k`th central moment of the sample.
+    ///
+    /// This is the mean of the `k`th powers of the differences from the mean,
+    /// so the second central moment is the population variance. Returns 0 for
+    /// an empty sample (except for `k = 0`).
+    ///
+    /// Panics if `k > N`.
+    #[inline]
+    pub fn central_moment(&self, k: usize) -> F {
+        assert!(k <= N, "moment order exceeds the maximal order N");
+        match k {
+            0 => F::one(),
+            1 => F::zero(),
+            _ if self.n == 0 => F::zero(),
+            _ => self.sum[k - 1] / F::from(self.n).unwrap(),
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<const N: usize, F: FloatCore + Float> Moments<N, F> {
+    /// Calculate the `k`th standardized moment of the sample.
+    ///
+    /// This is the `k`th central moment divided by the `k`th power of the
+    /// population standard deviation, so the third and fourth standardized
+    /// moments are the skewness and the raw kurtosis. Returns NaN for `k >= 2`
+    /// if the variance of the sample is zero.
+    ///
+    /// Panics if `k > N`.
+    #[inline]
+    pub fn standardized_moment(&self, k: usize) -> F {
+        let moment = self.central_moment(k);
+        if k < 2 {
+            return moment;
+        }
+        let variance = self.central_moment(2);
+        if variance == F::zero() {
+            return FloatCore::nan();
+        }
+        moment / FloatCore::powi(variance.sqrt(), k as i32)
+    }
+}
+
+impl<const N: usize, F: FloatCore> core::default::Default for Moments<N, F> {
+    fn default() -> Moments<N, F> {
+        Moments::new()
+    }
+}
+
+impl<const N: usize, F: FloatCore> Estimate<F> for Moments<N, F> {
+    #[inline]
+    fn add(&mut self, x: F) {
+        if self.n == 0 {
+            self.n = checked_len::<F>(1);
+            self.avg = x;
+            return;
+        }
+        self.merge_inner(1, x, &[F::zero(); N]);
+    }
+
+    #[inline]
+    fn estimate(&self) -> F {
+        self.mean()
+    }
+}
+
+impl<const N: usize, F: FloatCore> Merge for Moments<N, F> {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Moments, Merge};
+    ///
+    /// let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    /// let (left, right) = sequence.split_at(3);
+    /// let total: Moments<5> = sequence.iter().map(|x| *x).collect();
+    /// let mut left: Moments<5> = left.iter().map(|x| *x).collect();
+    /// let right: Moments<5> = right.iter().map(|x| *x).collect();
+    /// left.merge(&right);
+    /// assert!((total.central_moment(4) - left.central_moment(4)).abs() < 1e-12);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &Moments<N, F>) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other.clone();
+            return;
+        }
+        self.merge_inner(other.n, other.avg, &other.sum);
+    }
+}
+
+impl<const N: usize, F: FloatCore> core::iter::FromIterator<F> for Moments<N, F> {
+    fn from_iter<T>(iter: T) -> Moments<N, F>
+        where T: IntoIterator<Item=F>
+    {
+        let mut e = Moments::new();
+        for i in iter {
+            e.add(i);
+        }
+        e
+    }
+}
+
+impl<const N: usize, F: FloatCore> core::iter::Extend<F> for Moments<N, F> {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=F>
+    {
+        for i in iter {
+            self.add(i);
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const N: usize, F: Serialize> Serialize for Moments<N, F> {
+    /// Serialize the estimator as a tuple of the sample size, the mean and the
+    /// intermediate sums.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeTuple;
+        let mut tuple = serializer.serialize_tuple(N + 2)?;
+        tuple.serialize_element(&self.n)?;
+        tuple.serialize_element(&self.avg)?;
+        for sum in &self.sum {
+            tuple.serialize_element(sum)?;
+        }
+        tuple.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize, F: FloatCore + Deserialize<'de>> Deserialize<'de> for Moments<N, F> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Moments<N, F>, D::Error> {
+        use core::marker::PhantomData;
+        use serde::de::{Error, SeqAccess, Visitor};
+
+        struct MomentsVisitor<const N: usize, F>(PhantomData<F>);
+
+        impl<'de, const N: usize, F: FloatCore + Deserialize<'de>> Visitor<'de> for MomentsVisitor<N, F> {
+            type Value = Moments<N, F>;
+
+            fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(formatter, "a tuple of length {}", N + 2)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Moments<N, F>, A::Error> {
+                let mut e = Moments::new();
+                e.n = seq.next_element()?.ok_or_else(|| Error::invalid_length(0, &self))?;
+                e.avg = seq.next_element()?.ok_or_else(|| Error::invalid_length(1, &self))?;
+                for (i, sum) in e.sum.iter_mut().enumerate() {
+                    *sum = seq.next_element()?.ok_or_else(|| Error::invalid_length(i + 2, &self))?;
+                }
+                Ok(e)
+            }
+        }
+
+        deserializer.deserialize_tuple(N + 2, MomentsVisitor(PhantomData))
+    }
+}
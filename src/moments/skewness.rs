@@ -2,24 +2,53 @@
 /// numbers ("population").
 ///
 /// This can be used to estimate the standard error of the mean.
-#[derive(Debug, Clone)]
-pub struct Skewness {
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Skewness<F = f64> {
     /// Estimator of mean and variance.
-    avg: MeanWithError,
+    avg: MeanWithError<F>,
     /// Intermediate sum of cubes for calculating the skewness.
-    sum_3: f64,
+    sum_3: F,
 }
 
-impl Skewness {
+impl<F: FloatCore> Skewness<F> {
     /// Create a new skewness estimator.
     #[inline]
-    pub fn new() -> Skewness {
+    pub fn new() -> Skewness<F> {
         Skewness {
             avg: MeanWithError::new(),
-            sum_3: 0.,
+            sum_3: F::zero(),
         }
     }
 
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Skewness::new();
+    }
+
+    /// Create a new skewness estimator from the sample size, the mean and the
+    /// sums of the squared and cubed differences from the mean.
+    ///
+    /// The caller must ensure that the statistics are consistent, see
+    /// [`Variance::from_raw`]. `sum_3` is `Σ(x - mean)³`.
+    ///
+    /// Panics if `sum_2` is negative or NaN, or if `count` is 0 but one of the
+    /// other statistics is not.
+    ///
+    /// [`Variance::from_raw`]: ./struct.Variance.html#method.from_raw
+    #[inline]
+    pub fn from_raw(count: u64, mean: F, sum_2: F, sum_3: F) -> Skewness<F> {
+        assert!(count > 0 || sum_3 == F::zero(), "sum_3 of an empty sample must be 0");
+        Skewness { avg: Variance::from_raw(count, mean, sum_2), sum_3 }
+    }
+
+    /// Return the sufficient statistics, to be restored with `from_raw`.
+    #[inline]
+    pub fn raw_moments(&self) -> RawMoments<F> {
+        RawMoments { sum_3: self.sum_3, ..self.avg.raw_moments() }
+    }
+
     /// Increment the sample size.
     ///
     /// This does not update anything else.
@@ -34,17 +63,48 @@ impl Skewness {
     ///
     /// This is useful for avoiding unnecessary divisions in the inner loop.
     #[inline]
-    fn add_inner(&mut self, delta: f64, delta_n: f64) {
+    fn add_inner(&mut self, delta: F, delta_n: F) {
         // This algorithm was suggested by Terriberry.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let n = f64::approx_from(self.len()).unwrap();
-        let term = delta * delta_n * (n - 1.);
-        self.sum_3 += term * delta_n * (n - 2.)
-            - 3.*delta_n * self.avg.sum_2;
+        //
+        // The sample size is maintained as `F` by `Mean`, which avoids
+        // converting it in the inner loop.
+        let n = self.avg.avg.count;
+        let two = F::from(2).unwrap();
+        let three = F::from(3).unwrap();
+        let term = delta * delta_n * (n - F::one());
+        self.sum_3 = self.sum_3 + term * delta_n * (n - two)
+            - three*delta_n * self.avg.sum_2;
         self.avg.add_inner(delta_n);
     }
 
+    /// Create a new skewness estimator from a slice of observations.
+    #[inline]
+    fn from_slice(xs: &[F]) -> Skewness<F> {
+        let avg = Variance::from_slice(xs);
+        let mean = avg.mean();
+        let sum_3 = pairwise_sum(xs, move |x| {
+            let d = x - mean;
+            d*d*d
+        });
+        Skewness { avg, sum_3 }
+    }
+
+    /// Add a slice of observations.
+    ///
+    /// This is faster than calling `add` for each element, because the slice
+    /// is summarized in bulk and then merged into the estimator. The result
+    /// agrees with repeated calls to `add` up to rounding errors, which are
+    /// usually smaller for `add_slice`.
+    #[inline]
+    pub fn add_slice(&mut self, xs: &[F]) {
+        if xs.is_empty() {
+            return;
+        }
+        self.merge(&Skewness::from_slice(xs));
+    }
+
     /// Determine whether the sample is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -55,7 +115,7 @@ impl Skewness {
     ///
     /// Returns 0 for an empty sample.
     #[inline]
-    pub fn mean(&self) -> f64 {
+    pub fn mean(&self) -> F {
         self.avg.mean()
     }
 
@@ -69,7 +129,7 @@ impl Skewness {
     ///
     /// This is an unbiased estimator of the variance of the population.
     #[inline]
-    pub fn sample_variance(&self) -> f64 {
+    pub fn sample_variance(&self) -> F {
         self.avg.sample_variance()
     }
 
@@ -77,63 +137,200 @@ impl Skewness {
     ///
     /// This is a biased estimator of the variance of the population.
     #[inline]
-    pub fn population_variance(&self) -> f64 {
+    pub fn population_variance(&self) -> F {
         self.avg.population_variance()
     }
 
+    /// Calculate the second central moment of the sample.
+    ///
+    /// This is equal to the population variance. Returns 0 for an empty
+    /// sample.
+    #[inline]
+    pub fn central_moment_2(&self) -> F {
+        self.avg.central_moment_2()
+    }
+
+    /// Calculate the third central moment of the sample.
+    ///
+    /// This is the mean of the cubed differences from the mean. Returns 0 for
+    /// an empty sample.
+    #[inline]
+    pub fn central_moment_3(&self) -> F {
+        let n = self.len();
+        if n == 0 {
+            return F::zero();
+        }
+        self.sum_3 / F::from(n).unwrap()
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<F: FloatCore + Float> Skewness<F> {
+    /// Calculate the sample standard deviation.
+    ///
+    /// This is the square root of the sample variance.
+    #[inline]
+    pub fn sample_std_dev(&self) -> F {
+        self.avg.sample_std_dev()
+    }
+
+    /// Calculate the population standard deviation of the sample.
+    ///
+    /// This is the square root of the population variance.
+    #[inline]
+    pub fn std_dev(&self) -> F {
+        self.avg.std_dev()
+    }
+
+    /// Calculate the coefficient of variation of the sample.
+    ///
+    /// Returns NaN if the mean is zero.
+    #[inline]
+    pub fn coefficient_of_variation(&self) -> F {
+        self.avg.coefficient_of_variation()
+    }
+
+    /// Calculate the sample coefficient of variation.
+    ///
+    /// Returns NaN if the mean is zero.
+    #[inline]
+    pub fn sample_coefficient_of_variation(&self) -> F {
+        self.avg.sample_coefficient_of_variation()
+    }
+
     /// Estimate the standard error of the mean of the population.
     #[inline]
-    pub fn error_mean(&self) -> f64 {
+    pub fn error_mean(&self) -> F {
         self.avg.error()
     }
 
     /// Estimate the skewness of the population.
+    ///
+    /// This is the biased Fisher-Pearson coefficient of skewness `g1` of the
+    /// sample. See `sample_skewness` for the bias-corrected estimator.
+    ///
+    /// Returns NaN if the variance of the sample is zero (including samples
+    /// with less than two observations), because the skewness is not defined
+    /// in that case.
     #[inline]
-    pub fn skewness(&self) -> f64 {
-        if self.sum_3 == 0. {
-            return 0.;
-        }
-        let n = f64::approx_from(self.len()).unwrap();
+    pub fn skewness(&self) -> F {
         let sum_2 = self.avg.sum_2;
-        debug_assert_ne!(sum_2, 0.);
+        if sum_2 == F::zero() {
+            return FloatCore::nan();
+        }
+        let n = self.avg.avg.count;
         n.sqrt() * self.sum_3 / (sum_2*sum_2*sum_2).sqrt()
     }
+
+    /// Calculate the sample skewness.
+    ///
+    /// This is the adjusted Fisher-Pearson coefficient of skewness
+    /// `G1 = g1 sqrt(n(n - 1))/(n - 2)`, which is reported by most statistics
+    /// packages and spreadsheets. Returns NaN for samples with less than three
+    /// observations or with zero variance.
+    #[inline]
+    pub fn sample_skewness(&self) -> F {
+        let n = self.len();
+        if n < 3 {
+            return FloatCore::nan();
+        }
+        let n = F::from(n).unwrap();
+        self.skewness() * (n * (n - F::one())).sqrt() / (n - F::from(2).unwrap())
+    }
+
+    /// Estimate the standard error of the skewness of the population.
+    ///
+    /// This uses the large-sample formula
+    /// `sqrt(6n(n - 1)/((n - 2)(n + 1)(n + 3)))`, which only depends on the
+    /// sample size. Returns NaN for samples with less than three observations.
+    #[inline]
+    pub fn error_skewness(&self) -> F {
+        let n = self.len();
+        if n < 3 {
+            return FloatCore::nan();
+        }
+        let n = F::from(n).unwrap();
+        let one = F::one();
+        let two = F::from(2).unwrap();
+        let three = F::from(3).unwrap();
+        let six = F::from(6).unwrap();
+        (six * n * (n - one) / ((n - two) * (n + one) * (n + three))).sqrt()
+    }
 }
 
-impl Default for Skewness {
-    fn default() -> Skewness {
+impl<F: FloatCore> core::default::Default for Skewness<F> {
+    fn default() -> Skewness<F> {
         Skewness::new()
     }
 }
 
-impl Estimate for Skewness {
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<F: FloatCore + Float> Estimate<F> for Skewness<F> {
     #[inline]
-    fn add(&mut self, x: f64) {
+    fn add(&mut self, x: F) {
         let delta = x - self.mean();
         self.increment();
-        let n = f64::approx_from(self.len()).unwrap();
+        let n = self.avg.avg.count;
         self.add_inner(delta, delta/n);
     }
 
     #[inline]
-    fn estimate(&self) -> f64 {
+    fn estimate(&self) -> F {
         self.skewness()
     }
 }
 
-impl Merge for Skewness {
+impl<F: FloatCore> Merge for Skewness<F> {
     #[inline]
-    fn merge(&mut self, other: &Skewness) {
-        let len_self = f64::approx_from(self.len()).unwrap();
-        let len_other = f64::approx_from(other.len()).unwrap();
+    fn merge(&mut self, other: &Skewness<F>) {
+        if other.is_empty() {
+            return;
+        }
+        let len_self = self.avg.avg.count;
+        let len_other = other.avg.avg.count;
         let len_total = len_self + len_other;
         let delta = other.mean() - self.mean();
         let delta_n = delta / len_total;
-        self.sum_3 += other.sum_3
+        let three = F::from(3).unwrap();
+        self.sum_3 = self.sum_3 + other.sum_3
             + delta*delta_n*delta_n * len_self*len_other*(len_self - len_other)
-            + 3.*delta_n * (len_self * other.avg.sum_2 - len_other * self.avg.sum_2);
+            + three*delta_n * (len_self * other.avg.sum_2 - len_other * self.avg.sum_2);
         self.avg.merge(&other.avg);
     }
 }
 
-impl_from_iterator!(Skewness);
+impl<F: FloatCore> From<Skewness<F>> for Variance<F> {
+    /// Discard the skewness of the sample, keeping only the mean and the variance.
+    ///
+    /// This is cheap, because `Skewness` already contains a `Variance`.
+    #[inline]
+    fn from(x: Skewness<F>) -> Variance<F> {
+        x.avg
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<F: FloatCore + Float + fmt::Display> fmt::Display for Skewness<F> {
+    /// Format the sample size, the mean, the sample standard
+    /// deviation and the skewness on one line.
+    ///
+    /// The precision of the formatter is used for the statistics, e.g.
+    /// `Skewness { n: 3, mean: 2.00, std: 1.00, skewness: 0.00 }` for `{:.2}`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_summary(f, "Skewness", self.len(), &[
+            ("mean", self.mean()),
+            ("std", self.sample_std_dev()),
+            ("skewness", self.skewness()),
+        ])
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl_generic_from_iterator!(Skewness, FloatCore + Float);
+#[cfg(any(feature = "std", feature = "libm"))]
+impl_generic_from_par_iter!(Skewness, FloatCore + Float);
+#[cfg(any(feature = "std", feature = "libm"))]
+impl_generic_from_array!(Skewness, FloatCore + Float);
+#[cfg(any(feature = "std", feature = "libm"))]
+impl_generic_extend!(Skewness, FloatCore + Float);
+impl_generic_sum!(Skewness);
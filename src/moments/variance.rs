@@ -12,19 +12,61 @@
 /// let a: Variance = (1..6).map(Into::into).collect();
 /// println!("The mean is {} ± {}.", a.mean(), a.error());
 /// ```
-#[derive(Debug, Clone)]
-pub struct Variance {
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Variance<F = f64> {
     /// Estimator of average.
-    avg: Mean,
+    avg: Mean<F>,
     /// Intermediate sum of squares for calculating the variance.
-    sum_2: f64,
+    sum_2: F,
 }
 
-impl Variance {
+impl<F: FloatCore> Variance<F> {
     /// Create a new variance estimator.
     #[inline]
-    pub fn new() -> Variance {
-        Variance { avg: Mean::new(), sum_2: 0. }
+    pub fn new() -> Variance<F> {
+        Variance { avg: Mean::new(), sum_2: F::zero() }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Variance::new();
+    }
+
+    /// Create a new variance estimator from the sample size, the mean and the
+    /// sum of the squared differences from the mean.
+    ///
+    /// The caller must ensure that the statistics are consistent: `sum_2` is
+    /// `Σ(x - mean)²` over `count` observations with unit weight, so it is 0
+    /// for less than two observations.
+    ///
+    /// Panics if `sum_2` is negative or NaN, or if `count` is 0 but one of the
+    /// other statistics is not.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Variance, Estimate};
+    ///
+    /// let mut a = Variance::from_raw(2, 2., 2.);
+    /// a.add(3.);
+    /// let b: Variance = [1., 3., 3.].iter().map(|x| *x).collect();
+    /// assert_eq!(a.mean(), b.mean());
+    /// assert!((a.sample_variance() - b.sample_variance()).abs() < 1e-15);
+    /// ```
+    #[inline]
+    pub fn from_raw(count: u64, mean: F, sum_2: F) -> Variance<F> {
+        assert!(sum_2 >= F::zero(), "sum_2 must not be negative");
+        assert!(count > 0 || sum_2 == F::zero(), "sum_2 of an empty sample must be 0");
+        Variance { avg: Mean::from_raw(count, mean), sum_2 }
+    }
+
+    /// Return the sufficient statistics, to be restored with `from_raw`.
+    #[inline]
+    pub fn raw_moments(&self) -> RawMoments<F> {
+        RawMoments { sum_2: self.sum_2, ..self.avg.raw_moments() }
     }
 
     /// Increment the sample size.
@@ -41,14 +83,37 @@ impl Variance {
     ///
     /// This is useful for avoiding unnecessary divisions in the inner loop.
     #[inline]
-    fn add_inner(&mut self, delta_n: f64) {
+    fn add_inner(&mut self, delta_n: F) {
         // This algorithm introduced by Welford in 1962 trades numerical
         // stability for a division inside the loop.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let n = f64::approx_from(self.avg.len()).unwrap();
+        let n = self.avg.sum_weights();
         self.avg.add_inner(delta_n);
-        self.sum_2 += delta_n * delta_n * n * (n - 1.);
+        self.sum_2 = self.sum_2 + delta_n * delta_n * n * (n - F::one());
+    }
+
+    /// Create a new variance estimator from a slice of observations.
+    #[inline]
+    fn from_slice(xs: &[F]) -> Variance<F> {
+        let avg = Mean::from_slice(xs);
+        let mean = avg.mean();
+        let sum_2 = pairwise_sum(xs, move |x| (x - mean)*(x - mean));
+        Variance { avg, sum_2 }
+    }
+
+    /// Add a slice of observations.
+    ///
+    /// This is faster than calling `add` for each element, because the slice
+    /// is summarized in bulk and then merged into the estimator. The result
+    /// agrees with repeated calls to `add` up to rounding errors, which are
+    /// usually smaller for `add_slice`.
+    #[inline]
+    pub fn add_slice(&mut self, xs: &[F]) {
+        if xs.is_empty() {
+            return;
+        }
+        self.merge(&Variance::from_slice(xs));
     }
 
     /// Determine whether the sample is empty.
@@ -61,7 +126,7 @@ impl Variance {
     ///
     /// Returns 0 for an empty sample.
     #[inline]
-    pub fn mean(&self) -> f64 {
+    pub fn mean(&self) -> F {
         self.avg.mean()
     }
 
@@ -71,63 +136,288 @@ impl Variance {
         self.avg.len()
     }
 
+    /// Return the sum of the weights.
+    ///
+    /// This is equal to the sample size, unless samples were merged using
+    /// `merge_weighted`. The variance is normalized by the sum of the
+    /// weights. Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum_weights(&self) -> F {
+        self.avg.sum_weights()
+    }
+
     /// Calculate the sample variance.
     ///
     /// This is an unbiased estimator of the variance of the population.
     #[inline]
-    pub fn sample_variance(&self) -> f64 {
-        if self.avg.len() < 2 {
-            return 0.;
+    pub fn sample_variance(&self) -> F {
+        let n = self.avg.sum_weights();
+        if self.avg.len() < 2 || n <= F::one() {
+            return F::zero();
         }
-        self.sum_2 / f64::approx_from(self.avg.len() - 1).unwrap()
+        self.sum_2 / (n - F::one())
     }
 
     /// Calculate the population variance of the sample.
     ///
     /// This is a biased estimator of the variance of the population.
     #[inline]
-    pub fn population_variance(&self) -> f64 {
-        let n = self.avg.len();
-        if n < 2 {
-            return 0.;
+    pub fn population_variance(&self) -> F {
+        if self.avg.len() < 2 {
+            return F::zero();
+        }
+        self.sum_2 / self.avg.sum_weights()
+    }
+
+    /// Calculate the second central moment of the sample.
+    ///
+    /// This is the mean of the squared differences from the mean, which is
+    /// equal to the population variance. Returns 0 for an empty sample.
+    #[inline]
+    pub fn central_moment_2(&self) -> F {
+        if self.avg.is_empty() {
+            return F::zero();
+        }
+        self.sum_2 / self.avg.sum_weights()
+    }
+
+    /// Merge another sample into this one, scaling its contribution by
+    /// `weight`.
+    ///
+    /// The other sample is treated as if each of its observations had been
+    /// observed `weight` times, so it represents `weight` times its sum of
+    /// weights (see [`Mean::merge_weighted`]). This is useful for stratified
+    /// sampling, where each sample is drawn from a stratum of known size.
+    /// The mean and the population variance are then estimates for the
+    /// combined population.
+    ///
+    /// The sample size `len()` still counts the actual observations, while
+    /// the variances are normalized by `sum_weights()`. Note that the sample
+    /// variance and the error are calculated as if the scaled observations
+    /// were independent, so they underestimate the uncertainty for weights
+    /// larger than 1. `merge(other)` is equivalent to
+    /// `merge_weighted(other, 1.)`.
+    ///
+    /// Panics if the weight is not positive.
+    ///
+    /// [`Mean::merge_weighted`]: ./struct.Mean.html#method.merge_weighted
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::Variance;
+    ///
+    /// let mut a: Variance = [1., 3.].iter().map(|x| *x).collect();
+    /// let b: Variance = [5., 7.].iter().map(|x| *x).collect();
+    /// a.merge_weighted(&b, 2.);
+    /// let c: Variance = [1., 3., 5., 5., 7., 7.].iter().map(|x| *x).collect();
+    /// assert_eq!(a.mean(), c.mean());
+    /// assert!((a.population_variance() - c.population_variance()).abs() < 1e-12);
+    /// ```
+    #[inline]
+    pub fn merge_weighted(&mut self, other: &Variance<F>, weight: F) {
+        assert!(weight > F::zero(), "weight must be positive");
+        if other.is_empty() {
+            return;
+        }
+        // This algorithm was proposed by Chan et al. in 1979.
+        //
+        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
+        let len_self = self.avg.sum_weights();
+        let len_other = weight * other.avg.sum_weights();
+        let len_total = len_self + len_other;
+        let delta = other.mean() - self.mean();
+        self.avg.merge_weighted(&other.avg, weight);
+        // All terms are non-negative, so there is no cancellation, even if
+        // the sample sizes are very different. Dividing by `len_total` first
+        // avoids overflowing for huge sample sizes.
+        self.sum_2 = self.sum_2 + weight * other.sum_2
+            + delta*delta * (len_self / len_total) * len_other;
+    }
+
+    /// Remove an observation from the sample.
+    ///
+    /// This reverses a previous `add` of the same value by undoing Welford's
+    /// update. Note that the result is only exact up to floating-point error,
+    /// which accumulates over repeated cycles of adding and removing
+    /// observations.
+    ///
+    /// Panics if the sample is empty.
+    #[inline]
+    pub fn remove(&mut self, x: F) {
+        let delta = x - self.avg.mean();
+        self.avg.remove(x);
+        if self.avg.len() < 2 {
+            self.sum_2 = F::zero();
+            return;
+        }
+        self.sum_2 = self.sum_2 - delta * (x - self.avg.mean());
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<F: FloatCore + Float> Variance<F> {
+    /// Calculate the sample standard deviation.
+    ///
+    /// This is the square root of the sample variance. Returns 0 for samples
+    /// with less than two observations.
+    #[inline]
+    pub fn sample_std_dev(&self) -> F {
+        self.sample_variance().sqrt()
+    }
+
+    /// Calculate the population standard deviation of the sample.
+    ///
+    /// This is the square root of the population variance. Returns 0 for
+    /// samples with less than two observations.
+    #[inline]
+    pub fn std_dev(&self) -> F {
+        self.population_variance().sqrt()
+    }
+
+    /// Calculate the coefficient of variation of the sample.
+    ///
+    /// This is the population standard deviation divided by the mean. Returns
+    /// NaN if the mean is zero, because the ratio is meaningless in that case.
+    #[inline]
+    pub fn coefficient_of_variation(&self) -> F {
+        let mean = self.mean();
+        if mean == F::zero() {
+            return FloatCore::nan();
         }
-        self.sum_2 / f64::approx_from(n).unwrap()
+        self.std_dev() / mean
+    }
+
+    /// Calculate the sample coefficient of variation.
+    ///
+    /// This is the sample standard deviation divided by the mean. Returns NaN
+    /// if the mean is zero, because the ratio is meaningless in that case.
+    #[inline]
+    pub fn sample_coefficient_of_variation(&self) -> F {
+        let mean = self.mean();
+        if mean == F::zero() {
+            return FloatCore::nan();
+        }
+        self.sample_std_dev() / mean
     }
 
     /// Estimate the standard error of the mean of the population.
     #[inline]
-    pub fn error(&self) -> f64 {
+    pub fn error(&self) -> F {
+        if self.avg.is_empty() {
+            return F::zero();
+        }
+        (self.sample_variance() / self.avg.sum_weights()).sqrt()
+    }
+
+    /// Estimate a confidence interval for the mean of the population, using
+    /// the normal approximation.
+    ///
+    /// This is `mean ± z*error`, where `z` is the critical value of the
+    /// standard normal distribution for the confidence `level` (e.g. 0.95).
+    /// The approximation is good for large samples; see
+    /// `confidence_interval_t` for small ones. Returns NaN bounds for samples
+    /// with less than two observations, because the error is not defined.
+    ///
+    /// Panics if `level` is not between 0 and 1 (exclusive).
+    #[inline]
+    pub fn confidence_interval(&self, level: F) -> (F, F) {
+        let level = level.to_f64().unwrap();
+        assert!(level > 0. && level < 1., "level must be between 0 and 1");
+        if self.avg.len() < 2 {
+            return (FloatCore::nan(), FloatCore::nan());
+        }
+        let z = F::from(normal_quantile(0.5 + 0.5 * level)).unwrap();
+        let (mean, error) = (self.mean(), self.error());
+        (mean - z * error, mean + z * error)
+    }
+
+    /// Estimate a confidence interval for the mean of the population, using
+    /// Student's t-distribution.
+    ///
+    /// This is `mean ± t*error`, where `t` is the critical value of the
+    /// t-distribution with `len() - 1` degrees of freedom for the confidence
+    /// `level` (e.g. 0.95). It is exact for normally distributed populations,
+    /// and wider than `confidence_interval` for small samples. Returns NaN
+    /// bounds for samples with less than two observations.
+    ///
+    /// Panics if `level` is not between 0 and 1 (exclusive).
+    #[inline]
+    pub fn confidence_interval_t(&self, level: F) -> (F, F) {
+        let level = level.to_f64().unwrap();
+        assert!(level > 0. && level < 1., "level must be between 0 and 1");
         let n = self.avg.len();
-        if n == 0 {
-            return 0.;
+        if n < 2 {
+            return (FloatCore::nan(), FloatCore::nan());
+        }
+        let df = (n - 1).approx_as::<f64>().unwrap();
+        let t = F::from(student_t_quantile(0.5 + 0.5 * level, df)).unwrap();
+        let (mean, error) = (self.mean(), self.error());
+        (mean - t * error, mean + t * error)
+    }
+
+    /// Calculate how many standard deviations an observation is away from
+    /// the mean.
+    ///
+    /// This uses the population standard deviation. Returns NaN if it is
+    /// zero, which is the case for samples with less than two observations.
+    #[inline]
+    pub fn z_score(&self, x: F) -> F {
+        let std_dev = self.std_dev();
+        if std_dev == F::zero() {
+            return FloatCore::nan();
         }
-        (self.sample_variance() / f64::approx_from(n).unwrap()).sqrt()
+        (x - self.mean()) / std_dev
     }
 
+    /// Replace each element of a slice by its z-score.
+    ///
+    /// This normalizes the elements using the mean and the population
+    /// standard deviation of the estimator, as calculated by `z_score`. All
+    /// elements become NaN if the standard deviation is zero.
+    #[inline]
+    pub fn standardize_slice(&self, xs: &mut [F]) {
+        let (mean, std_dev) = (self.mean(), self.std_dev());
+        let inv_std_dev = if std_dev == F::zero() {
+            FloatCore::nan()
+        } else {
+            F::one() / std_dev
+        };
+        for x in xs {
+            *x = (*x - mean) * inv_std_dev;
+        }
+    }
 }
 
-impl core::default::Default for Variance {
-    fn default() -> Variance {
+impl<F: FloatCore> core::default::Default for Variance<F> {
+    fn default() -> Variance<F> {
         Variance::new()
     }
 }
 
-impl Estimate for Variance {
+impl<F: FloatCore> Estimate<F> for Variance<F> {
     #[inline]
-    fn add(&mut self, sample: f64) {
+    fn add(&mut self, sample: F) {
         self.increment();
-        let delta_n = (sample - self.avg.mean())
-            / f64::approx_from(self.len()).unwrap();
+        let delta_n = (sample - self.avg.mean()) / self.avg.sum_weights();
         self.add_inner(delta_n);
     }
 
     #[inline]
-    fn estimate(&self) -> f64 {
-        self.population_variance()
+    fn estimate(&self) -> F {
+        self.sample_variance()
     }
 }
 
-impl Merge for Variance {
+impl<F: FloatCore> Remove<F> for Variance<F> {
+    #[inline]
+    fn remove(&mut self, x: F) {
+        Variance::remove(self, x);
+    }
+}
+
+impl<F: FloatCore> Merge for Variance<F> {
     /// Merge another sample into this one.
     ///
     ///
@@ -146,17 +436,76 @@ impl Merge for Variance {
     /// assert_eq!(avg_total.sample_variance(), avg_left.sample_variance());
     /// ```
     #[inline]
-    fn merge(&mut self, other: &Variance) {
-        // This algorithm was proposed by Chan et al. in 1979.
-        //
-        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let len_self = f64::approx_from(self.len()).unwrap();
-        let len_other = f64::approx_from(other.len()).unwrap();
-        let len_total = len_self + len_other;
-        let delta = other.mean() - self.mean();
-        self.avg.merge(&other.avg);
-        self.sum_2 += other.sum_2 + delta*delta * len_self * len_other / len_total;
+    fn merge(&mut self, other: &Variance<F>) {
+        self.merge_weighted(other, F::one());
+    }
+}
+
+impl<F: FloatCore> From<Variance<F>> for Mean<F> {
+    /// Discard the variance of the sample, keeping only the mean.
+    ///
+    /// This is cheap, because `Variance` already contains a `Mean`.
+    #[inline]
+    fn from(x: Variance<F>) -> Mean<F> {
+        x.avg
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<F: FloatCore + Float + fmt::Display> fmt::Display for Variance<F> {
+    /// Format the sample size, the mean and the sample standard
+    /// deviation on one line.
+    ///
+    /// The precision of the formatter is used for the statistics, e.g.
+    /// `Variance { n: 3, mean: 2.00, std: 1.00 }` for `{:.2}`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_summary(f, "Variance", self.len(), &[
+            ("mean", self.mean()),
+            ("std", self.sample_std_dev()),
+        ])
     }
 }
 
-impl_from_iterator!(Variance);
+impl_generic_from_iterator!(Variance);
+impl_generic_from_par_iter!(Variance);
+impl_generic_from_array!(Variance);
+impl_generic_extend!(Variance);
+impl_generic_sum!(Variance);
+
+/// Calculate Welch's t-test statistic for the difference of the means of two
+/// populations.
+///
+/// In contrast to Student's t-test, the variances of the populations do not
+/// have to be equal. Returns the t statistic and the degrees of freedom of
+/// the Welch-Satterthwaite approximation, which can be used to look up a
+/// p-value. Both are NaN if one of the samples has less than two
+/// observations, or if both samples have zero variance, because the
+/// statistic is undefined without any spread.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Variance, welch_t};
+///
+/// let a: Variance = [19.8, 20.4, 19.6, 17.8, 18.5].iter().map(|x| *x).collect();
+/// let b: Variance = [28.2, 26.6, 20.1, 23.3, 25.2].iter().map(|x| *x).collect();
+/// let (t, df) = welch_t(&a, &b);
+/// println!("t = {} with {} degrees of freedom", t, df);
+/// ```
+#[cfg(any(feature = "std", feature = "libm"))]
+pub fn welch_t<F: FloatCore + Float>(a: &Variance<F>, b: &Variance<F>) -> (F, F) {
+    if a.len() < 2 || b.len() < 2 {
+        return (FloatCore::nan(), FloatCore::nan());
+    }
+    // The squared standard errors of the means.
+    let (e_a, e_b) = (FloatCore::powi(a.error(), 2), FloatCore::powi(b.error(), 2));
+    if e_a + e_b == F::zero() {
+        return (FloatCore::nan(), FloatCore::nan());
+    }
+    let t = (a.mean() - b.mean()) / (e_a + e_b).sqrt();
+    let df = FloatCore::powi(e_a + e_b, 2) / (
+        FloatCore::powi(e_a, 2) / F::from(a.len() - 1).unwrap()
+        + FloatCore::powi(e_b, 2) / F::from(b.len() - 1).unwrap());
+    (t, df)
+}
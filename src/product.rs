@@ -0,0 +1,151 @@
+use core;
+
+use num_traits::Float;
+
+use super::{Estimate, Merge};
+
+/// Calculate the product of a sequence of numbers.
+///
+/// The logarithms of the absolute values are accumulated, and the sign and
+/// zero factors are tracked separately. This avoids the overflow and the
+/// underflow of multiplying many factors directly, as long as the product
+/// itself is representable. Otherwise, `ln_abs_product` can be used, which is
+/// useful for likelihoods.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Product;
+///
+/// let a: Product = [2., -3., 1e200, 1e-200].iter().map(|x| *x).collect();
+/// assert!((a.product() + 6.).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Product {
+    /// Sum of the logarithms of the absolute values of the nonzero factors.
+    sum_log: f64,
+    /// Whether an odd number of negative factors was observed.
+    negative: bool,
+    /// Whether a zero factor was observed.
+    zero: bool,
+    /// Sample size.
+    n: u64,
+}
+
+impl Product {
+    /// Create a new product estimator.
+    #[inline]
+    pub fn new() -> Product {
+        Product { sum_log: 0., negative: false, zero: false, n: 0 }
+    }
+
+    /// Reset the estimator to an empty sequence.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Product::new();
+    }
+
+    /// Determine whether the sequence is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the number of factors.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Calculate the product.
+    ///
+    /// Returns 1 for an empty sequence and 0 if a factor was 0. Returns NaN
+    /// if a factor was NaN, or if 0 and an infinite factor were observed,
+    /// because their product is not defined.
+    #[inline]
+    pub fn product(&self) -> f64 {
+        if self.sum_log.is_nan() || (self.zero && self.sum_log.is_infinite()) {
+            return f64::NAN;
+        }
+        if self.zero {
+            return 0.;
+        }
+        let abs = Float::exp(self.sum_log);
+        if self.negative { -abs } else { abs }
+    }
+
+    /// Calculate the logarithm of the absolute value of the product.
+    ///
+    /// This can be used when the product itself overflows or underflows.
+    /// Returns `-inf` if a factor was 0.
+    #[inline]
+    pub fn ln_abs_product(&self) -> f64 {
+        if self.zero && !self.sum_log.is_nan() {
+            return f64::NEG_INFINITY;
+        }
+        self.sum_log
+    }
+
+    /// Determine whether the product is negative.
+    ///
+    /// This is independent of whether a factor was 0.
+    #[inline]
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+}
+
+impl core::default::Default for Product {
+    fn default() -> Product {
+        Product::new()
+    }
+}
+
+impl Estimate for Product {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        if x == 0. {
+            self.zero = true;
+        } else {
+            // NaN propagates to the product.
+            self.sum_log += Float::ln(x.abs());
+            self.negative ^= x.is_sign_negative();
+        }
+        self.n += 1;
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.product()
+    }
+}
+
+impl Merge for Product {
+    /// Merge another sequence into this one.
+    ///
+    /// The result is the product of both products.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Product, Merge};
+    ///
+    /// let mut a: Product = [2., -3.].iter().map(|x| *x).collect();
+    /// let b: Product = [-1., 4.].iter().map(|x| *x).collect();
+    /// a.merge(&b);
+    /// assert!((a.product() - 24.).abs() < 1e-12);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &Product) {
+        self.sum_log += other.sum_log;
+        self.negative ^= other.negative;
+        self.zero |= other.zero;
+        self.n += other.n;
+    }
+}
+
+impl_from_iterator!(Product);
+impl_extend!(Product);
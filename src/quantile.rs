@@ -2,12 +2,19 @@ use core;
 use core::cmp::min;
 
 use conv::{ApproxFrom, ConvAsUtil, ConvUtil, ValueFrom};
+use num_traits::float::FloatCore;
 use quickersort::sort_floats;
+#[cfg(feature = "std")]
+use std::vec::Vec;
 
-use super::Estimate;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Deserialize};
+
+use super::{Estimate, Merge};
 
 /// Estimate the p-quantile of a sequence of numbers ("population").
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Quantile {
     /// Marker heights.
     q: [f64; 5],
@@ -25,7 +32,7 @@ impl Quantile {
     /// Panics if `p` is not between 0 and 1.
     #[inline]
     pub fn new(p: f64) -> Quantile {
-        assert!(0. <= p && p <= 1.);
+        assert!((0. ..=1.).contains(&p));
         Quantile {
             q: [0.; 5],
             n: [1, 2, 3, 4, 0],
@@ -34,67 +41,135 @@ impl Quantile {
         }
     }
 
+    /// Create a new p-quantile estimator, buffering the first `k`
+    /// observations before switching to the P² algorithm.
+    ///
+    /// The P² algorithm initializes its markers from the first 5
+    /// observations, so the estimates can be inaccurate until it has seen
+    /// many more. Instead, the first `k` observations are sorted, and the
+    /// markers are initialized to their exact quantiles. Until then, the
+    /// exact p-quantile of the buffered observations is returned. This
+    /// improves the accuracy for small and medium samples, at the cost of
+    /// memory for `k` observations. The buffer is kept in a separate
+    /// [`WarmupQuantile`], which is only available with the `std` feature.
+    ///
+    /// Panics if `p` is not between 0 and 1 or if `k` is less than 5.
+    ///
+    /// [`WarmupQuantile`]: ./struct.WarmupQuantile.html
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Quantile, Estimate};
+    ///
+    /// let mut q = Quantile::with_warmup(0.9, 100);
+    /// for i in 1..11 {
+    ///     q.add(f64::from(i));
+    /// }
+    /// assert_eq!(q.quantile(), 9.5);
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn with_warmup(p: f64, k: usize) -> WarmupQuantile {
+        WarmupQuantile::new(p, k)
+    }
+
+    /// Create a new median estimator.
+    ///
+    /// This is the same as `Quantile::new(0.5)`.
+    #[inline]
+    pub fn median() -> Quantile {
+        Quantile::new(0.5)
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The value of `p` is kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Quantile::new(self.p());
+    }
+
     /// Return the value of `p` for this p-quantile.
     #[inline]
     pub fn p(&self) -> f64 {
         self.dm[2]
     }
 
-    /// Parabolic prediction for marker height.
-    #[inline]
-    fn parabolic(&self, i: usize, d: f64) -> f64 {
-        debug_assert_eq!(d.abs(), 1.);
-        let s: i64 = d.approx().unwrap();
-        self.q[i] + d / f64::approx_from(self.n[i + 1] - self.n[i - 1]).unwrap()
-            * (f64::approx_from(self.n[i] - self.n[i - 1] + s).unwrap()
-               * (self.q[i + 1] - self.q[i])
-               / f64::approx_from(self.n[i + 1] - self.n[i]).unwrap()
-               + f64::approx_from(self.n[i + 1] - self.n[i] - s).unwrap()
-               * (self.q[i] - self.q[i - 1])
-               / f64::approx_from(self.n[i] - self.n[i - 1]).unwrap())
+    /// Create a p-quantile estimator with the markers initialized to the
+    /// quantiles of at least 5 sorted observations.
+    #[cfg(feature = "std")]
+    fn from_sorted(p: f64, sorted: &[f64]) -> Quantile {
+        let mut quantile = Quantile::new(p);
+        quantile.reset_positions(i64::value_from(sorted.len()).unwrap());
+        for i in 0..5 {
+            quantile.q[i] = sorted[usize::value_from(quantile.n[i] - 1).unwrap()];  // n[i] >= 1
+        }
+        quantile
     }
 
-    /// Linear prediction for marker height.
-    #[inline]
-    fn linear(&self, i: usize, d: f64) -> f64 {
-        debug_assert_eq!(d.abs(), 1.);
-        let s: usize = d.approx().unwrap();
-        self.q[i] + d * (self.q[i + s] - self.q[i])
-            / f64::approx_from(self.n[i + s] - self.n[i]).unwrap()
+    /// Move the markers to their desired positions for a sample of size
+    /// `total`, keeping them distinct.
+    fn reset_positions(&mut self, total: i64) {
+        let len_total = f64::approx_from(total).unwrap();
+        for i in 0..5 {
+            self.m[i] = 1. + (len_total - 1.) * self.dm[i];
+        }
+        self.n[0] = 1;
+        self.n[4] = total;
+        for i in 1..4 {
+            let n: i64 = FloatCore::round(self.m[i]).approx().unwrap();
+            self.n[i] = n.max(self.n[i - 1] + 1);
+        }
+        for i in (1..4).rev() {
+            self.n[i] = self.n[i].min(self.n[i + 1] - 1);
+        }
     }
 
     /// Estimate the p-quantile of the population.
     ///
-    /// Returns 0 for an empty sample.
+    /// The P² markers are only updated after 5 observations. For smaller
+    /// samples, the observations are sorted and the p-quantile of the sample
+    /// is returned instead: This is the observation with the rank
+    /// `ceil(p*len)`, or the average of two adjacent observations if `p*len`
+    /// is an integer. Returns 0 for an empty sample.
     #[inline]
     pub fn quantile(&self) -> f64 {
-        if self.len() >= 5 {
+        if self.len() > 5 {
             return self.q[2];
         }
 
-        // Estimate quantile by sorting the sample.
+        // Estimate quantile by sorting the sample. For 5 observations, the
+        // markers were just initialized to the sorted observations.
+        let mut heights = self.q;
+        let len = usize::value_from(self.len()).unwrap();  // <= 5
+        sort_floats(&mut heights[..len]);
+        sorted_quantile(&heights[..len], self.p())
+    }
+
+    /// Estimate the fraction of the population that is below `x`.
+    ///
+    /// This is the inverse of the quantile, estimated by linearly
+    /// interpolating the ranks between the markers (or between the sorted
+    /// observations for samples with less than 5 observations). Returns 0 if
+    /// `x` is below the minimum and 1 if `x` is above the maximum of the
+    /// sample. Returns 0 for an empty sample and NaN if `x` is NaN.
+    #[inline]
+    pub fn percentile_rank(&self, x: f64) -> f64 {
+        if x.is_nan() {
+            return f64::NAN;
+        }
         if self.is_empty() {
             return 0.;
         }
-        let mut heights: [f64; 4] = [
-            self.q[0], self.q[1], self.q[2], self.q[3]
-        ];
-        let len = usize::value_from(self.len()).unwrap();  // < 5
-        sort_floats(&mut heights[..len]);
-        let desired_index = ConvUtil::approx_as::<f64>(len).unwrap() * self.p() - 1.;
-        let mut index = desired_index.ceil();
-        if desired_index == index && index >= 0. {
-            let index: usize = index.approx().unwrap();  // < 5
-            if index < len - 1 {
-                // `q[index]` and `q[index + 1]` are equally valid estimates,
-                // by convention we take their average.
-                return 0.5*self.q[index] + 0.5*self.q[index + 1];
-            }
+        if self.n[4] < 5 {
+            let mut heights = self.q;
+            let len = usize::value_from(self.n[4]).unwrap();  // < 5
+            sort_floats(&mut heights[..len]);
+            return sorted_rank(&heights[..len], x);
         }
-        index = index.max(0.);
-        let mut index: usize = index.approx().unwrap();  // < 5
-        index = min(index, len - 1);
-        self.q[index]
+        interpolate_rank(&self.q, |i| f64::approx_from(self.n[i]).unwrap(), x)
     }
 
     /// Return the sample size.
@@ -110,10 +185,102 @@ impl Quantile {
     }
 }
 
+/// Parabolic prediction for the height of marker `i`.
+#[inline]
+fn parabolic(q: &[f64], n: &[i64], i: usize, d: f64) -> f64 {
+    debug_assert_eq!(d.abs(), 1.);
+    let s: i64 = d.approx().unwrap();
+    q[i] + d / f64::approx_from(n[i + 1] - n[i - 1]).unwrap()
+        * (f64::approx_from(n[i] - n[i - 1] + s).unwrap()
+           * (q[i + 1] - q[i])
+           / f64::approx_from(n[i + 1] - n[i]).unwrap()
+           + f64::approx_from(n[i + 1] - n[i] - s).unwrap()
+           * (q[i] - q[i - 1])
+           / f64::approx_from(n[i] - n[i - 1]).unwrap())
+}
+
+/// Linear prediction for the height of marker `i`.
+#[inline]
+fn linear(q: &[f64], n: &[i64], i: usize, d: f64) -> f64 {
+    debug_assert_eq!(d.abs(), 1.);
+    let j = if d > 0. { i + 1 } else { i - 1 };
+    q[i] + d * (q[j] - q[i]) / f64::approx_from(n[j] - n[i]).unwrap()
+}
+
+/// Insert `x` into the sorted observations, keeping NaN last like
+/// `sort_floats`.
+#[cfg(feature = "std")]
+#[inline]
+fn insert_sorted(sorted: &mut Vec<f64>, x: f64) {
+    let i = if x.is_nan() {
+        sorted.len()
+    } else {
+        sorted.partition_point(|&y| y <= x)
+    };
+    sorted.insert(i, x);
+}
+
+/// Calculate the p-quantile of sorted observations, as documented for
+/// `Quantile::quantile`.
+fn sorted_quantile(heights: &[f64], p: f64) -> f64 {
+    let len = heights.len();
+    if len == 0 {
+        return 0.;
+    }
+    let desired_index = ConvUtil::approx_as::<f64>(len).unwrap() * p - 1.;
+    let mut index = FloatCore::ceil(desired_index);
+    if desired_index == index && index >= 0. {
+        let index: usize = index.approx().unwrap();  // < len
+        if index < len - 1 {
+            // `heights[index]` and `heights[index + 1]` are equally valid
+            // estimates, by convention we take their average.
+            return 0.5*heights[index] + 0.5*heights[index + 1];
+        }
+    }
+    index = index.max(0.);
+    let mut index: usize = index.approx().unwrap();  // < len
+    index = min(index, len - 1);
+    heights[index]
+}
+
+/// Calculate the rank of `x` in sorted observations as a fraction of the
+/// sample, interpolating linearly between them.
+///
+/// The observations must not be empty and `x` must not be NaN.
+#[inline]
+fn sorted_rank(sorted: &[f64], x: f64) -> f64 {
+    interpolate_rank(sorted, |i| i.approx_as::<f64>().unwrap() + 1., x)
+}
+
+/// Interpolate the rank of `x` linearly between the given heights and their
+/// positions, returning it as a fraction of the sample.
+///
+/// The heights must be sorted, with NaN last. `x` must not be NaN.
+fn interpolate_rank<P: Fn(usize) -> f64>(heights: &[f64], position: P, x: f64) -> f64 {
+    let last = heights.len() - 1;
+    if x < heights[0] {
+        return 0.;
+    }
+    // Find `i` with `heights[i] <= x < heights[i + 1]`. If there is none, `x`
+    // is not below any of the heights except NaN.
+    let i = match (0..last).find(|&i| x < heights[i + 1]) {
+        Some(i) => i,
+        None => return 1.,
+    };
+    let rank = position(i) + (x - heights[i]) / (heights[i + 1] - heights[i])
+        * (position(i + 1) - position(i));
+    (rank - 1.) / (position(last) - 1.)
+}
+
 impl core::default::Default for Quantile {
     /// Create a new median estimator.
+    ///
+    /// A quantile estimator is not meaningful without a value of `p`, but the
+    /// median is the most common choice. This makes it possible to use
+    /// `Quantile` with [`concatenate`](./macro.concatenate.html) and in
+    /// structs deriving `Default`. Use `Quantile::new(p)` for other quantiles.
     fn default() -> Quantile {
-        Quantile::new(0.5)
+        Quantile::median()
     }
 }
 
@@ -162,11 +329,11 @@ impl Estimate for Quantile {
             if d >= 1. && self.n[i + 1] - self.n[i] > 1 ||
                d <= -1. && self.n[i - 1] - self.n[i] < -1 {
                 let d = d.signum();
-                let q_new = self.parabolic(i, d);
+                let q_new = parabolic(&self.q, &self.n, i, d);
                 if self.q[i - 1] < q_new && q_new < self.q[i + 1] {
                     self.q[i] = q_new;
                 } else {
-                    self.q[i] = self.linear(i, d);
+                    self.q[i] = linear(&self.q, &self.n, i, d);
                 }
                 let delta: i64 = d.approx().unwrap();  // d == +-1
                 self.n[i] += delta;
@@ -179,6 +346,778 @@ impl Estimate for Quantile {
     }
 }
 
+impl Merge for Quantile {
+    /// Merge another sample into this one.
+    ///
+    /// The P² algorithm does not define how to merge estimators, so this is an
+    /// approximation: The marker heights are averaged weighted by the sample
+    /// sizes and the marker positions are reset to their desired positions.
+    /// The result is usually close to the estimate of a single pass over both
+    /// samples, but it is less accurate. If one of the samples has less than 5
+    /// observations, its observations are added exactly.
+    ///
+    /// Panics if the values of `p` are different.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Quantile, Estimate, Merge};
+    ///
+    /// let mut q_left = Quantile::new(0.5);
+    /// let mut q_right = Quantile::new(0.5);
+    /// for i in 0..100 {
+    ///     q_left.add(f64::from(i));
+    ///     q_right.add(f64::from(i + 100));
+    /// }
+    /// q_left.merge(&q_right);
+    /// assert_eq!(q_left.len(), 200);
+    /// assert!((q_left.quantile() - 100.).abs() < 5.);
+    /// ```
+    fn merge(&mut self, other: &Quantile) {
+        assert_eq!(self.p(), other.p(), "cannot merge quantiles with different p");
+        if other.n[4] < 5 {
+            let len = usize::value_from(other.n[4]).unwrap();  // < 5
+            for &x in &other.q[..len] {
+                self.add(x);
+            }
+            return;
+        }
+        if self.n[4] < 5 {
+            let len = usize::value_from(self.n[4]).unwrap();  // < 5
+            let mut merged = other.clone();
+            for &x in &self.q[..len] {
+                merged.add(x);
+            }
+            *self = merged;
+            return;
+        }
+
+        // Average the marker heights. The extreme markers are the exact
+        // minimum and maximum.
+        let len_self = f64::approx_from(self.n[4]).unwrap();
+        let len_other = f64::approx_from(other.n[4]).unwrap();
+        let len_total = len_self + len_other;
+        for i in 1..4 {
+            self.q[i] = (len_self * self.q[i] + len_other * other.q[i]) / len_total;
+        }
+        self.q[0] = self.q[0].min(other.q[0]);
+        self.q[4] = self.q[4].max(other.q[4]);
+
+        self.reset_positions(self.n[4] + other.n[4]);
+    }
+}
+
+/// Estimate the p-quantile of a sequence of numbers ("population"), buffering
+/// the first observations to initialize the P² markers exactly.
+///
+/// This is created with [`Quantile::with_warmup`]. The buffer is kept sorted,
+/// so adding an observation during the warm-up takes `O(k)` time, while the
+/// queries do not need to sort. Afterwards, this behaves like a [`Quantile`].
+/// This is only available with the `std` feature.
+///
+/// [`Quantile::with_warmup`]: ./struct.Quantile.html#method.with_warmup
+/// [`Quantile`]: ./struct.Quantile.html
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WarmupQuantile {
+    /// Estimator of the quantile, which is empty until the warm-up is over.
+    quantile: Quantile,
+    /// Number of observations to buffer before initializing the markers.
+    warmup_len: usize,
+    /// Sorted observations buffered before initializing the markers.
+    warmup: Vec<f64>,
+}
+
+#[cfg(feature = "std")]
+impl WarmupQuantile {
+    /// Create a new p-quantile estimator, buffering the first `k`
+    /// observations.
+    ///
+    /// Panics if `p` is not between 0 and 1 or if `k` is less than 5.
+    #[inline]
+    pub fn new(p: f64, k: usize) -> WarmupQuantile {
+        assert!(k >= 5, "the warm-up must have at least 5 observations");
+        WarmupQuantile {
+            quantile: Quantile::new(p),
+            warmup_len: k,
+            warmup: Vec::with_capacity(k),
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The values of `p` and `k` are kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.quantile.clear();
+        self.warmup.clear();
+    }
+
+    /// Return the value of `p` for this p-quantile.
+    #[inline]
+    pub fn p(&self) -> f64 {
+        self.quantile.p()
+    }
+
+    /// Determine whether the observations are still buffered, because the
+    /// markers are not initialized yet.
+    #[inline]
+    fn is_warming_up(&self) -> bool {
+        self.quantile.is_empty()
+    }
+
+    /// Estimate the p-quantile of the population.
+    ///
+    /// During the warm-up, this is the p-quantile of the buffered
+    /// observations, as documented for `Quantile::quantile`. Returns 0 for an
+    /// empty sample.
+    #[inline]
+    pub fn quantile(&self) -> f64 {
+        if self.is_warming_up() {
+            return sorted_quantile(&self.warmup, self.p());
+        }
+        self.quantile.quantile()
+    }
+
+    /// Estimate the fraction of the population that is below `x`.
+    ///
+    /// During the warm-up, the ranks are interpolated between the buffered
+    /// observations, as documented for `Quantile::percentile_rank`.
+    #[inline]
+    pub fn percentile_rank(&self, x: f64) -> f64 {
+        if !self.is_warming_up() {
+            return self.quantile.percentile_rank(x);
+        }
+        if x.is_nan() {
+            return f64::NAN;
+        }
+        if self.warmup.is_empty() {
+            return 0.;
+        }
+        sorted_rank(&self.warmup, x)
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        if self.is_warming_up() {
+            return u64::value_from(self.warmup.len()).unwrap();
+        }
+        self.quantile.len()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(feature = "std")]
+impl Estimate for WarmupQuantile {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        if !self.is_warming_up() {
+            self.quantile.add(x);
+            return;
+        }
+        insert_sorted(&mut self.warmup, x);
+        if self.warmup.len() == self.warmup_len {
+            self.quantile = Quantile::from_sorted(self.p(), &self.warmup);
+            self.warmup.clear();
+        }
+    }
+
+    fn estimate(&self) -> f64 {
+        self.quantile()
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::iter::Extend<f64> for WarmupQuantile {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=f64>
+    {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Merge for WarmupQuantile {
+    /// Merge another sample into this one.
+    ///
+    /// This is the same approximation as merging [`Quantile`]. If one of the
+    /// samples is still warming up, its buffered observations are added
+    /// exactly.
+    ///
+    /// Panics if the values of `p` are different.
+    ///
+    /// [`Quantile`]: ./struct.Quantile.html
+    fn merge(&mut self, other: &WarmupQuantile) {
+        assert_eq!(self.p(), other.p(), "cannot merge quantiles with different p");
+        if other.is_warming_up() {
+            for &x in &other.warmup {
+                self.add(x);
+            }
+            return;
+        }
+        if self.is_warming_up() {
+            let mut merged = other.quantile.clone();
+            for &x in &self.warmup {
+                merged.add(x);
+            }
+            self.quantile = merged;
+            self.warmup.clear();
+            return;
+        }
+        self.quantile.merge(&other.quantile);
+    }
+}
+
+/// Estimate the interquartile range of a sequence of numbers ("population").
+///
+/// This is the difference between the 0.75-quantile and the 0.25-quantile,
+/// which are estimated using [`Quantile`].
+///
+/// [`Quantile`]: ./struct.Quantile.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::InterquartileRange;
+///
+/// let a: InterquartileRange = (1..101).map(f64::from).collect();
+/// println!("The interquartile range is {}.", a.iqr());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InterquartileRange {
+    /// Estimator of the lower quartile.
+    lower: Quantile,
+    /// Estimator of the upper quartile.
+    upper: Quantile,
+}
+
+impl InterquartileRange {
+    /// Create a new interquartile range estimator.
+    #[inline]
+    pub fn new() -> InterquartileRange {
+        InterquartileRange {
+            lower: Quantile::new(0.25),
+            upper: Quantile::new(0.75),
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = InterquartileRange::new();
+    }
+
+    /// Estimate the lower quartile (the 0.25-quantile) of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn lower_quartile(&self) -> f64 {
+        self.lower.quantile()
+    }
+
+    /// Estimate the upper quartile (the 0.75-quantile) of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn upper_quartile(&self) -> f64 {
+        self.upper.quantile()
+    }
+
+    /// Estimate the interquartile range of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn iqr(&self) -> f64 {
+        self.upper_quartile() - self.lower_quartile()
+    }
+
+    /// Calculate Tukey's fences `[Q1 - k*IQR, Q3 + k*IQR]`.
+    ///
+    /// Observations outside of the fences are considered outliers. Tukey
+    /// suggested `k = 1.5` for outliers and `k = 3` for far out observations.
+    ///
+    /// Panics if `k` is negative.
+    #[inline]
+    pub fn fences(&self, k: f64) -> (f64, f64) {
+        assert!(k >= 0., "k must not be negative");
+        let iqr = self.iqr();
+        (self.lower_quartile() - k * iqr, self.upper_quartile() + k * iqr)
+    }
+
+    /// Determine whether `x` is outside of Tukey's fences with the multiplier
+    /// `k`, using the current estimates of the quartiles.
+    ///
+    /// Returns `false` for an empty sample and for NaN. Panics if `k` is
+    /// negative.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::InterquartileRange;
+    ///
+    /// let a: InterquartileRange = (1..101).map(f64::from).collect();
+    /// assert!(!a.is_outlier(90., 1.5));
+    /// assert!(a.is_outlier(1000., 1.5));
+    /// assert!(a.is_outlier(-200., 1.5));
+    /// ```
+    #[inline]
+    pub fn is_outlier(&self, x: f64, k: f64) -> bool {
+        let (lower, upper) = self.fences(k);
+        !self.is_empty() && (x < lower || x > upper)
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.lower.len()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lower.is_empty()
+    }
+}
+
+impl core::default::Default for InterquartileRange {
+    fn default() -> InterquartileRange {
+        InterquartileRange::new()
+    }
+}
+
+impl Estimate for InterquartileRange {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.lower.add(x);
+        self.upper.add(x);
+    }
+
+    fn estimate(&self) -> f64 {
+        self.iqr()
+    }
+}
+
+impl Merge for InterquartileRange {
+    /// Merge another sample into this one.
+    ///
+    /// This has the same accuracy caveats as merging [`Quantile`].
+    ///
+    /// [`Quantile`]: ./struct.Quantile.html
+    fn merge(&mut self, other: &InterquartileRange) {
+        self.lower.merge(&other.lower);
+        self.upper.merge(&other.upper);
+    }
+}
+
+impl_from_iterator!(InterquartileRange);
+impl_extend!(InterquartileRange);
+
+/// Estimate the median absolute deviation of a sequence of numbers
+/// ("population").
+///
+/// This is the median of the absolute differences from the median, which is a
+/// robust alternative to the standard deviation.
+///
+/// Calculating it exactly requires two passes over the data, using the median
+/// of the first one in the second one. Instead, the median and the median of
+/// the absolute differences from the current estimate of the median are
+/// estimated at the same time with two [`Quantile`] estimators. This is only an
+/// approximation, which is poor for small samples and for populations whose
+/// median changes over time.
+///
+/// [`Quantile`]: ./struct.Quantile.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::MedianAbsoluteDeviation;
+///
+/// let a: MedianAbsoluteDeviation = (1..101).map(f64::from).collect();
+/// println!("The median absolute deviation is {}.", a.mad());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MedianAbsoluteDeviation {
+    /// Estimator of the median.
+    median: Quantile,
+    /// Estimator of the median of the absolute differences from the median.
+    deviation: Quantile,
+}
+
+impl MedianAbsoluteDeviation {
+    /// Create a new median absolute deviation estimator.
+    #[inline]
+    pub fn new() -> MedianAbsoluteDeviation {
+        MedianAbsoluteDeviation {
+            median: Quantile::median(),
+            deviation: Quantile::median(),
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = MedianAbsoluteDeviation::new();
+    }
+
+    /// Estimate the median of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn median(&self) -> f64 {
+        self.median.quantile()
+    }
+
+    /// Estimate the median absolute deviation of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mad(&self) -> f64 {
+        self.deviation.quantile()
+    }
+
+    /// Estimate the standard deviation of a normally distributed population
+    /// from the median absolute deviation.
+    ///
+    /// This is the median absolute deviation scaled by 1.4826, which makes it
+    /// a consistent estimator of the standard deviation for normally
+    /// distributed populations. Returns 0 for an empty sample.
+    #[inline]
+    pub fn scaled_mad(&self) -> f64 {
+        1.4826 * self.mad()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.median.len()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.median.is_empty()
+    }
+}
+
+impl core::default::Default for MedianAbsoluteDeviation {
+    fn default() -> MedianAbsoluteDeviation {
+        MedianAbsoluteDeviation::new()
+    }
+}
+
+impl Estimate for MedianAbsoluteDeviation {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.median.add(x);
+        self.deviation.add((x - self.median.quantile()).abs());
+    }
+
+    fn estimate(&self) -> f64 {
+        self.mad()
+    }
+}
+
+impl Merge for MedianAbsoluteDeviation {
+    /// Merge another sample into this one.
+    ///
+    /// This has the same accuracy caveats as merging [`Quantile`]. In
+    /// addition, the absolute differences of each sample were taken from its
+    /// own median, which is only accurate if the medians are similar.
+    ///
+    /// [`Quantile`]: ./struct.Quantile.html
+    fn merge(&mut self, other: &MedianAbsoluteDeviation) {
+        self.median.merge(&other.median);
+        self.deviation.merge(&other.deviation);
+    }
+}
+
+impl_from_iterator!(MedianAbsoluteDeviation);
+impl_extend!(MedianAbsoluteDeviation);
+
+/// Estimate several quantiles of a sequence of numbers ("population") at once.
+///
+/// This uses the extended P² algorithm, which maintains a single set of
+/// markers for all requested probabilities: One marker for each value of `p`,
+/// one for the minimum and the maximum, and one between each pair of adjacent
+/// markers. Each observation only updates this shared set of `2k + 3` markers
+/// for `k` distinct values of `p` between 0 and 1, instead of the `5k` markers
+/// of separate [`Quantile`] estimators. For a single value of `p`, these are
+/// the same markers as in `Quantile`. This is only available with the `std`
+/// feature.
+///
+/// [`Quantile`]: ./struct.Quantile.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Quantiles;
+///
+/// let mut a = Quantiles::new(&[0.5, 0.9, 0.99]);
+/// for i in 0..1000 {
+///     a.add(f64::from(i));
+/// }
+/// println!("The median is {} and the 90th percentile is {}.",
+///          a.quantile(0.5), a.quantile(0.9));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Quantiles {
+    /// Values of `p`, in the order passed to `new`.
+    ps: Vec<f64>,
+    /// Marker heights, or the sorted observations until there are enough of
+    /// them to initialize the markers.
+    q: Vec<f64>,
+    /// Marker positions.
+    n: Vec<i64>,
+    /// Desired marker positions.
+    m: Vec<f64>,
+    /// Increment in desired marker positions.
+    dm: Vec<f64>,
+}
+
+#[cfg(feature = "std")]
+impl Quantiles {
+    /// Create a new estimator of the p-quantiles for the given values of `p`.
+    ///
+    /// Panics if a value of `p` is not between 0 and 1.
+    pub fn new(ps: &[f64]) -> Quantiles {
+        for &p in ps {
+            assert!((0. ..=1.).contains(&p), "p must be between 0 and 1");
+        }
+        // The markers are at the minimum, the maximum and each value of `p`,
+        // with another marker halfway between each pair of them.
+        let mut probabilities = ps.to_vec();
+        probabilities.push(0.);
+        probabilities.push(1.);
+        sort_floats(&mut probabilities);
+        probabilities.dedup();
+        let mut dm = Vec::with_capacity(2 * probabilities.len() - 1);
+        for w in probabilities.windows(2) {
+            dm.push(w[0]);
+            dm.push((w[0] + w[1]) / 2.);
+        }
+        dm.push(1.);
+
+        let markers = dm.len();
+        Quantiles {
+            ps: ps.to_vec(),
+            q: Vec::with_capacity(markers),
+            n: vec![0; markers],
+            m: vec![0.; markers],
+            dm,
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The values of `p` are kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.q.clear();
+    }
+
+    /// Determine whether the markers are initialized, which happens as soon
+    /// as there is one observation per marker.
+    #[inline]
+    fn has_markers(&self) -> bool {
+        self.q.len() == self.dm.len()
+    }
+
+    /// Initialize the markers to the sorted observations.
+    fn init_markers(&mut self) {
+        let last = self.dm.len() - 1;
+        for i in 0..self.dm.len() {
+            self.n[i] = i64::value_from(i + 1).unwrap();
+            self.m[i] = 1. + f64::approx_from(last).unwrap() * self.dm[i];
+        }
+    }
+
+    /// Move the markers to their desired positions for a sample of size
+    /// `total`, keeping them distinct.
+    fn reset_positions(&mut self, total: i64) {
+        let len_total = f64::approx_from(total).unwrap();
+        let last = self.dm.len() - 1;
+        for i in 0..=last {
+            self.m[i] = 1. + (len_total - 1.) * self.dm[i];
+        }
+        self.n[0] = 1;
+        self.n[last] = total;
+        for i in 1..last {
+            let n: i64 = FloatCore::round(self.m[i]).approx().unwrap();
+            self.n[i] = n.max(self.n[i - 1] + 1);
+        }
+        for i in (1..last).rev() {
+            self.n[i] = self.n[i].min(self.n[i + 1] - 1);
+        }
+    }
+
+    /// Add an observation sampled from the population.
+    pub fn add(&mut self, x: f64) {
+        if !self.has_markers() {
+            insert_sorted(&mut self.q, x);
+            if self.has_markers() {
+                self.init_markers();
+            }
+            return;
+        }
+
+        // Find the first marker above `x`, whose position is incremented
+        // together with all following ones.
+        let last = self.dm.len() - 1;
+        if x < self.q[0] {
+            self.q[0] = x;
+        }
+        if self.q[last] < x {
+            self.q[last] = x;
+        }
+        let k = (1..last).find(|&i| x < self.q[i]).unwrap_or(last);
+        for n in &mut self.n[k..] {
+            *n += 1;
+        }
+        for (m, &dm) in self.m.iter_mut().zip(self.dm.iter()) {
+            *m += dm;
+        }
+
+        // Adjust height of markers.
+        for i in 1..last {
+            let d: f64 = self.m[i] - f64::approx_from(self.n[i]).unwrap();
+            if d >= 1. && self.n[i + 1] - self.n[i] > 1 ||
+               d <= -1. && self.n[i - 1] - self.n[i] < -1 {
+                let d = d.signum();
+                let q_new = parabolic(&self.q, &self.n, i, d);
+                if self.q[i - 1] < q_new && q_new < self.q[i + 1] {
+                    self.q[i] = q_new;
+                } else {
+                    self.q[i] = linear(&self.q, &self.n, i, d);
+                }
+                let delta: i64 = d.approx().unwrap();  // d == +-1
+                self.n[i] += delta;
+            }
+        }
+    }
+
+    /// Return the values of `p`, in the order passed to `new`.
+    #[inline]
+    pub fn ps(&self) -> &[f64] {
+        &self.ps
+    }
+
+    /// Estimate the p-quantile of the population.
+    ///
+    /// Until there are more observations than markers, the p-quantile of the
+    /// sorted sample is returned, as documented for `Quantile::quantile`.
+    /// Returns 0 for an empty sample. Panics if `p` was not passed to `new`.
+    pub fn quantile(&self, p: f64) -> f64 {
+        assert!(self.ps.contains(&p), "p-quantile is not estimated");
+        if self.len() <= u64::value_from(self.dm.len()).unwrap() {
+            // The markers were just initialized to the sorted observations,
+            // if at all.
+            return sorted_quantile(&self.q, p);
+        }
+        let i = self.dm.iter().position(|&dm| dm == p).unwrap();  // p in ps
+        self.q[i]
+    }
+
+    /// Estimate all p-quantiles of the population, in the order of the values
+    /// of `p` passed to `new`.
+    ///
+    /// Returns 0 for an empty sample.
+    pub fn quantiles(&self) -> Vec<f64> {
+        self.ps.iter().map(|&p| self.quantile(p)).collect()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        if self.has_markers() {
+            u64::value_from(self.n[self.dm.len() - 1]).unwrap()  // > 0
+        } else {
+            u64::value_from(self.q.len()).unwrap()
+        }
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.q.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::iter::Extend<f64> for Quantiles {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=f64>
+    {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Merge for Quantiles {
+    /// Merge another sample into this one.
+    ///
+    /// This is the same approximation as merging [`Quantile`]: The marker
+    /// heights are averaged weighted by the sample sizes and the marker
+    /// positions are reset to their desired positions. If one of the samples
+    /// has too few observations to initialize the markers, its observations
+    /// are added exactly.
+    ///
+    /// Panics if the values of `p` are different.
+    ///
+    /// [`Quantile`]: ./struct.Quantile.html
+    fn merge(&mut self, other: &Quantiles) {
+        assert_eq!(self.dm, other.dm, "cannot merge quantiles with different p");
+        if !other.has_markers() {
+            for &x in &other.q {
+                self.add(x);
+            }
+            return;
+        }
+        if !self.has_markers() {
+            let mut merged = other.clone();
+            for &x in &self.q {
+                merged.add(x);
+            }
+            merged.ps.clone_from(&self.ps);
+            *self = merged;
+            return;
+        }
+
+        // Average the marker heights. The extreme markers are the exact
+        // minimum and maximum.
+        let last = self.dm.len() - 1;
+        let len_self = f64::approx_from(self.n[last]).unwrap();
+        let len_other = f64::approx_from(other.n[last]).unwrap();
+        let len_total = len_self + len_other;
+        for i in 1..last {
+            self.q[i] = (len_self * self.q[i] + len_other * other.q[i]) / len_total;
+        }
+        self.q[0] = self.q[0].min(other.q[0]);
+        self.q[last] = self.q[last].max(other.q[last]);
+
+        self.reset_positions(self.n[last] + other.n[last]);
+    }
+}
+
 #[test]
 fn reference() {
     let observations = [
@@ -215,3 +1154,463 @@ fn few_observations() {
     assert_eq!(q.len(), 4);
     assert_eq!(q.quantile(), 2.5);
 }
+
+#[test]
+fn initialization() {
+    let mut q = Quantile::new(0.25);
+    q.add(5.);
+    assert_eq!(q.len(), 1);
+    assert_eq!(q.quantile(), 5.);
+    q.add(1.);
+    q.add(3.);
+    assert_eq!(q.len(), 3);
+    assert_eq!(q.quantile(), 1.);
+    q.add(4.);
+    assert_eq!(q.quantile(), 2.);
+    q.add(2.);
+    // The markers are initialized to the sorted observations.
+    assert_eq!(q.len(), 5);
+    assert!(!q.is_empty());
+    assert_eq!(q.q, [1., 2., 3., 4., 5.]);
+    assert_eq!(q.quantile(), 2.);
+}
+
+#[test]
+fn few_unsorted_observations() {
+    let mut q = Quantile::new(0.5);
+    q.add(3.);
+    q.add(1.);
+    assert_eq!(q.quantile(), 2.);
+    q.add(2.);
+    assert_eq!(q.quantile(), 2.);
+    q.add(-4.);
+    assert_eq!(q.quantile(), 1.5);
+}
+
+#[test]
+fn clear() {
+    let mut q = Quantile::new(0.9);
+    for i in 0..20 {
+        q.add(f64::from(i));
+    }
+    q.clear();
+    assert!(q.is_empty());
+    assert_eq!(q.len(), 0);
+    assert_eq!(q.p(), 0.9);
+    assert_eq!(q.quantile(), 0.);
+    q.add(1.);
+    assert_eq!(q.quantile(), 1.);
+}
+
+#[test]
+fn merge_few_observations() {
+    let mut a = Quantile::new(0.5);
+    let mut b = Quantile::new(0.5);
+    a.merge(&b);
+    assert!(a.is_empty());
+    for i in 0..3 {
+        a.add(f64::from(i));
+        b.add(f64::from(i + 3));
+    }
+    let mut c = a.clone();
+    c.merge(&b);
+    assert_eq!(c.len(), 6);
+    b.merge(&a);
+    assert_eq!(b.len(), 6);
+    for i in 6..20 {
+        b.add(f64::from(i));
+        c.add(f64::from(i));
+    }
+    assert_eq!(b.len(), 20);
+    assert_eq!(c.len(), 20);
+    assert_eq!(c.quantile(), b.quantile());
+}
+
+#[test]
+fn merge() {
+    // Pseudo-random permutation of `0..1000`.
+    let mut observations = [0.; 1000];
+    for (i, x) in observations.iter_mut().enumerate() {
+        *x = ((i * 7919) % 1000) as f64;
+    }
+    for &p in &[0., 0.1, 0.5, 0.9, 1.] {
+        for &mid in &[5, 200, 500, 900] {
+            let (left, right) = observations.split_at(mid);
+            let mut total = Quantile::new(p);
+            for &x in &observations {
+                total.add(x);
+            }
+            let mut q_left = Quantile::new(p);
+            for &x in left {
+                q_left.add(x);
+            }
+            let mut q_right = Quantile::new(p);
+            for &x in right {
+                q_right.add(x);
+            }
+            q_left.merge(&q_right);
+            assert_eq!(q_left.len(), 1000);
+            assert!((q_left.quantile() - total.quantile()).abs() < 20.,
+                    "p = {}, mid = {}: {} vs. {}", p, mid, q_left.quantile(), total.quantile());
+            for i in 1..5 {
+                assert!(q_left.n[i] > q_left.n[i - 1]);
+            }
+            for _ in 0..100 {
+                q_left.add(500.);
+            }
+            assert_eq!(q_left.len(), 1100);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn quantiles() {
+    let ps = [0.9, 0., 0.25, 0.5, 0.99, 1.];
+    let mut a = Quantiles::new(&ps);
+    assert!(a.is_empty());
+    assert_eq!(a.ps(), ps);
+    assert_eq!(a.quantiles(), [0.; 6]);
+    // The extremes, the four other values of `p` and the 5 midpoints.
+    assert_eq!(a.dm.len(), 11);
+    let mut individual: Vec<Quantile> = ps.iter().map(|&p| Quantile::new(p)).collect();
+    for i in 0..1000 {
+        let x = f64::from((i * 7919) % 1000);
+        a.add(x);
+        for q in &mut individual {
+            q.add(x);
+        }
+    }
+    assert_eq!(a.len(), 1000);
+    for (q, &p) in individual.iter().zip(ps.iter()) {
+        assert!((a.quantile(p) - q.quantile()).abs() < 10.,
+                "p = {}: {} vs. {}", p, a.quantile(p), q.quantile());
+    }
+    assert_eq!(a.quantile(0.), 0.);
+    assert_eq!(a.quantile(1.), 999.);
+    assert_eq!(a.quantiles()[0], a.quantile(0.9));
+    for i in 1..a.n.len() {
+        assert!(a.n[i] > a.n[i - 1]);
+        assert!(a.q[i] >= a.q[i - 1]);
+    }
+    a.clear();
+    assert!(a.is_empty());
+    assert_eq!(a.ps(), ps);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn quantiles_single() {
+    // For a single value of `p`, the markers are the same as for `Quantile`.
+    let observations = [
+        0.02, 0.5, 0.74, 3.39, 0.83,
+        22.37, 10.15, 15.43, 38.62, 15.92,
+        34.60, 10.28, 1.47, 0.40, 0.05,
+        11.39, 0.27, 0.42, 0.09, 11.37,
+    ];
+    let mut a = Quantiles::new(&[0.5]);
+    let mut q = Quantile::new(0.5);
+    for &x in observations.iter() {
+        a.add(x);
+        q.add(x);
+        assert_eq!(a.quantile(0.5), q.quantile());
+    }
+    assert_eq!(a.n, q.n);
+    assert_eq!(a.q, q.q);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn quantiles_few_observations() {
+    let mut a = Quantiles::new(&[0.25, 0.5]);
+    for &x in &[4., 1., 3., 2.] {
+        a.add(x);
+    }
+    assert_eq!(a.len(), 4);
+    assert_eq!(a.quantile(0.25), 1.5);
+    assert_eq!(a.quantile(0.5), 2.5);
+    for &x in &[5., 6., 7.] {
+        a.add(x);
+    }
+    // The markers are initialized to the sorted observations.
+    assert_eq!(a.len(), 7);
+    assert_eq!(a.q, [1., 2., 3., 4., 5., 6., 7.]);
+    assert_eq!(a.quantile(0.5), 4.);
+    a.add(8.);
+    assert_eq!(a.len(), 8);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn quantiles_merge() {
+    let ps = [0.5, 0.9];
+    let mut total = Quantiles::new(&ps);
+    let mut left = Quantiles::new(&ps);
+    let mut right = Quantiles::new(&[0.9, 0.5]);
+    for i in 0..1000 {
+        let x = f64::from((i * 7919) % 1000);
+        total.add(x);
+        if i < 300 {
+            left.add(x);
+        } else {
+            right.add(x);
+        }
+    }
+    let mut merged = left.clone();
+    merged.merge(&right);
+    assert_eq!(merged.len(), 1000);
+    assert_eq!(merged.ps(), ps);
+    for &p in &ps {
+        assert!((merged.quantile(p) - total.quantile(p)).abs() < 20.);
+    }
+
+    let mut few = Quantiles::new(&ps);
+    few.add(3.);
+    let mut merged = few.clone();
+    merged.merge(&left);
+    assert_eq!(merged.len(), 301);
+    left.merge(&few);
+    assert_eq!(left.len(), 301);
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic]
+fn quantiles_unknown_p() {
+    let a = Quantiles::new(&[0.25, 0.75]);
+    a.quantile(0.5);
+}
+
+#[test]
+fn median() {
+    let mut q = Quantile::median();
+    assert_eq!(q.p(), 0.5);
+    for &x in &[3., 1., 2.] {
+        q.add(x);
+    }
+    assert_eq!(q.quantile(), 2.);
+}
+
+#[test]
+fn interquartile_range() {
+    let mut a = InterquartileRange::new();
+    assert!(a.is_empty());
+    assert_eq!(a.iqr(), 0.);
+    for &x in &[4., 1., 3., 2.] {
+        a.add(x);
+    }
+    assert_eq!(a.len(), 4);
+    assert_eq!(a.lower_quartile(), 1.5);
+    assert_eq!(a.upper_quartile(), 3.5);
+    assert_eq!(a.iqr(), 2.);
+    assert_eq!(a.estimate(), 2.);
+
+    let mut lower = Quantile::new(0.25);
+    let mut upper = Quantile::new(0.75);
+    let mut a = InterquartileRange::new();
+    for i in 0..1000 {
+        let x = f64::from((i * 7919) % 1000);
+        a.add(x);
+        lower.add(x);
+        upper.add(x);
+    }
+    assert_eq!(a.lower_quartile(), lower.quantile());
+    assert_eq!(a.upper_quartile(), upper.quantile());
+    assert!((a.iqr() - 500.).abs() < 10.);
+    a.clear();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn percentile_rank() {
+    let mut q = Quantile::new(0.5);
+    assert_eq!(q.percentile_rank(1.), 0.);
+    q.add(2.);
+    assert_eq!(q.percentile_rank(1.), 0.);
+    assert_eq!(q.percentile_rank(2.), 1.);
+    q.add(3.);
+    q.add(1.);
+    assert_eq!(q.percentile_rank(0.), 0.);
+    assert_eq!(q.percentile_rank(1.), 0.);
+    assert_eq!(q.percentile_rank(1.5), 0.25);
+    assert_eq!(q.percentile_rank(2.), 0.5);
+    assert_eq!(q.percentile_rank(3.), 1.);
+    assert_eq!(q.percentile_rank(4.), 1.);
+
+    let mut q = Quantile::new(0.9);
+    for i in 0..1000 {
+        q.add(f64::from((i * 7919) % 1000));
+    }
+    assert_eq!(q.percentile_rank(-1.), 0.);
+    assert_eq!(q.percentile_rank(1000.), 1.);
+    assert!((q.percentile_rank(q.quantile()) - 0.9).abs() < 0.01);
+    assert!((q.percentile_rank(500.) - 0.5).abs() < 0.05);
+    let mut previous = 0.;
+    for i in 0..100 {
+        let rank = q.percentile_rank(f64::from(i * 10));
+        assert!(rank >= previous);
+        previous = rank;
+    }
+}
+
+#[test]
+fn percentile_rank_nan() {
+    let mut q = Quantile::new(0.5);
+    assert!(q.percentile_rank(f64::NAN).is_nan());
+    q.add(1.);
+    q.add(f64::NAN);
+    assert!(q.percentile_rank(f64::NAN).is_nan());
+    assert_eq!(q.percentile_rank(0.), 0.);
+    assert_eq!(q.percentile_rank(5.), 1.);
+    // The markers are initialized with the NaN, which must not panic.
+    for i in 0..10 {
+        q.add(f64::from(i));
+    }
+    assert!(q.percentile_rank(f64::NAN).is_nan());
+    assert_eq!(q.percentile_rank(-1.), 0.);
+    q.percentile_rank(5.);
+}
+
+#[test]
+fn median_absolute_deviation() {
+    let mut a = MedianAbsoluteDeviation::new();
+    assert!(a.is_empty());
+    assert_eq!(a.mad(), 0.);
+    a.add(3.);
+    assert_eq!(a.median(), 3.);
+    assert_eq!(a.mad(), 0.);
+
+    // Uniform on [0, 1000): The median absolute deviation is 250.
+    let mut a = MedianAbsoluteDeviation::new();
+    for i in 0..10_000 {
+        a.add(f64::from((i * 7919) % 1000));
+    }
+    a.add(1e9);
+    assert_eq!(a.len(), 10_001);
+    assert!((a.median() - 500.).abs() < 10.);
+    assert!((a.mad() - 250.).abs() < 10.);
+    assert_eq!(a.scaled_mad(), 1.4826 * a.mad());
+    assert_eq!(a.estimate(), a.mad());
+
+    let mut b = a.clone();
+    b.merge(&a);
+    assert_eq!(b.len(), 20_002);
+    assert!((b.mad() - 250.).abs() < 10.);
+    b.clear();
+    assert!(b.is_empty());
+}
+
+#[test]
+fn outliers() {
+    // A tight distribution around 10 with one obvious outlier.
+    let mut a = InterquartileRange::new();
+    for i in 0..1000 {
+        a.add(10. + f64::from(i % 21 - 10) * 0.01);
+    }
+    a.add(50.);
+    let (lower, upper) = a.fences(1.5);
+    assert!(lower < 9.9 && lower > 9.5);
+    assert!(upper > 10.1 && upper < 10.5);
+    assert!(a.is_outlier(50., 1.5));
+    assert!(a.is_outlier(9., 1.5));
+    assert!(!a.is_outlier(10.05, 1.5));
+    assert!(!a.is_outlier(f64::NAN, 1.5));
+    // The fences are wider for larger multipliers.
+    assert!(a.is_outlier(10.3, 1.5));
+    assert!(!a.is_outlier(10.3, 20.));
+
+    assert!(!InterquartileRange::new().is_outlier(1., 1.5));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn warmup() {
+    let mut q = Quantile::with_warmup(0.5, 10);
+    assert_eq!(q.quantile(), 0.);
+    assert_eq!(q.percentile_rank(1.), 0.);
+    for &x in &[5., 1., 4., 2., 3., 6.] {
+        q.add(x);
+    }
+    // Beyond 5 observations, the quantile of the buffer is still exact.
+    assert_eq!(q.len(), 6);
+    assert_eq!(q.quantile(), 3.5);
+    assert_eq!(q.percentile_rank(3.5), 0.5);
+    for &x in &[7., 8., 9., 10.] {
+        q.add(x);
+    }
+    // The markers are initialized to the quantiles of the buffer.
+    assert_eq!(q.len(), 10);
+    assert!(q.warmup.is_empty());
+    assert_eq!(q.quantile.n, [1, 3, 6, 8, 10]);
+    assert_eq!(q.quantile.q, [1., 3., 6., 8., 10.]);
+    q.add(11.);
+    assert_eq!(q.len(), 11);
+
+    q.clear();
+    assert!(q.is_empty());
+    for i in 0..9 {
+        q.add(f64::from(i));
+    }
+    assert_eq!(q.warmup.len(), 9);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn warmup_accuracy() {
+    // A skewed sequence, whose 0.9-quantile is 160^2.
+    let sequence: Vec<f64> = (0..200).map(|i| f64::from((i * 37) % 200).powi(2)).collect();
+    let mut plain = Quantile::new(0.9);
+    let mut warm = Quantile::with_warmup(0.9, 100);
+    for &x in &sequence {
+        plain.add(x);
+        warm.add(x);
+    }
+    let exact = 25_600.;
+    assert!((warm.quantile() - exact).abs() < (plain.quantile() - exact).abs());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn merge_warmup() {
+    let mut a = Quantile::with_warmup(0.5, 20);
+    let mut b = Quantile::with_warmup(0.5, 20);
+    for i in 0..10 {
+        a.add(f64::from(i));
+    }
+    for i in 10..19 {
+        b.add(f64::from(i));
+    }
+    let mut c = a.clone();
+    c.merge(&b);
+    assert_eq!(c.len(), 19);
+    assert_eq!(c.quantile(), 9.);
+    c.merge(&b);
+    assert_eq!(c.len(), 28);
+    assert!(c.warmup.is_empty());
+
+    let mut d = Quantile::with_warmup(0.5, 5);
+    for i in 20..100 {
+        d.add(f64::from(i));
+    }
+    let mut e = d.clone();
+    e.merge(&a);
+    assert_eq!(e.len(), 90);
+    a.merge(&d);
+    assert_eq!(a.len(), 90);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn warmup_sorted() {
+    let mut q = Quantile::with_warmup(0.5, 10);
+    for &x in &[5., 1., 4., 1., 3., 9.] {
+        q.add(x);
+    }
+    assert_eq!(q.warmup, [1., 1., 3., 4., 5., 9.]);
+    assert_eq!(q.quantile(), 3.5);
+    assert_eq!(q.percentile_rank(4.5), 0.7);
+    q.add(f64::NAN);
+    assert_eq!(q.percentile_rank(4.5), 7. / 12.);
+    assert_eq!(q.percentile_rank(10.), 1.);
+    assert!(q.percentile_rank(f64::NAN).is_nan());
+}
@@ -16,7 +16,7 @@ impl<F> Reduce<F> {
     /// Create a new reduction estimator given an initial value and a reduction.
     #[inline]
     pub fn from_value_and_fn(x: f64, f: F) -> Reduce<F> {
-        Reduce { x: x, reduce: f }
+        Reduce { x, reduce: f }
     }
 
     /// Estimate the reduction of the population.
@@ -0,0 +1,202 @@
+use conv::{ConvUtil, ValueFrom};
+use std::vec::Vec;
+
+use super::Merge;
+
+/// Draw a uniform random sample of fixed size from a sequence of numbers
+/// ("population").
+///
+/// Every observation has the same probability of being in the sample, without
+/// storing more than `k` of them. This is Vitter's Algorithm R.
+///
+/// The random numbers are generated by a small internal generator, so the
+/// sample only depends on the seed and the observations, which makes it
+/// reproducible. It is not suitable for cryptographic purposes. This type is
+/// only available with the `std` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::ReservoirSample;
+///
+/// let mut a = ReservoirSample::with_seed(10, 42);
+/// for i in 0..1000 {
+///     a.add(f64::from(i));
+/// }
+/// assert_eq!(a.samples().len(), 10);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReservoirSample {
+    /// Maximal number of samples.
+    k: usize,
+    /// Sampled observations.
+    samples: Vec<f64>,
+    /// Sample size.
+    n: u64,
+    /// State of the random number generator.
+    rng: SplitMix64,
+}
+
+impl ReservoirSample {
+    /// Create a new reservoir keeping a sample of size `k`, using a fixed
+    /// seed.
+    ///
+    /// Panics if `k` is 0.
+    #[inline]
+    pub fn new(k: usize) -> ReservoirSample {
+        ReservoirSample::with_seed(k, 0)
+    }
+
+    /// Create a new reservoir keeping a sample of size `k`, using the given
+    /// seed for the random number generator.
+    ///
+    /// Panics if `k` is 0.
+    #[inline]
+    pub fn with_seed(k: usize, seed: u64) -> ReservoirSample {
+        assert!(k > 0, "the sample must not be empty");
+        ReservoirSample {
+            k,
+            samples: Vec::with_capacity(k),
+            n: 0,
+            rng: SplitMix64 { state: seed },
+        }
+    }
+
+    /// Reset the reservoir to an empty sample.
+    ///
+    /// The state of the random number generator is kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.n = 0;
+    }
+
+    /// Add an observation.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        self.n += 1;
+        if self.samples.len() < self.k {
+            self.samples.push(x);
+            return;
+        }
+        let i = self.rng.below(self.n);
+        if i < self.k.value_as::<u64>().unwrap() {
+            self.samples[usize::value_from(i).unwrap()] = x;  // < k
+        }
+    }
+
+    /// Return the maximal number of samples.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.k
+    }
+
+    /// Return the sampled observations.
+    ///
+    /// These are all observations if there were at most `k` of them. Their
+    /// order is not meaningful.
+    #[inline]
+    pub fn samples(&self) -> &[f64] {
+        &self.samples
+    }
+
+    /// Return the number of observations added, including the ones that were
+    /// not sampled.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+}
+
+impl Merge for ReservoirSample {
+    /// Merge another sample into this one.
+    ///
+    /// The result is a uniform random sample of the observations of both
+    /// samples. The random number generator of `self` is used.
+    ///
+    /// Panics if the values of `k` differ.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{ReservoirSample, Merge};
+    ///
+    /// let mut a = ReservoirSample::with_seed(5, 1);
+    /// let mut b = ReservoirSample::with_seed(5, 2);
+    /// a.extend((0..100).map(f64::from));
+    /// b.extend((100..300).map(f64::from));
+    /// a.merge(&b);
+    /// assert_eq!(a.len(), 300);
+    /// assert_eq!(a.samples().len(), 5);
+    /// ```
+    fn merge(&mut self, other: &ReservoirSample) {
+        assert_eq!(self.k, other.k, "the values of k must be the same");
+        if other.is_empty() {
+            return;
+        }
+        // Each sampled observation stands for `n/samples.len()` observations.
+        // The number of samples taken from each reservoir therefore follows a
+        // hypergeometric distribution, which is simulated by drawing without
+        // replacement.
+        let mut left = core::mem::take(&mut self.samples);
+        let mut right = other.samples.clone();
+        let (mut n_left, mut n_right) = (self.n, other.n);
+        let len = core::cmp::min(left.len() + right.len(), self.k);
+        let mut samples = Vec::with_capacity(self.k);
+        while samples.len() < len {
+            let source = if self.rng.below(n_left + n_right) < n_left {
+                n_left -= 1;
+                &mut left
+            } else {
+                n_right -= 1;
+                &mut right
+            };
+            let i = self.rng.below(source.len().value_as::<u64>().unwrap());
+            samples.push(source.swap_remove(usize::value_from(i).unwrap()));
+        }
+        self.samples = samples;
+        self.n += other.n;
+    }
+}
+
+impl_extend!(ReservoirSample);
+
+/// The SplitMix64 random number generator.
+///
+/// See https://prng.di.unimi.it/splitmix64.c.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    /// Generate a random `u64`.
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Generate a random integer in `[0, n)`.
+    ///
+    /// This uses Lemire's multiplication method, whose bias is negligible for
+    /// `n` much smaller than `2^64`.
+    #[inline]
+    fn below(&mut self, n: u64) -> u64 {
+        debug_assert!(n > 0);
+        ((u128::from(self.next_u64()) * u128::from(n)) >> 64) as u64
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::VecDeque;
+
+use super::{Estimate, Remove};
+
+/// Estimate a statistic of the last observations of a sequence of numbers
+/// ("moving window").
+///
+/// The last `window` observations are kept in a ring buffer. When the window
+/// is full, adding an observation removes the oldest one from the inner
+/// estimator `E`, so each update takes O(1) time, independent of the size of
+/// the window. The inner estimator must support removing observations, like
+/// [`Mean`] and [`Variance`]. This type is only available with the `std`
+/// feature.
+///
+/// Rounding errors accumulate over many updates. For very long sequences,
+/// the estimator can be rebuilt from the window with `recalculate`.
+///
+/// [`Mean`]: ./struct.Mean.html
+/// [`Variance`]: ./struct.Variance.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Sliding, Mean, Estimate};
+///
+/// let mut a: Sliding<Mean> = Sliding::new(3);
+/// for &x in &[1., 2., 3., 4., 5.] {
+///     a.add(x);
+/// }
+/// assert_eq!(a.estimator().mean(), 4.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sliding<E, F = f64> {
+    /// Estimator of the observations in the window.
+    estimator: E,
+    /// Observations in the window, from the oldest to the newest.
+    recent: VecDeque<F>,
+    /// Maximal number of observations in the window.
+    window: usize,
+}
+
+impl<E, F> Sliding<E, F>
+    where E: Estimate<F> + Remove<F> + Default, F: Copy
+{
+    /// Create a new estimator over the last `window` observations.
+    ///
+    /// Panics if `window` is 0.
+    #[inline]
+    pub fn new(window: usize) -> Sliding<E, F> {
+        assert!(window > 0, "window must be at least 1");
+        Sliding {
+            estimator: E::default(),
+            recent: VecDeque::with_capacity(window),
+            window,
+        }
+    }
+
+    /// Reset the estimator to an empty window.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.estimator = E::default();
+        self.recent.clear();
+    }
+
+    /// Return the maximal number of observations in the window.
+    #[inline]
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Return the number of observations in the window.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.recent.len()
+    }
+
+    /// Determine whether the window is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.recent.is_empty()
+    }
+
+    /// Determine whether the window is full, so that the next observation
+    /// evicts the oldest one.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.recent.len() == self.window
+    }
+
+    /// Return the estimator of the observations in the window.
+    #[inline]
+    pub fn estimator(&self) -> &E {
+        &self.estimator
+    }
+
+    /// Iterate over the observations in the window, from the oldest to the
+    /// newest.
+    #[inline]
+    pub fn iter<'a>(&'a self) -> impl Iterator<Item=F> + 'a {
+        self.recent.iter().cloned()
+    }
+
+    /// Rebuild the estimator from the observations in the window.
+    ///
+    /// This discards the rounding errors accumulated by removing
+    /// observations and takes O(`window`) time.
+    pub fn recalculate(&mut self) {
+        let mut estimator = E::default();
+        for &x in &self.recent {
+            estimator.add(x);
+        }
+        self.estimator = estimator;
+    }
+}
+
+impl<E, F> Estimate<F> for Sliding<E, F>
+    where E: Estimate<F> + Remove<F> + Default, F: Copy
+{
+    #[inline]
+    fn add(&mut self, x: F) {
+        if self.is_full() {
+            let oldest = self.recent.pop_front().unwrap();
+            self.estimator.remove(oldest);
+        }
+        self.recent.push_back(x);
+        self.estimator.add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> F {
+        self.estimator.estimate()
+    }
+}
+
+impl<E, F> core::iter::Extend<F> for Sliding<E, F>
+    where E: Estimate<F> + Remove<F> + Default, F: Copy
+{
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=F>
+    {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
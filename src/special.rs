@@ -0,0 +1,201 @@
+use core::f64::consts::PI;
+
+use conv::ConvUtil;
+use num_traits::Float;
+
+/// Calculate the quantile function of the standard normal distribution.
+///
+/// This uses Acklam's rational approximation, whose relative error is less
+/// than `1.15e-9`. Returns `-inf` for 0 and `inf` for 1.
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1, 2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2, 1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1, 2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1, 1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2, 6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3, -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838, -2.549_732_539_343_734,
+        4.374_664_141_464_968, 2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3, 3.224_671_290_700_398e-1,
+        2.445_134_137_142_996, 3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+
+    debug_assert!((0. ..=1.).contains(&p));
+    if p == 0. {
+        return f64::NEG_INFINITY;
+    }
+    if p == 1. {
+        return f64::INFINITY;
+    }
+    // Approximation of the lower tail, which is mirrored for the upper tail.
+    let tail = |q: f64| {
+        let q = Float::sqrt(-2. * Float::ln(q));
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.)
+    };
+    if p < P_LOW {
+        tail(p)
+    } else if p > 1. - P_LOW {
+        -tail(1. - p)
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.)
+    }
+}
+
+/// Calculate the logarithm of the gamma function for positive `x`.
+///
+/// This uses the Lanczos approximation, which is accurate to about 15
+/// significant digits.
+pub fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.;
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9, 676.520_368_121_885_1, -1_259.139_216_722_402_8,
+        771.323_428_777_653_1, -176.615_029_162_140_6, 12.507_343_278_686_905,
+        -0.138_571_095_265_720_12, 9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    debug_assert!(x > 0.);
+    if x < 0.5 {
+        // Use the reflection formula, because the approximation is only
+        // accurate for `x >= 0.5`.
+        return Float::ln(PI / Float::sin(PI * x)) - ln_gamma(1. - x);
+    }
+    let x = x - 1.;
+    let mut a = COEFFICIENTS[0];
+    for (i, &c) in COEFFICIENTS.iter().enumerate().skip(1) {
+        a += c / (x + i.approx_as::<f64>().unwrap());
+    }
+    let t = x + G + 0.5;
+    0.5 * Float::ln(2. * PI) + (x + 0.5) * Float::ln(t) - t + Float::ln(a)
+}
+
+/// Evaluate the continued fraction of the regularized incomplete beta
+/// function using Lentz's method.
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const MAX_ITERATIONS: u32 = 300;
+    const EPSILON: f64 = 1e-15;
+    const TINY: f64 = 1e-300;
+    let not_tiny = |d: f64| if d.abs() < TINY { TINY } else { d };
+
+    let (qab, qap, qam) = (a + b, a + 1., a - 1.);
+    let mut c = 1.;
+    let mut d = 1. / not_tiny(1. - qab * x / qap);
+    let mut h = d;
+    for m in 1..=MAX_ITERATIONS {
+        let m = f64::from(m);
+        let m2 = 2. * m;
+        // Even step.
+        let aa = m * (b - m) * x / ((qam + m2) * (a + m2));
+        d = 1. / not_tiny(1. + aa * d);
+        c = not_tiny(1. + aa / c);
+        h *= d * c;
+        // Odd step.
+        let aa = -(a + m) * (qab + m) * x / ((a + m2) * (qap + m2));
+        d = 1. / not_tiny(1. + aa * d);
+        c = not_tiny(1. + aa / c);
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.).abs() < EPSILON {
+            break;
+        }
+    }
+    h
+}
+
+/// Calculate the regularized incomplete beta function `I_x(a, b)` for
+/// positive `a` and `b`.
+pub fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0. {
+        return 0.;
+    }
+    if x >= 1. {
+        return 1.;
+    }
+    let ln_front = ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b)
+        + a * Float::ln(x) + b * Float::ln(1. - x);
+    // The continued fraction converges quickly on this side of the mean, so
+    // the symmetry `I_x(a, b) = 1 - I_{1-x}(b, a)` is used on the other side.
+    if x < (a + 1.) / (a + b + 2.) {
+        Float::exp(ln_front) * beta_continued_fraction(a, b, x) / a
+    } else {
+        1. - Float::exp(ln_front) * beta_continued_fraction(b, a, 1. - x) / b
+    }
+}
+
+/// Calculate the quantile function of Student's t-distribution with `df`
+/// degrees of freedom.
+///
+/// The tail probability `I_x(df/2, 1/2)` with `x = df/(df + t^2)` is inverted
+/// by bisection. Returns `-inf` for 0 and `inf` for 1.
+pub fn student_t_quantile(p: f64, df: f64) -> f64 {
+    debug_assert!((0. ..=1.).contains(&p) && df > 0.);
+    if p < 0.5 {
+        return -student_t_quantile(1. - p, df);
+    }
+    if p == 0.5 {
+        return 0.;
+    }
+    if p == 1. {
+        return f64::INFINITY;
+    }
+    // Find `x` such that `I_x(df/2, 1/2) = 2(1 - p)`, which is increasing in
+    // `x`.
+    let target = 2. * (1. - p);
+    let (mut low, mut high) = (0., 1.);
+    while high - low > 1e-15 * high {
+        let mid = 0.5 * (low + high);
+        if incomplete_beta(0.5 * df, 0.5, mid) < target {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    let x = 0.5 * (low + high);
+    Float::sqrt(df * (1. - x) / x)
+}
+
+#[test]
+fn normal_quantile_reference() {
+    assert_eq!(normal_quantile(0.5), 0.);
+    assert_almost_eq!(normal_quantile(0.975), 1.959_963_984_540_054, 1e-8);
+    assert_almost_eq!(normal_quantile(0.025), -1.959_963_984_540_054, 1e-8);
+    assert_almost_eq!(normal_quantile(0.995), 2.575_829_303_548_901, 1e-8);
+    assert_almost_eq!(normal_quantile(1e-5), -4.264_890_793_922_825, 1e-8);
+    assert_eq!(normal_quantile(1.), f64::INFINITY);
+}
+
+#[test]
+fn ln_gamma_reference() {
+    assert_almost_eq!(ln_gamma(1.), 0., 1e-14);
+    assert_almost_eq!(ln_gamma(0.5), 0.5 * PI.ln(), 1e-14);
+    assert_almost_eq!(ln_gamma(10.), 362_880f64.ln(), 1e-12);
+    assert_almost_eq!(ln_gamma(0.1), 2.252_712_651_734_206, 1e-13);
+}
+
+#[test]
+fn student_t_quantile_reference() {
+    for &(df, expected) in &[
+        (1., 12.706_204_736_174_7),
+        (2., 4.302_652_729_749_464),
+        (10., 2.228_138_851_986_273),
+        (30., 2.042_272_456_301_238),
+    ] {
+        assert_almost_eq!(student_t_quantile(0.975, df), expected, 1e-9);
+    }
+    assert_almost_eq!(student_t_quantile(0.05, 5.), -2.015_048_372_669_157, 1e-9);
+    assert_eq!(student_t_quantile(0.5, 3.), 0.);
+    // For many degrees of freedom, the distribution is almost normal.
+    assert_almost_eq!(student_t_quantile(0.975, 1e6), normal_quantile(0.975), 1e-5);
+}
@@ -0,0 +1,121 @@
+use core;
+
+use super::{Estimate, Merge};
+
+/// Calculate the sum of a sequence of numbers.
+///
+/// This uses Neumaier's variant of Kahan summation, keeping track of the
+/// lost low-order bits separately. The error is therefore independent of the
+/// length of the sequence, even if it is ill-conditioned.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Sum;
+///
+/// let a: Sum = [1e16, 1., -1e16].iter().map(|x| *x).collect();
+/// assert_eq!(a.sum(), 1.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sum {
+    /// Sum, up to the compensation for rounding errors.
+    sum: f64,
+    /// Running compensation for the rounding errors in `sum`.
+    compensation: f64,
+    /// Number of summands.
+    n: u64,
+}
+
+impl Sum {
+    /// Create a new sum estimator.
+    #[inline]
+    pub fn new() -> Sum {
+        Sum { sum: 0., compensation: 0., n: 0 }
+    }
+
+    /// Reset the estimator to an empty sequence.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Sum::new();
+    }
+
+    /// Determine whether the sequence is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the number of summands.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Calculate the sum.
+    ///
+    /// Returns 0 for an empty sequence.
+    #[inline]
+    pub fn sum(&self) -> f64 {
+        self.sum + self.compensation
+    }
+
+    /// Add `x` to the sum, compensating for the rounding error.
+    #[inline]
+    fn add_compensated(&mut self, x: f64) {
+        let t = self.sum + x;
+        if self.sum.abs() >= x.abs() {
+            self.compensation += (self.sum - t) + x;
+        } else {
+            self.compensation += (x - t) + self.sum;
+        }
+        self.sum = t;
+    }
+}
+
+impl core::default::Default for Sum {
+    fn default() -> Sum {
+        Sum::new()
+    }
+}
+
+impl Estimate for Sum {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.add_compensated(x);
+        self.n += 1;
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.sum()
+    }
+}
+
+impl Merge for Sum {
+    /// Merge another sequence into this one.
+    ///
+    /// The compensations for the rounding errors of both sums are kept.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Sum, Merge};
+    ///
+    /// let mut a: Sum = [1e16, 1.].iter().map(|x| *x).collect();
+    /// let b: Sum = [-1e16, 1.].iter().map(|x| *x).collect();
+    /// a.merge(&b);
+    /// assert_eq!(a.sum(), 2.);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &Sum) {
+        self.add_compensated(other.sum);
+        self.compensation += other.compensation;
+        self.n += other.n;
+    }
+}
+
+impl_from_iterator!(Sum);
+impl_extend!(Sum);
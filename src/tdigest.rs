@@ -0,0 +1,276 @@
+use core::f64::consts::PI;
+
+use conv::ConvUtil;
+use std::vec::Vec;
+
+use super::Merge;
+
+/// A cluster of observations summarized by `TDigest`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct Centroid {
+    /// Mean of the observations.
+    mean: f64,
+    /// Number of observations.
+    weight: u64,
+}
+
+/// Estimate arbitrary quantiles of a sequence of numbers ("population").
+///
+/// This uses the merging variant of Dunning's t-digest. The observations are
+/// clustered into centroids, whose size is limited by a scale function, such
+/// that centroids near the tails are small. This makes the estimates of
+/// extreme quantiles like the 0.999-quantile accurate, even for skewed
+/// distributions. In contrast to [`Quantile`], the quantiles do not have to be
+/// chosen in advance, and merging is accurate.
+///
+/// The number of centroids is bounded by about the compression parameter, so
+/// the memory does not grow with the sample size. Larger values are more
+/// accurate and use more memory. This type is only available with the `std`
+/// feature.
+///
+/// [`Quantile`]: ./struct.Quantile.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::TDigest;
+///
+/// let mut a = TDigest::new(100.);
+/// for i in 0..10_000 {
+///     a.add(f64::from(i));
+/// }
+/// println!("The 0.99-quantile is {}.", a.quantile(0.99));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TDigest {
+    /// Compression parameter.
+    compression: f64,
+    /// Merged centroids, sorted by their means.
+    centroids: Vec<Centroid>,
+    /// Observations and centroids not merged yet.
+    buffer: Vec<Centroid>,
+    /// Sample size.
+    n: u64,
+    /// Smallest observation.
+    min: f64,
+    /// Largest observation.
+    max: f64,
+}
+
+impl TDigest {
+    /// Create a new t-digest with the given compression parameter.
+    ///
+    /// 100 is a common choice. Panics if `compression` is less than 1.
+    #[inline]
+    pub fn new(compression: f64) -> TDigest {
+        assert!(compression >= 1., "compression must be at least 1");
+        TDigest {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            n: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The compression parameter is kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = TDigest::new(self.compression);
+    }
+
+    /// Return the compression parameter.
+    #[inline]
+    pub fn compression(&self) -> f64 {
+        self.compression
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Add an observation.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        self.buffer.push(Centroid { mean: x, weight: 1 });
+        self.n += 1;
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+        if self.buffer.len() >= self.buffer_capacity() {
+            self.compress();
+        }
+    }
+
+    /// Return the number of buffered centroids that triggers merging.
+    #[inline]
+    fn buffer_capacity(&self) -> usize {
+        (5. * self.compression).ceil().approx_as::<usize>().unwrap()
+    }
+
+    /// Merge the buffered centroids into the centroids.
+    fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let mut buffer = core::mem::take(&mut self.buffer);
+        buffer.extend_from_slice(&self.centroids);
+        self.centroids = merge_centroids(buffer, self.n, self.compression);
+    }
+
+    /// Return the merged centroids, including the buffered ones.
+    fn merged_centroids(&self) -> Vec<Centroid> {
+        if self.buffer.is_empty() {
+            return self.centroids.clone();
+        }
+        let mut all = self.buffer.clone();
+        all.extend_from_slice(&self.centroids);
+        merge_centroids(all, self.n, self.compression)
+    }
+
+    /// Return the number of centroids, which determines the memory used.
+    #[inline]
+    pub fn centroid_count(&self) -> usize {
+        self.merged_centroids().len()
+    }
+
+    /// Estimate the p-quantile of the population.
+    ///
+    /// The quantile is interpolated linearly between the means of adjacent
+    /// centroids, and between the smallest (largest) observation and the
+    /// first (last) centroid. Returns 0 for an empty sample. Panics if `p` is
+    /// not between 0 and 1.
+    pub fn quantile(&self, p: f64) -> f64 {
+        assert!((0. ..=1.).contains(&p), "p must be between 0 and 1");
+        if self.is_empty() {
+            return 0.;
+        }
+        let centroids = self.merged_centroids();
+        let n = self.n.approx_as::<f64>().unwrap();
+        let target = p * n;
+        // Each centroid is assumed to be centered at its mean, so half of its
+        // weight is below the mean.
+        let mut cumulative = 0.;
+        let mut previous: Option<(f64, f64)> = None;
+        for c in &centroids {
+            let weight = c.weight.approx_as::<f64>().unwrap();
+            let center = cumulative + 0.5 * weight;
+            if target < center {
+                let (x0, t0) = previous.unwrap_or((self.min, 0.));
+                return interpolate(x0, t0, c.mean, center, target);
+            }
+            previous = Some((c.mean, center));
+            cumulative += weight;
+        }
+        let (x0, t0) = previous.unwrap();  // The sample is not empty.
+        interpolate(x0, t0, self.max, n, target)
+    }
+}
+
+/// Interpolate linearly between `(t0, x0)` and `(t1, x1)` at `t`.
+#[inline]
+fn interpolate(x0: f64, t0: f64, x1: f64, t1: f64, t: f64) -> f64 {
+    if t1 <= t0 {
+        return x1;
+    }
+    x0 + (t - t0) / (t1 - t0) * (x1 - x0)
+}
+
+/// The scale function `k_1` of the t-digest, mapping a quantile to a scale
+/// where each centroid may span at most one unit.
+#[inline]
+fn scale(q: f64, compression: f64) -> f64 {
+    compression / (2. * PI) * (2. * q - 1.).asin()
+}
+
+/// The inverse of `scale`.
+#[inline]
+fn inverse_scale(k: f64, compression: f64) -> f64 {
+    if k >= compression / 4. {
+        return 1.;
+    }
+    0.5 * ((2. * PI * k / compression).sin() + 1.)
+}
+
+/// Merge unsorted centroids of `n` observations in total, such that each
+/// merged centroid spans at most one unit of the scale function.
+fn merge_centroids(mut centroids: Vec<Centroid>, n: u64, compression: f64)
+    -> Vec<Centroid>
+{
+    centroids.sort_by(|a, b| a.mean.partial_cmp(&b.mean).unwrap_or(core::cmp::Ordering::Equal));
+    let n = n.approx_as::<f64>().unwrap();
+    let mut merged: Vec<Centroid> = Vec::new();
+    let mut iter = centroids.into_iter();
+    let mut current = match iter.next() {
+        Some(c) => c,
+        None => return merged,
+    };
+    // Weight of the centroids before `current`.
+    let mut weight_before = 0.;
+    let mut q_limit = inverse_scale(scale(0., compression) + 1., compression) * n;
+    for c in iter {
+        let current_weight = current.weight.approx_as::<f64>().unwrap();
+        let c_weight = c.weight.approx_as::<f64>().unwrap();
+        if weight_before + current_weight + c_weight <= q_limit {
+            let weight = current.weight + c.weight;
+            current.mean += (c.mean - current.mean) * c_weight
+                / weight.approx_as::<f64>().unwrap();
+            current.weight = weight;
+        } else {
+            weight_before += current_weight;
+            merged.push(current);
+            current = c;
+            let k = scale(weight_before / n, compression);
+            q_limit = inverse_scale(k + 1., compression) * n;
+        }
+    }
+    merged.push(current);
+    merged
+}
+
+impl Merge for TDigest {
+    /// Merge another sample into this one.
+    ///
+    /// The compression parameter of `self` is kept.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{TDigest, Merge};
+    ///
+    /// let mut a = TDigest::new(100.);
+    /// let mut b = TDigest::new(100.);
+    /// a.extend((0..500).map(f64::from));
+    /// b.extend((500..1000).map(f64::from));
+    /// a.merge(&b);
+    /// assert_eq!(a.len(), 1000);
+    /// assert!((a.quantile(0.5) - 500.).abs() < 5.);
+    /// ```
+    fn merge(&mut self, other: &TDigest) {
+        if other.is_empty() {
+            return;
+        }
+        self.buffer.extend_from_slice(&other.buffer);
+        self.buffer.extend_from_slice(&other.centroids);
+        self.n += other.n;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.compress();
+    }
+}
+
+impl_extend!(TDigest);
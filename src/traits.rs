@@ -1,13 +1,231 @@
+use core::borrow::Borrow;
+use core::fmt;
+use core::iter::FromIterator;
+
+use num_traits::float::FloatCore;
+
 /// Estimate a statistic of a sequence of numbers ("population").
-pub trait Estimate {
+///
+/// `F` is the type of the observations, which defaults to `f64`.
+pub trait Estimate<F = f64> {
     /// Add an observation sampled from the population.
-    fn add(&mut self, x: f64);
+    fn add(&mut self, x: F);
+
+    /// Add an observation sampled from the population, unless it is NaN.
+    ///
+    /// In contrast to `add`, which lets a NaN poison all subsequent estimates,
+    /// this rejects NaN and leaves the estimator unchanged. Infinities are not
+    /// considered errors and are added like any other observation.
+    #[inline]
+    fn try_add(&mut self, x: F) -> Result<(), NanError>
+        where F: FloatCore
+    {
+        if x.is_nan() {
+            return Err(NanError);
+        }
+        self.add(x);
+        Ok(())
+    }
+
+    /// Add all observations of an iterator, returning how many were added.
+    ///
+    /// This is like `extend`, but the count can be used for reporting the
+    /// progress when the observations are added in chunks.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Mean, Estimate};
+    ///
+    /// let mut a = Mean::new();
+    /// assert_eq!(a.add_iter((1..4).map(f64::from)), 3);
+    /// assert_eq!(a.add_iter((4..6).map(f64::from)), 2);
+    /// assert_eq!(a.mean(), 3.);
+    /// ```
+    #[inline]
+    fn add_iter<I>(&mut self, iter: I) -> usize
+        where I: IntoIterator<Item=F>
+    {
+        let mut n = 0;
+        for x in iter {
+            self.add(x);
+            n += 1;
+        }
+        n
+    }
 
     /// Estimate the statistic of the population.
-    fn estimate(&self) -> f64;
+    ///
+    /// This is the primary statistic of the estimator, which is the one its
+    /// name refers to. It allows generic code over `E: Estimate` to obtain a
+    /// result. The primary statistic is:
+    ///
+    /// * [`Mean`], [`GeometricMean`], [`HarmonicMean`], [`TrimmedMean`],
+    ///   [`WinsorizedMean`], [`ExpMovingAverage`] and [`Moments`]: the mean.
+    /// * [`Variance`]: the sample variance (not the population variance).
+    /// * [`ExpMovingVariance`]: the variance.
+    /// * [`Skewness`]: the skewness.
+    /// * [`Kurtosis`]: the excess kurtosis.
+    /// * [`Sum`] and [`Product`]: the sum and the product.
+    /// * [`Min`], [`Max`] and [`Range`]: the minimum, the maximum and the
+    ///   range.
+    /// * [`Quantile`]: the `p`-quantile given at construction.
+    /// * [`InterquartileRange`]: the interquartile range.
+    /// * [`MedianAbsoluteDeviation`]: the median absolute deviation.
+    /// * [`Gini`]: the Gini coefficient.
+    ///
+    /// Use the methods of the estimators to get other statistics.
+    ///
+    /// [`Mean`]: ./struct.Mean.html
+    /// [`GeometricMean`]: ./struct.GeometricMean.html
+    /// [`HarmonicMean`]: ./struct.HarmonicMean.html
+    /// [`TrimmedMean`]: ./struct.TrimmedMean.html
+    /// [`WinsorizedMean`]: ./struct.WinsorizedMean.html
+    /// [`ExpMovingAverage`]: ./struct.ExpMovingAverage.html
+    /// [`Moments`]: ./struct.Moments.html
+    /// [`Variance`]: ./struct.Variance.html
+    /// [`ExpMovingVariance`]: ./struct.ExpMovingVariance.html
+    /// [`Skewness`]: ./struct.Skewness.html
+    /// [`Kurtosis`]: ./struct.Kurtosis.html
+    /// [`Sum`]: ./struct.Sum.html
+    /// [`Product`]: ./struct.Product.html
+    /// [`Min`]: ./struct.Min.html
+    /// [`Max`]: ./struct.Max.html
+    /// [`Range`]: ./struct.Range.html
+    /// [`Quantile`]: ./struct.Quantile.html
+    /// [`InterquartileRange`]: ./struct.InterquartileRange.html
+    /// [`MedianAbsoluteDeviation`]: ./struct.MedianAbsoluteDeviation.html
+    /// [`Gini`]: ./struct.Gini.html
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Estimate, Mean, Max};
+    ///
+    /// fn summarize<E: Estimate + Default>(xs: &[f64]) -> f64 {
+    ///     let mut e = E::default();
+    ///     for &x in xs {
+    ///         e.add(x);
+    ///     }
+    ///     e.estimate()
+    /// }
+    ///
+    /// let xs = [1., 5., 3.];
+    /// assert_eq!(summarize::<Mean>(&xs), 3.);
+    /// assert_eq!(summarize::<Max>(&xs), 5.);
+    /// ```
+    fn estimate(&self) -> F;
 }
 
 /// Merge another sample into this one.
 pub trait Merge {
     fn merge(&mut self, other: &Self);
+
+    /// Merge several samples into a new estimator.
+    ///
+    /// This is useful for combining the partial results of parallel or
+    /// distributed computations. The parts can be given by value or by
+    /// reference. Returns an empty estimator if there are no parts.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Variance, Merge};
+    ///
+    /// let parts: Vec<Variance> = vec![
+    ///     [1., 2.].iter().map(|x| *x).collect(),
+    ///     [3.].iter().map(|x| *x).collect(),
+    ///     [4., 5.].iter().map(|x| *x).collect(),
+    /// ];
+    /// let a = Variance::merge_all(&parts);
+    /// assert_eq!(a.mean(), 3.);
+    /// assert_eq!(a.sample_variance(), 2.5);
+    /// assert!(Variance::<f64>::merge_all(Vec::<Variance>::new()).is_empty());
+    /// ```
+    fn merge_all<I>(parts: I) -> Self
+        where Self: Sized + Default, I: IntoIterator, I::Item: Borrow<Self>
+    {
+        let mut merged = Self::default();
+        for part in parts {
+            merged.merge(part.borrow());
+        }
+        merged
+    }
+}
+
+/// Remove an observation from the sample.
+///
+/// This is implemented by the estimators that can undo `add`, which is needed
+/// for statistics over a sliding window (see [`Sliding`]).
+///
+/// [`Sliding`]: ./struct.Sliding.html
+pub trait Remove<F = f64> {
+    /// Remove an observation that was previously added.
+    ///
+    /// The result is only exact up to floating-point error. Removing an
+    /// observation that was never added gives meaningless estimates.
+    fn remove(&mut self, x: F);
+}
+
+/// Calculate statistics of the items of an iterator.
+///
+/// This is implemented for all iterators and is an alternative to `collect()`
+/// that only accepts estimators.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Statistics, Variance};
+///
+/// let a = (1..6).map(f64::from).statistics::<Variance>();
+/// assert_eq!(a.mean(), 3.);
+/// ```
+pub trait Statistics: Iterator + Sized {
+    /// Consume the iterator and add all items to a new estimator.
+    #[inline]
+    fn statistics<E>(self) -> E
+        where E: Estimate<Self::Item> + FromIterator<Self::Item>
+    {
+        self.collect()
+    }
+}
+
+impl<I: Iterator> Statistics for I {}
+
+/// The error returned when trying to add a NaN observation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NanError;
+
+impl fmt::Display for NanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("observation is NaN")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NanError {}
+
+/// Build an estimator from the observations of an iterator, skipping NaN.
+///
+/// This is the same as collecting the iterator after filtering out NaN.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{skip_nan, Variance};
+///
+/// let a: Variance = skip_nan(vec![1., f64::NAN, 2., 3.]);
+/// assert_eq!(a.len(), 3);
+/// assert_eq!(a.mean(), 2.);
+/// ```
+#[inline]
+pub fn skip_nan<F, E, I>(iter: I) -> E
+    where F: FloatCore, E: FromIterator<F>, I: IntoIterator<Item=F>
+{
+    iter.into_iter().filter(|x| !x.is_nan()).collect()
 }
@@ -0,0 +1,252 @@
+use conv::{ConvAsUtil, ConvUtil};
+use quickersort::sort_floats;
+use std::vec::Vec;
+
+use super::{Estimate, Merge, Mean};
+
+/// Sort the observations and determine how many of them are in each tail, if
+/// the fraction `alpha` is cut from each tail.
+///
+/// This is `floor(alpha*n)`, so at least one observation is not in a tail if
+/// `alpha < 0.5`.
+fn sort_and_cut(samples: &[f64], alpha: f64) -> (Vec<f64>, usize) {
+    let mut sorted = samples.to_vec();
+    sort_floats(&mut sorted);
+    let k: usize = (alpha * sorted.len().approx_as::<f64>().unwrap())
+        .floor().approx().unwrap();
+    (sorted, k)
+}
+
+/// Calculate the trimmed mean of a sequence of numbers ("population").
+///
+/// The trimmed mean discards the fraction `alpha` of the smallest and the
+/// largest observations and averages over the remaining ones, which makes it
+/// robust against outliers.
+///
+/// In contrast to the other estimators, this does not use constant memory: All
+/// observations are stored, and they are sorted whenever the mean is
+/// calculated. In return, the result is exact. This type is only available
+/// with the `std` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{TrimmedMean, Estimate};
+///
+/// let mut a = TrimmedMean::new(0.2);
+/// for &x in &[1e9, 1., 2., 3., -1e9] {
+///     a.add(x);
+/// }
+/// assert_eq!(a.mean(), 2.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TrimmedMean {
+    /// Fraction of the observations discarded from each tail.
+    alpha: f64,
+    /// All observations, in the order they were added.
+    samples: Vec<f64>,
+}
+
+impl TrimmedMean {
+    /// Create a new trimmed mean estimator, discarding the fraction `alpha`
+    /// from each tail.
+    ///
+    /// Panics if `alpha` is not in the interval [0, 0.5).
+    #[inline]
+    pub fn new(alpha: f64) -> TrimmedMean {
+        assert!((0. ..0.5).contains(&alpha), "alpha must be in [0, 0.5)");
+        TrimmedMean { alpha, samples: Vec::new() }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The value of `alpha` is kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Return the fraction of the observations discarded from each tail.
+    #[inline]
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Return the sample size, including the discarded observations.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.samples.len() as u64
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Calculate the trimmed mean of the sample.
+    ///
+    /// `floor(alpha*n)` observations are discarded from each tail, so at least
+    /// one observation is kept. Returns 0 for an empty sample.
+    pub fn mean(&self) -> f64 {
+        let (sorted, k) = sort_and_cut(&self.samples, self.alpha);
+        let avg: Mean = sorted[k..sorted.len() - k].iter().map(|x| *x).collect();
+        avg.mean()
+    }
+}
+
+impl Estimate for TrimmedMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.samples.push(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
+
+impl Merge for TrimmedMean {
+    /// Merge another sample into this one.
+    ///
+    /// The value of `alpha` of `self` is kept.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{TrimmedMean, Estimate, Merge};
+    ///
+    /// let mut a = TrimmedMean::new(0.25);
+    /// let mut b = TrimmedMean::new(0.25);
+    /// a.extend([1e9, 1.].iter().map(|x| *x));
+    /// b.extend([3., -1e9].iter().map(|x| *x));
+    /// a.merge(&b);
+    /// assert_eq!(a.mean(), 2.);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &TrimmedMean) {
+        self.samples.extend_from_slice(&other.samples);
+    }
+}
+
+impl_extend!(TrimmedMean);
+
+/// Calculate the Winsorized mean of a sequence of numbers ("population").
+///
+/// The Winsorized mean replaces the fraction `alpha` of the smallest and the
+/// largest observations by the most extreme of the remaining ones before
+/// averaging, which makes it robust against outliers. In contrast to the
+/// trimmed mean, the extreme observations are clamped instead of discarded.
+///
+/// Like [`TrimmedMean`], this stores all observations, so it does not use
+/// constant memory, but the result is exact. This type is only available with
+/// the `std` feature.
+///
+/// [`TrimmedMean`]: ./struct.TrimmedMean.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{WinsorizedMean, Estimate};
+///
+/// let mut a = WinsorizedMean::new(0.2);
+/// for &x in &[-1e9, 1., 2., 3., 14.] {
+///     a.add(x);
+/// }
+/// // The sample is Winsorized to [1, 1, 2, 3, 3].
+/// assert_eq!(a.mean(), 2.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WinsorizedMean {
+    /// Fraction of the observations clamped in each tail.
+    alpha: f64,
+    /// All observations, in the order they were added.
+    samples: Vec<f64>,
+}
+
+impl WinsorizedMean {
+    /// Create a new Winsorized mean estimator, clamping the fraction `alpha`
+    /// in each tail.
+    ///
+    /// Panics if `alpha` is not in the interval [0, 0.5).
+    #[inline]
+    pub fn new(alpha: f64) -> WinsorizedMean {
+        assert!((0. ..0.5).contains(&alpha), "alpha must be in [0, 0.5)");
+        WinsorizedMean { alpha, samples: Vec::new() }
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The value of `alpha` is kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Return the fraction of the observations clamped in each tail.
+    #[inline]
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.samples.len() as u64
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Calculate the Winsorized mean of the sample.
+    ///
+    /// `floor(alpha*n)` observations are clamped in each tail. Returns 0 for
+    /// an empty sample and NaN if one of the observations is NaN, like the
+    /// mean.
+    pub fn mean(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        let (sorted, k) = sort_and_cut(&self.samples, self.alpha);
+        if sorted[sorted.len() - 1].is_nan() {
+            // NaN is sorted last.
+            return f64::NAN;
+        }
+        let (lower, upper) = (sorted[k], sorted[sorted.len() - 1 - k]);
+        let avg: Mean = sorted.iter().map(|x| x.clamp(lower, upper)).collect();
+        avg.mean()
+    }
+}
+
+impl Estimate for WinsorizedMean {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.samples.push(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
+
+impl Merge for WinsorizedMean {
+    /// Merge another sample into this one.
+    ///
+    /// The value of `alpha` of `self` is kept.
+    #[inline]
+    fn merge(&mut self, other: &WinsorizedMean) {
+        self.samples.extend_from_slice(&other.samples);
+    }
+}
+
+impl_extend!(WinsorizedMean);
@@ -1,5 +1,8 @@
 use core;
 
+#[cfg(any(feature = "std", feature = "libm"))]
+use num_traits::Float;
+
 use super::{MeanWithError, Estimate, Merge};
 
 
@@ -17,9 +20,12 @@ use super::{MeanWithError, Estimate, Merge};
 /// println!("The weighted mean is {}.", a.mean());
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WeightedMean  {
     /// Sum of the weights.
     weight_sum: f64,
+    /// Sum of the squares of the weights.
+    weight_sum_sq: f64,
     /// Weighted mean value.
     weighted_avg: f64,
 }
@@ -28,11 +34,26 @@ impl WeightedMean {
     /// Create a new weighted and unweighted mean estimator.
     pub fn new() -> WeightedMean {
         WeightedMean {
-            weight_sum: 0., weighted_avg: 0.,
+            weight_sum: 0., weight_sum_sq: 0., weighted_avg: 0.,
         }
     }
 
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = WeightedMean::new();
+    }
+
     /// Add an observation sampled from the population.
+    ///
+    /// The weight is not validated. A zero weight does not change the mean,
+    /// unless the sum of the weights is still zero, which makes the mean NaN.
+    /// A negative weight removes a previously added observation with the same
+    /// weight, up to rounding errors, but its square is still added to the sum
+    /// of the squared weights. Use [`remove`] to remove an observation
+    /// completely.
+    ///
+    /// [`remove`]: #method.remove
     #[inline]
     pub fn add(&mut self, sample: f64, weight: f64) {
         // The algorithm for the unweighted mean was suggested by Welford in 1962.
@@ -42,11 +63,46 @@ impl WeightedMean {
         // and
         // http://people.ds.cam.ac.uk/fanf2/hermes/doc/antiforgery/stats.pdf.
         self.weight_sum += weight;
+        self.weight_sum_sq += weight*weight;
 
         let prev_avg = self.weighted_avg;
         self.weighted_avg = prev_avg + (weight / self.weight_sum) * (sample - prev_avg);
     }
 
+    /// Remove an observation that was previously added with the same weight.
+    ///
+    /// This reverses the previous `add`, including the square of the weight
+    /// in the sum of the squared weights, so the estimator ends up in the
+    /// state it would be in if the observation had never been added (up to
+    /// floating-point error). If the sum of the weights becomes 0, the mean
+    /// is reset to 0.
+    #[inline]
+    pub fn remove(&mut self, sample: f64, weight: f64) {
+        self.weight_sum -= weight;
+        self.weight_sum_sq -= weight*weight;
+        if self.weight_sum == 0. {
+            self.weighted_avg = 0.;
+            return;
+        }
+
+        let prev_avg = self.weighted_avg;
+        self.weighted_avg = prev_avg - (weight / self.weight_sum) * (sample - prev_avg);
+    }
+
+    /// Add a slice of observations with the corresponding weights.
+    ///
+    /// This is equivalent to calling `add` for each pair.
+    ///
+    /// Panics if the slices have different lengths.
+    #[inline]
+    pub fn add_slice_weighted(&mut self, samples: &[f64], weights: &[f64]) {
+        assert_eq!(samples.len(), weights.len(),
+                   "samples and weights must have the same length");
+        for (&sample, &weight) in samples.iter().zip(weights) {
+            self.add(sample, weight);
+        }
+    }
+
     /// Determine whether the sample is empty.
     ///
     /// Might be a false positive if the sum of weights is zero.
@@ -63,6 +119,45 @@ impl WeightedMean {
         self.weight_sum
     }
 
+    /// Return the sum of the squared weights.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum_weights_sq(&self) -> f64 {
+        self.weight_sum_sq
+    }
+
+    /// Calculate the effective sample size.
+    ///
+    /// This is Kish's effective sample size `(Σw)²/Σw²`, the number of
+    /// observations with unit weight that would give an estimate of the mean
+    /// with the same variance. It equals the number of observations if all
+    /// weights are the same, and is smaller for non-uniform weights, down to
+    /// 1 if a single observation dominates. Returns 0 for an empty sample.
+    #[inline]
+    pub fn effective_len(&self) -> f64 {
+        if self.weight_sum_sq == 0. {
+            return 0.;
+        }
+        self.weight_sum * self.weight_sum / self.weight_sum_sq
+    }
+
+    /// Return the estimator with all weights scaled so that they sum to 1.
+    ///
+    /// The mean and the effective sample size do not change. Returns the
+    /// estimator unchanged if the sum of the weights is 0.
+    #[inline]
+    pub fn normalized(&self) -> WeightedMean {
+        if self.weight_sum == 0. {
+            return self.clone();
+        }
+        WeightedMean {
+            weight_sum: 1.,
+            weight_sum_sq: self.weight_sum_sq / (self.weight_sum * self.weight_sum),
+            weighted_avg: self.weighted_avg,
+        }
+    }
+
     /// Estimate the weighted mean of the population.
     ///
     /// Returns 0 for an empty sample.
@@ -78,6 +173,12 @@ impl core::default::Default for WeightedMean {
     }
 }
 
+/// Collect `(sample, weight)` pairs.
+///
+/// The pairs are added using [`WeightedMean::add`], so zero and negative
+/// weights are treated the same way.
+///
+/// [`WeightedMean::add`]: ./struct.WeightedMean.html#method.add
 impl core::iter::FromIterator<(f64, f64)> for WeightedMean {
     fn from_iter<T>(iter: T) -> WeightedMean
         where T: IntoIterator<Item=(f64, f64)>
@@ -90,6 +191,16 @@ impl core::iter::FromIterator<(f64, f64)> for WeightedMean {
     }
 }
 
+impl core::iter::Extend<(f64, f64)> for WeightedMean {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        for (i, w) in iter {
+            self.add(i, w);
+        }
+    }
+}
+
 impl Merge for WeightedMean {
     /// Merge another sample into this one.
     ///
@@ -116,6 +227,7 @@ impl Merge for WeightedMean {
                              + other.weight_sum * other.weighted_avg)
                             / total_weight_sum;
         self.weight_sum = total_weight_sum;
+        self.weight_sum_sq += other.weight_sum_sq;
     }
 }
 
@@ -135,9 +247,8 @@ impl Merge for WeightedMean {
 /// println!("The weighted mean is {} ± {}.", a.weighted_mean(), a.error());
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WeightedMeanWithError {
-    /// Sum of the squares of the weights.
-    weight_sum_sq: f64,
     /// Estimator of the weighted mean.
     weighted_avg: WeightedMean,
     /// Estimator of unweighted mean and its variance.
@@ -149,13 +260,26 @@ impl WeightedMeanWithError {
     #[inline]
     pub fn new() -> WeightedMeanWithError {
         WeightedMeanWithError {
-            weight_sum_sq: 0.,
             weighted_avg: WeightedMean::new(),
             unweighted_avg: MeanWithError::new(),
         }
     }
 
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = WeightedMeanWithError::new();
+    }
+
     /// Add an observation sampled from the population.
+    ///
+    /// The weight is not validated. A zero weight does not change the mean,
+    /// unless the sum of the weights is still zero, which makes the mean NaN.
+    /// A negative weight is subtracted from the sum of the weights, but still
+    /// counts as an observation for the unweighted mean and the sum of the
+    /// squared weights. Use [`remove`] to remove an observation.
+    ///
+    /// [`remove`]: #method.remove
     #[inline]
     pub fn add(&mut self, sample: f64, weight: f64) {
         // The algorithm for the unweighted mean was suggested by Welford in 1962.
@@ -165,11 +289,39 @@ impl WeightedMeanWithError {
         // https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance
         // and
         // http://people.ds.cam.ac.uk/fanf2/hermes/doc/antiforgery/stats.pdf.
-        self.weight_sum_sq += weight*weight;
         self.weighted_avg.add(sample, weight);
         self.unweighted_avg.add(sample);
     }
 
+    /// Remove an observation that was previously added with the same weight.
+    ///
+    /// This reverses the previous `add` for the weighted and the unweighted
+    /// statistics, like [`WeightedMean::remove`] and [`Mean::remove`].
+    ///
+    /// Panics if the sample is empty.
+    ///
+    /// [`WeightedMean::remove`]: ./struct.WeightedMean.html#method.remove
+    /// [`Mean::remove`]: ./struct.Mean.html#method.remove
+    #[inline]
+    pub fn remove(&mut self, sample: f64, weight: f64) {
+        self.unweighted_avg.remove(sample);
+        self.weighted_avg.remove(sample, weight);
+    }
+
+    /// Add a slice of observations with the corresponding weights.
+    ///
+    /// This is equivalent to calling `add` for each pair.
+    ///
+    /// Panics if the slices have different lengths.
+    #[inline]
+    pub fn add_slice_weighted(&mut self, samples: &[f64], weights: &[f64]) {
+        assert_eq!(samples.len(), weights.len(),
+                   "samples and weights must have the same length");
+        for (&sample, &weight) in samples.iter().zip(weights) {
+            self.add(sample, weight);
+        }
+    }
+
     /// Determine whether the sample is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -189,7 +341,20 @@ impl WeightedMeanWithError {
     /// Returns 0 for an empty sample.
     #[inline]
     pub fn sum_weights_sq(&self) -> f64 {
-        self.weight_sum_sq
+        self.weighted_avg.sum_weights_sq()
+    }
+
+    /// Return the estimator with all weights scaled so that they sum to 1.
+    ///
+    /// The means, the variances, the effective sample size and the error do
+    /// not change. Returns the estimator unchanged if the sum of the weights
+    /// is 0.
+    #[inline]
+    pub fn normalized(&self) -> WeightedMeanWithError {
+        WeightedMeanWithError {
+            weighted_avg: self.weighted_avg.normalized(),
+            unweighted_avg: self.unweighted_avg.clone(),
+        }
     }
 
     /// Estimate the weighted mean of the population.
@@ -215,13 +380,15 @@ impl WeightedMeanWithError {
     }
 
     /// Calculate the effective sample size.
+    ///
+    /// This is `(Σw)²/Σw²`, which equals `len()` for unit weights and is
+    /// smaller for non-uniform weights (see [`WeightedMean::effective_len`]).
+    /// Returns 0 for an empty sample.
+    ///
+    /// [`WeightedMean::effective_len`]: ./struct.WeightedMean.html#method.effective_len
     #[inline]
     pub fn effective_len(&self) -> f64 {
-        if self.is_empty() {
-            return 0.
-        }
-        let weight_sum = self.weighted_avg.sum_weights();
-        weight_sum * weight_sum / self.weight_sum_sq
+        self.weighted_avg.effective_len()
     }
 
     /// Calculate the *unweighted* population variance of the sample.
@@ -245,19 +412,23 @@ impl WeightedMeanWithError {
     /// Returns 0 if the sum of weights is 0.
     ///
     /// This unbiased estimator assumes that the samples were independently
-    /// drawn from the same population with constant variance.
+    /// drawn from the same population with constant variance. It is the
+    /// *unweighted* sample variance divided by the effective sample size. In
+    /// contrast, [`WeightedVariance::error`] estimates the variance of the
+    /// population with the weights. Both agree if all weights are the same.
+    ///
+    /// [`WeightedVariance::error`]: ./struct.WeightedVariance.html#method.error
+    #[cfg(any(feature = "std", feature = "libm"))]
     #[inline]
     pub fn error(&self) -> f64 {
         // This uses the same estimate as WinCross, which should provide better
         // results than the ones used by SPSS or Mentor.
         //
         // See http://www.analyticalgroup.com/download/WEIGHTED_VARIANCE.pdf.
-        let weight_sum = self.weighted_avg.sum_weights();
-        if weight_sum == 0. {
+        if self.weighted_avg.sum_weights() == 0. {
             return 0.;
         }
-        let inv_effective_len = self.weight_sum_sq / (weight_sum * weight_sum);
-        (self.sample_variance() * inv_effective_len).sqrt()
+        Float::sqrt(self.sample_variance() / self.effective_len())
     }
 }
 
@@ -283,7 +454,6 @@ impl Merge for WeightedMeanWithError {
     /// ```
     #[inline]
     fn merge(&mut self, other: &WeightedMeanWithError) {
-        self.weight_sum_sq += other.weight_sum_sq;
         self.weighted_avg.merge(&other.weighted_avg);
         self.unweighted_avg.merge(&other.unweighted_avg);
     }
@@ -295,6 +465,12 @@ impl core::default::Default for WeightedMeanWithError {
     }
 }
 
+/// Collect `(sample, weight)` pairs.
+///
+/// The pairs are added using [`WeightedMeanWithError::add`], so zero and negative
+/// weights are treated the same way.
+///
+/// [`WeightedMeanWithError::add`]: ./struct.WeightedMeanWithError.html#method.add
 impl core::iter::FromIterator<(f64, f64)> for WeightedMeanWithError {
     fn from_iter<T>(iter: T) -> WeightedMeanWithError
         where T: IntoIterator<Item=(f64, f64)>
@@ -306,3 +482,325 @@ impl core::iter::FromIterator<(f64, f64)> for WeightedMeanWithError {
         a
     }
 }
+
+impl core::iter::Extend<(f64, f64)> for WeightedMeanWithError {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        for (i, w) in iter {
+            self.add(i, w);
+        }
+    }
+}
+
+/// Estimate the weighted arithmetic mean and the weighted variance of a
+/// sequence of numbers ("population").
+///
+/// The unbiased estimator of the variance depends on the meaning of the
+/// weights, so there are separate methods for frequency weights (the number
+/// of times an observation occurred) and reliability weights (the importance
+/// or inverse variance of an observation). This can also be used to estimate
+/// the standard error of the weighted mean.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::WeightedVariance;
+///
+/// let a: WeightedVariance = [(1., 1.), (2., 2.), (3., 1.)]
+///     .iter().map(|&x| x).collect();
+/// println!("The weighted mean is {} and the variance is {}.",
+///          a.mean(), a.population_variance());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WeightedVariance {
+    /// Estimator of the weighted mean.
+    weighted_avg: WeightedMean,
+    /// Intermediate weighted sum of squares for calculating the variance.
+    sum_2: f64,
+}
+
+impl WeightedVariance {
+    /// Create a new weighted variance estimator.
+    #[inline]
+    pub fn new() -> WeightedVariance {
+        WeightedVariance {
+            weighted_avg: WeightedMean::new(),
+            sum_2: 0.,
+        }
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = WeightedVariance::new();
+    }
+
+    /// Add an observation sampled from the population.
+    ///
+    /// The weight is not validated. A zero weight does not change the mean,
+    /// unless the sum of the weights is still zero, which makes the mean NaN.
+    /// A negative weight removes a previously added observation with the same
+    /// weight, up to rounding errors, but its square is still added to the sum
+    /// of the squared weights. Use [`remove`] to remove an observation
+    /// completely.
+    ///
+    /// [`remove`]: #method.remove
+    #[inline]
+    pub fn add(&mut self, sample: f64, weight: f64) {
+        // This algorithm was suggested by West in 1979.
+        //
+        // See
+        // https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance
+        // and
+        // http://people.ds.cam.ac.uk/fanf2/hermes/doc/antiforgery/stats.pdf.
+        let delta = sample - self.weighted_avg.mean();
+        self.weighted_avg.add(sample, weight);
+        self.sum_2 += weight * delta * (sample - self.weighted_avg.mean());
+    }
+
+    /// Remove an observation that was previously added with the same weight.
+    ///
+    /// This reverses the previous `add`, like [`WeightedMean::remove`].
+    ///
+    /// [`WeightedMean::remove`]: ./struct.WeightedMean.html#method.remove
+    #[inline]
+    pub fn remove(&mut self, sample: f64, weight: f64) {
+        let delta = sample - self.weighted_avg.mean();
+        self.weighted_avg.remove(sample, weight);
+        if self.weighted_avg.is_empty() {
+            self.sum_2 = 0.;
+            return;
+        }
+        self.sum_2 -= weight * delta * (sample - self.weighted_avg.mean());
+    }
+
+    /// Add a slice of observations with the corresponding weights.
+    ///
+    /// This is equivalent to calling `add` for each pair.
+    ///
+    /// Panics if the slices have different lengths.
+    #[inline]
+    pub fn add_slice_weighted(&mut self, samples: &[f64], weights: &[f64]) {
+        assert_eq!(samples.len(), weights.len(),
+                   "samples and weights must have the same length");
+        for (&sample, &weight) in samples.iter().zip(weights) {
+            self.add(sample, weight);
+        }
+    }
+
+    /// Determine whether the sample is empty.
+    ///
+    /// Might be a false positive if the sum of weights is zero.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.weighted_avg.is_empty()
+    }
+
+    /// Return the sum of the weights.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum_weights(&self) -> f64 {
+        self.weighted_avg.sum_weights()
+    }
+
+    /// Return the sum of the squared weights.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn sum_weights_sq(&self) -> f64 {
+        self.weighted_avg.sum_weights_sq()
+    }
+
+    /// Return the estimator with all weights scaled so that they sum to 1.
+    ///
+    /// This is useful if the weights are probabilities, like in importance
+    /// sampling. The mean, the population variance, the reliability weighted
+    /// sample variance, the effective sample size and the error do not change.
+    /// The frequency weighted sample variance is no longer meaningful. Returns
+    /// the estimator unchanged if the sum of the weights is 0.
+    #[inline]
+    pub fn normalized(&self) -> WeightedVariance {
+        let weight_sum = self.sum_weights();
+        if weight_sum == 0. {
+            return self.clone();
+        }
+        WeightedVariance {
+            weighted_avg: self.weighted_avg.normalized(),
+            sum_2: self.sum_2 / weight_sum,
+        }
+    }
+
+    /// Estimate the weighted mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.weighted_avg.mean()
+    }
+
+    /// Calculate the weighted population variance of the sample.
+    ///
+    /// This is a biased estimator of the variance of the population. Returns
+    /// 0 if the sum of weights is 0.
+    #[inline]
+    pub fn population_variance(&self) -> f64 {
+        let weight_sum = self.sum_weights();
+        if weight_sum == 0. {
+            return 0.;
+        }
+        self.sum_2 / weight_sum
+    }
+
+    /// Calculate the sample variance, interpreting the weights as frequencies.
+    ///
+    /// This is an unbiased estimator of the variance of the population,
+    /// assuming that an observation with weight `w` occurred `w` times. It
+    /// uses the unbiasing factor `Σw - 1`. Returns 0 if the sum of weights is
+    /// not larger than 1.
+    #[inline]
+    pub fn frequency_sample_variance(&self) -> f64 {
+        let weight_sum = self.sum_weights();
+        if weight_sum <= 1. {
+            return 0.;
+        }
+        self.sum_2 / (weight_sum - 1.)
+    }
+
+    /// Calculate the sample variance, interpreting the weights as
+    /// reliabilities.
+    ///
+    /// This is an unbiased estimator of the variance of the population,
+    /// assuming that the weights describe the importance of the observations
+    /// and not their frequency. It uses the unbiasing factor
+    /// `Σw - Σw²/Σw = Σw (1 - 1/N_eff)`, where `N_eff` is the effective sample
+    /// size, so the result does not change when scaling all weights. Returns
+    /// 0 for samples with less than two observations.
+    #[inline]
+    pub fn reliability_sample_variance(&self) -> f64 {
+        let effective_len = self.effective_len();
+        if effective_len == 0. {
+            return 0.;
+        }
+        let denominator = self.sum_weights() * (1. - 1. / effective_len);
+        if denominator <= 0. {
+            return 0.;
+        }
+        self.sum_2 / denominator
+    }
+
+    /// Calculate the effective sample size.
+    ///
+    /// This is `(Σw)²/Σw²`, which equals the sample size for unit weights and
+    /// is smaller for non-uniform weights (see
+    /// [`WeightedMean::effective_len`]). Returns 0 for an empty sample.
+    ///
+    /// [`WeightedMean::effective_len`]: ./struct.WeightedMean.html#method.effective_len
+    #[inline]
+    pub fn effective_len(&self) -> f64 {
+        self.weighted_avg.effective_len()
+    }
+
+    /// Estimate the standard error of the weighted mean of the population.
+    ///
+    /// Returns 0 if the sum of weights is 0.
+    ///
+    /// This assumes that the samples were independently drawn from the same
+    /// population with constant variance `σ²`, so the variance of the weighted
+    /// mean is `σ² Σw²/(Σw)² = σ²/N_eff` (see Kish, *Survey Sampling*, 1965).
+    /// Here `σ²` is estimated by the reliability weighted sample variance.
+    /// This is a different estimator than [`WeightedMeanWithError::error`],
+    /// which estimates `σ²` without the weights. Both agree if all weights are
+    /// the same. Prefer this one if observations with small weights are also
+    /// less representative of the spread of the population.
+    ///
+    /// [`WeightedMeanWithError::error`]: ./struct.WeightedMeanWithError.html#method.error
+    #[cfg(any(feature = "std", feature = "libm"))]
+    #[inline]
+    pub fn error(&self) -> f64 {
+        let effective_len = self.effective_len();
+        if effective_len == 0. {
+            return 0.;
+        }
+        Float::sqrt(self.reliability_sample_variance() / effective_len)
+    }
+}
+
+impl Merge for WeightedVariance {
+    /// Merge another sample into this one.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{WeightedVariance, Merge};
+    ///
+    /// let weighted_sequence: &[(f64, f64)] = &[
+    ///     (1., 0.1), (2., 0.2), (3., 0.3), (4., 0.4), (5., 0.5),
+    ///     (6., 0.6), (7., 0.7), (8., 0.8), (9., 0.9)];
+    /// let (left, right) = weighted_sequence.split_at(3);
+    /// let var_total: WeightedVariance = weighted_sequence.iter().map(|&x| x).collect();
+    /// let mut var_left: WeightedVariance = left.iter().map(|&x| x).collect();
+    /// let var_right: WeightedVariance = right.iter().map(|&x| x).collect();
+    /// var_left.merge(&var_right);
+    /// assert!((var_total.mean() - var_left.mean()).abs() < 1e-15);
+    /// assert!((var_total.population_variance() - var_left.population_variance()).abs() < 1e-14);
+    /// ```
+    #[inline]
+    fn merge(&mut self, other: &WeightedVariance) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other.clone();
+            return;
+        }
+        // This generalizes the algorithm proposed by Chan et al. in 1979.
+        //
+        // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
+        let weight_self = self.sum_weights();
+        let weight_other = other.sum_weights();
+        let weight_total = weight_self + weight_other;
+        let delta = other.mean() - self.mean();
+        self.weighted_avg.merge(&other.weighted_avg);
+        self.sum_2 += other.sum_2
+            + delta*delta * weight_self * weight_other / weight_total;
+    }
+}
+
+impl core::default::Default for WeightedVariance {
+    fn default() -> WeightedVariance {
+        WeightedVariance::new()
+    }
+}
+
+/// Collect `(sample, weight)` pairs.
+///
+/// The pairs are added using [`WeightedVariance::add`], so zero and negative
+/// weights are treated the same way.
+///
+/// [`WeightedVariance::add`]: ./struct.WeightedVariance.html#method.add
+impl core::iter::FromIterator<(f64, f64)> for WeightedVariance {
+    fn from_iter<T>(iter: T) -> WeightedVariance
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        let mut a = WeightedVariance::new();
+        for (i, w) in iter {
+            a.add(i, w);
+        }
+        a
+    }
+}
+
+impl core::iter::Extend<(f64, f64)> for WeightedVariance {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        for (i, w) in iter {
+            self.add(i, w);
+        }
+    }
+}
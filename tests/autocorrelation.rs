@@ -0,0 +1,50 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+
+use average::{Autocorrelation, Correlation};
+
+#[test]
+fn trivial() {
+    let mut a = Autocorrelation::new(3);
+    assert!(a.is_empty());
+    assert!(a.autocorrelation(1).is_nan());
+    a.add(1.);
+    a.add(2.);
+    assert_eq!(a.len(), 2);
+    assert!(a.autocorrelation(1).is_nan());
+    a.add(4.);
+    assert_eq!(a.autocorrelation(1), 1.);
+    assert!(a.autocorrelation(2).is_nan());
+    a.clear();
+    assert!(a.is_empty());
+    assert_eq!(a.max_lag(), 3);
+}
+
+#[test]
+fn agrees_with_correlation() {
+    let xs: Vec<f64> = (0..100_u32)
+        .map(|i| f64::from(i).sin() + 0.01 * f64::from((i * 7) % 13))
+        .collect();
+    let mut a = Autocorrelation::new(5);
+    a.extend(xs.iter().map(|x| *x));
+    for lag in 1..=5 {
+        let expected: Correlation = xs.iter().zip(&xs[lag..])
+            .map(|(&x, &y)| (x, y))
+            .collect();
+        assert_almost_eq!(a.autocorrelation(lag), expected.correlation(), 1e-14);
+    }
+}
+
+#[test]
+#[should_panic]
+fn lag_too_large() {
+    Autocorrelation::new(2).autocorrelation(3);
+}
+
+#[test]
+#[should_panic]
+fn lag_zero() {
+    Autocorrelation::new(2).autocorrelation(0);
+}
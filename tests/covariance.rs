@@ -0,0 +1,124 @@
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+
+use average::{Covariance, Correlation, Merge};
+
+/// Calculate the population covariance using the two-pass algorithm.
+fn two_pass_covariance(xs: &[(f64, f64)]) -> f64 {
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = xs.iter().map(|p| p.1).sum::<f64>() / n;
+    xs.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum::<f64>() / n
+}
+
+const SEQUENCE: &[(f64, f64)] = &[
+    (1., 2.), (2., -3.), (-3., 5.), (4., 4.1), (5.5, 6.),
+    (6., 8.), (-7., 7.), (8., 9.2), (9., 0.)];
+
+#[test]
+fn trivial() {
+    let mut a = Covariance::new();
+    assert!(a.is_empty());
+    assert_eq!(a.len(), 0);
+    assert_eq!(a.covariance(), 0.);
+    assert_eq!(a.sample_covariance(), 0.);
+    a.add(1., 2.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean_x(), 1.);
+    assert_eq!(a.mean_y(), 2.);
+    assert_eq!(a.covariance(), 0.);
+    a.add(3., 6.);
+    assert_eq!(a.mean_x(), 2.);
+    assert_eq!(a.mean_y(), 4.);
+    assert_eq!(a.covariance(), 2.);
+    assert_eq!(a.sample_covariance(), 4.);
+    a.clear();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn simple() {
+    let a: Covariance = SEQUENCE.iter().map(|&p| p).collect();
+    assert_eq!(a.len(), 9);
+    assert_almost_eq!(a.covariance(), two_pass_covariance(SEQUENCE), 1e-14);
+    assert_almost_eq!(a.sample_covariance(), a.covariance() * 9. / 8., 1e-14);
+}
+
+#[test]
+fn variance() {
+    // The covariance of a variable with itself is its variance.
+    let a: Covariance = (1..6).map(|x| (f64::from(x), f64::from(x))).collect();
+    assert_eq!(a.covariance(), 2.);
+    assert_eq!(a.sample_covariance(), 2.5);
+}
+
+#[test]
+fn merge() {
+    for mid in 0..SEQUENCE.len() {
+        let (left, right) = SEQUENCE.split_at(mid);
+        let cov_total: Covariance = SEQUENCE.iter().map(|&p| p).collect();
+        let mut cov_left: Covariance = left.iter().map(|&p| p).collect();
+        let cov_right: Covariance = right.iter().map(|&p| p).collect();
+        cov_left.merge(&cov_right);
+        assert_eq!(cov_total.len(), cov_left.len());
+        assert_almost_eq!(cov_total.mean_x(), cov_left.mean_x(), 1e-14);
+        assert_almost_eq!(cov_total.mean_y(), cov_left.mean_y(), 1e-14);
+        assert_almost_eq!(cov_total.covariance(), cov_left.covariance(), 1e-14);
+    }
+}
+
+#[test]
+fn correlation_linear() {
+    let a: Correlation = (0..10).map(|x| (f64::from(x), 3. * f64::from(x) - 2.)).collect();
+    assert_almost_eq!(a.correlation(), 1., 1e-15);
+    let a: Correlation = (0..10).map(|x| (f64::from(x), -0.5 * f64::from(x))).collect();
+    assert_almost_eq!(a.correlation(), -1., 1e-15);
+    assert!(a.correlation() >= -1.);
+}
+
+#[test]
+fn correlation_uncorrelated() {
+    // `y` is symmetric around the mean of `x`.
+    let a: Correlation = [(-2., 4.), (-1., 1.), (0., 0.), (1., 1.), (2., 4.)]
+        .iter().map(|&p| p).collect();
+    assert_almost_eq!(a.correlation(), 0., 1e-15);
+}
+
+#[test]
+fn correlation_degenerate() {
+    let mut a = Correlation::new();
+    assert!(a.correlation().is_nan());
+    a.add(1., 2.);
+    assert!(a.correlation().is_nan());
+    a.add(2., 2.);
+    assert_eq!(a.variance_y(), 0.);
+    assert!(a.correlation().is_nan());
+}
+
+#[test]
+fn correlation_simple() {
+    let a: Correlation = SEQUENCE.iter().map(|&p| p).collect();
+    let cov: Covariance = SEQUENCE.iter().map(|&p| p).collect();
+    assert_eq!(a.covariance(), cov.covariance());
+    let var_x = two_pass_covariance(&SEQUENCE.iter().map(|p| (p.0, p.0)).collect::<Vec<_>>());
+    let var_y = two_pass_covariance(&SEQUENCE.iter().map(|p| (p.1, p.1)).collect::<Vec<_>>());
+    assert_almost_eq!(a.variance_x(), var_x, 1e-14);
+    assert_almost_eq!(a.variance_y(), var_y, 1e-14);
+    assert_almost_eq!(a.correlation(), cov.covariance() / (var_x * var_y).sqrt(), 1e-14);
+}
+
+#[test]
+fn correlation_merge() {
+    for mid in 0..SEQUENCE.len() {
+        let (left, right) = SEQUENCE.split_at(mid);
+        let r_total: Correlation = SEQUENCE.iter().map(|&p| p).collect();
+        let mut r_left: Correlation = left.iter().map(|&p| p).collect();
+        let r_right: Correlation = right.iter().map(|&p| p).collect();
+        r_left.merge(&r_right);
+        assert_eq!(r_total.len(), r_left.len());
+        assert_almost_eq!(r_total.variance_x(), r_left.variance_x(), 1e-13);
+        assert_almost_eq!(r_total.variance_y(), r_left.variance_y(), 1e-13);
+        assert_almost_eq!(r_total.correlation(), r_left.correlation(), 1e-14);
+    }
+}
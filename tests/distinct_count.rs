@@ -0,0 +1,72 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+extern crate average;
+
+use average::{DistinctCount, Merge};
+
+#[test]
+fn empty() {
+    let mut a = DistinctCount::new(10);
+    assert!(a.is_empty());
+    assert_eq!(a.estimate(), 0.);
+    a.add(&1);
+    assert!(!a.is_empty());
+    a.clear();
+    assert!(a.is_empty());
+    assert_eq!(a.precision(), 10);
+}
+
+#[test]
+fn small() {
+    let mut a = DistinctCount::new(12);
+    for i in 0..100_000 {
+        a.add(&(i % 10));
+    }
+    assert_eq!(a.estimate().round(), 10.);
+}
+
+#[test]
+fn large() {
+    for &n in &[1_000u64, 10_000, 100_000, 1_000_000] {
+        let mut a = DistinctCount::new(12);
+        for i in 0..n {
+            a.add(&i);
+            a.add(&i);
+        }
+        // The relative standard error is about 1.6%.
+        let error = (a.estimate() - n as f64).abs() / n as f64;
+        assert!(error < 0.06, "n = {}, estimate = {}", n, a.estimate());
+    }
+}
+
+#[test]
+fn merge() {
+    let mut a = DistinctCount::new(14);
+    let mut b = DistinctCount::new(14);
+    let mut total = DistinctCount::new(14);
+    for i in 0..30_000 {
+        a.add(&i);
+        total.add(&i);
+    }
+    for i in 20_000..50_000 {
+        b.add(&i);
+        total.add(&i);
+    }
+    a.merge(&b);
+    assert_eq!(a.estimate(), total.estimate());
+    assert!((a.estimate() - 50_000.).abs() < 2_500.);
+}
+
+#[test]
+#[should_panic]
+fn merge_different_precision() {
+    let mut a = DistinctCount::new(10);
+    a.merge(&DistinctCount::new(11));
+}
+
+#[test]
+#[should_panic]
+fn invalid_precision() {
+    DistinctCount::new(3);
+}
@@ -0,0 +1,116 @@
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+
+use average::{ExpMovingAverage, ExpMovingVariance, Estimate, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = ExpMovingAverage::new(0.25);
+    assert!(a.is_empty());
+    assert_eq!(a.len(), 0);
+    assert_eq!(a.mean(), 0.);
+    a.add(2.);
+    assert!(!a.is_empty());
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean(), 2.);
+    a.add(6.);
+    assert_eq!(a.mean(), 3.);
+    a.add(3.);
+    assert_eq!(a.mean(), 3.);
+    a.clear();
+    assert!(a.is_empty());
+    assert_eq!(a.alpha(), 0.25);
+}
+
+#[test]
+fn constant() {
+    let mut a = ExpMovingAverage::new(0.1);
+    for _ in 0..100 {
+        a.add(5.);
+    }
+    assert_eq!(a.mean(), 5.);
+}
+
+#[test]
+fn forgets_old_observations() {
+    let mut a = ExpMovingAverage::new(0.1);
+    for _ in 0..100 {
+        a.add(0.);
+    }
+    for _ in 0..200 {
+        a.add(1.);
+    }
+    assert_almost_eq!(a.mean(), 1., 1e-9);
+}
+
+#[test]
+fn half_life() {
+    let mut a = ExpMovingAverage::from_half_life(10.);
+    assert_almost_eq!((1. - a.alpha()).powi(10), 0.5, 1e-15);
+    a.add(0.);
+    for _ in 0..10 {
+        a.add(1.);
+    }
+    assert_almost_eq!(a.mean(), 0.5, 1e-15);
+}
+
+#[test]
+#[should_panic]
+fn invalid_alpha() {
+    ExpMovingAverage::new(0.);
+}
+
+#[test]
+fn variance_reference() {
+    // With `alpha = 0.5`, the observations have the weights 1/4, 1/4 and 1/2.
+    let mut a = ExpMovingVariance::new(0.5);
+    assert!(a.is_empty());
+    assert_eq!(a.variance(), 0.);
+    a.add(1.);
+    assert_eq!(a.mean(), 1.);
+    assert_eq!(a.variance(), 0.);
+    a.add(3.);
+    assert_eq!(a.mean(), 2.);
+    assert_eq!(a.variance(), 1.);
+    a.add(5.);
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.mean(), 3.5);
+    assert_eq!(a.variance(), 2.75);
+    assert_eq!(a.std_dev(), 2.75f64.sqrt());
+    assert_eq!(a.effective_len(), 1.75);
+}
+
+#[test]
+fn variance_mean_matches_average() {
+    let mut a = ExpMovingAverage::new(0.2);
+    let mut b = ExpMovingVariance::new(0.2);
+    for i in 0..20 {
+        let x = f64::from(i).sin();
+        a.add(x);
+        b.add(x);
+        assert_eq!(a.mean(), b.mean());
+    }
+    assert_almost_eq!(b.effective_len(), 5., 0.1);
+}
+
+#[test]
+fn variance_merge() {
+    let mut a = ExpMovingVariance::new(0.5);
+    let mut b = ExpMovingVariance::new(0.5);
+    a.merge(&b);
+    assert!(a.is_empty());
+    for &(x, y) in &[(1., 2.), (3., 2.), (5., 2.)] {
+        a.add(x);
+        b.add(y);
+    }
+    let mut c = ExpMovingVariance::new(0.5);
+    c.merge(&a);
+    assert_eq!(c.mean(), a.mean());
+    assert_eq!(c.variance(), a.variance());
+    a.merge(&b);
+    assert_eq!(a.len(), 6);
+    assert_eq!(a.effective_len(), 3.5);
+    assert_eq!(a.mean(), 2.75);
+    assert_eq!(a.variance(), 0.5 * 2.75 + 0.25 * 1.5 * 1.5);
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+extern crate average;
+
+use average::{FrequentItems, Merge};
+
+#[test]
+fn exact() {
+    let mut a = FrequentItems::new(3);
+    assert!(a.is_empty());
+    for &x in &[1, 2, 2, 3, 3, 3] {
+        a.add(x);
+    }
+    assert_eq!(a.len(), 6);
+    assert_eq!(a.top_k(), vec![(3, 3, 0), (2, 2, 0), (1, 1, 0)]);
+    assert_eq!(a.count(&2), 2);
+    assert_eq!(a.count(&4), 0);
+    a.clear();
+    assert!(a.is_empty());
+    assert!(a.top_k().is_empty());
+}
+
+#[test]
+fn replace_minimum() {
+    let mut a = FrequentItems::new(2);
+    for &x in &["a", "a", "b", "c"] {
+        a.add(x);
+    }
+    // "c" took over the counter of "b".
+    assert_eq!(a.top_k(), vec![("a", 2, 0), ("c", 2, 1)]);
+}
+
+#[test]
+fn heavy_hitters() {
+    let mut a = FrequentItems::new(10);
+    let mut n = 0;
+    for i in 0..10_000u64 {
+        // Items 0 to 2 make up a third of the sequence each.
+        let x = if i % 2 == 0 { i % 3 } else { 100 + (i * 7919) % 5000 };
+        a.add(x);
+        n += 1;
+    }
+    let top = a.top_k();
+    assert_eq!(top.len(), 10);
+    let mut heavy: Vec<u64> = top[..3].iter().map(|t| t.0).collect();
+    heavy.sort();
+    assert_eq!(heavy, vec![0, 1, 2]);
+    for &(key, count, error) in &top {
+        assert!(error <= n / 10);
+        if key < 3 {
+            let exact = (0..10_000u64).filter(|i| i % 2 == 0 && i % 3 == key).count() as u64;
+            assert!(count >= exact && count - error <= exact);
+        }
+    }
+}
+
+#[test]
+fn merge() {
+    let mut a = FrequentItems::new(3);
+    let mut b = FrequentItems::new(3);
+    for &x in &[1, 1, 1, 2, 2, 4] {
+        a.add(x);
+    }
+    for &x in &[1, 3, 3, 3, 5] {
+        b.add(x);
+    }
+    a.merge(&b);
+    assert_eq!(a.len(), 11);
+    let top = a.top_k();
+    assert_eq!(top.len(), 3);
+    assert_eq!(top[0], (1, 4, 0));
+    // 3 was not monitored by `a`, and might have occurred once there.
+    assert_eq!(top[1], (3, 4, 1));
+    assert_eq!(top[2], (2, 3, 1));
+
+    a.merge(&FrequentItems::new(3));
+    assert_eq!(a.top_k(), top);
+}
@@ -0,0 +1,81 @@
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+
+use average::{GeometricMean, Estimate, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = GeometricMean::new();
+    assert!(a.is_empty());
+    assert_eq!(a.len(), 0);
+    assert_eq!(a.mean(), 0.);
+    a.add(3.);
+    assert_eq!(a.len(), 1);
+    assert_almost_eq!(a.mean(), 3., 1e-15);
+    a.add(12.);
+    assert_almost_eq!(a.mean(), 6., 1e-15);
+    a.clear();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn simple() {
+    let a: GeometricMean = [1., 3., 9., 27., 81.].iter().map(|x| *x).collect();
+    assert_eq!(a.len(), 5);
+    assert_almost_eq!(a.mean(), 9., 1e-14);
+    assert_eq!(a.estimate(), a.mean());
+}
+
+#[test]
+fn no_overflow() {
+    let a: GeometricMean = (0..1000).map(|_| 1e300).collect();
+    assert_almost_eq!(a.mean(), 1e300, 1e289);
+}
+
+#[test]
+fn non_positive() {
+    let a: GeometricMean = [2., 0., 8.].iter().map(|x| *x).collect();
+    assert_eq!(a.mean(), 0.);
+    let a: GeometricMean = [2., -1., 8.].iter().map(|x| *x).collect();
+    assert!(a.mean().is_nan());
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let avg_total: GeometricMean = sequence.iter().map(|x| *x).collect();
+        let mut avg_left: GeometricMean = left.iter().map(|x| *x).collect();
+        let avg_right: GeometricMean = right.iter().map(|x| *x).collect();
+        avg_left.merge(&avg_right);
+        assert_eq!(avg_total.len(), avg_left.len());
+        assert_almost_eq!(avg_total.mean(), avg_left.mean(), 1e-14);
+    }
+}
+
+#[test]
+fn geometric_std_dev() {
+    let mut a = GeometricMean::new();
+    assert_eq!(a.geometric_std_dev(), 1.);
+    a.add(5.);
+    assert_eq!(a.geometric_std_dev(), 1.);
+    a.add(5.);
+    assert_eq!(a.geometric_std_dev(), 1.);
+
+    let a: GeometricMean = [0.5, 2., 8.].iter().map(|x| *x).collect();
+    assert_almost_eq!(a.mean(), 2., 1e-15);
+    assert_almost_eq!(a.geometric_std_dev(), 4., 1e-14);
+
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    let (left, right) = sequence.split_at(4);
+    let total: GeometricMean = sequence.iter().map(|x| *x).collect();
+    let mut avg_left: GeometricMean = left.iter().map(|x| *x).collect();
+    let avg_right: GeometricMean = right.iter().map(|x| *x).collect();
+    avg_left.merge(&avg_right);
+    assert_almost_eq!(total.geometric_std_dev(), avg_left.geometric_std_dev(), 1e-14);
+
+    let a: GeometricMean = [2., 0., 8.].iter().map(|x| *x).collect();
+    assert!(a.geometric_std_dev().is_nan());
+}
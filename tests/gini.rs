@@ -0,0 +1,67 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+
+use average::{Gini, Estimate, Merge};
+
+#[test]
+fn empty() {
+    let a = Gini::with_bounds(0., 1., 10);
+    assert!(a.is_empty());
+    assert_eq!(a.gini(), 0.);
+}
+
+#[test]
+fn equal() {
+    let mut a = Gini::with_bounds(0., 10., 100);
+    a.extend(std::iter::repeat_n(4.2, 1000));
+    assert_eq!(a.len(), 1000);
+    assert_eq!(a.gini(), 0.);
+}
+
+#[test]
+fn uniform() {
+    let n = 10_000;
+    let mut a = Gini::with_bounds(0., 1., 100);
+    a.extend((0..n).map(|i| (f64::from(i) + 0.5) / f64::from(n)));
+    assert_almost_eq!(a.gini(), 1. / 3., 1e-3);
+    // The inequality within the bins is neglected.
+    assert_almost_eq!(a.gini(), 1. / 3. - 1. / (3. * 100. * 100.), 1e-12);
+    assert_eq!(a.estimate(), a.gini());
+}
+
+#[test]
+fn midpoints() {
+    // The estimate is exact if all observations are at the midpoints of
+    // their bins.
+    let mut a = Gini::with_bounds(0., 100., 100);
+    a.extend(std::iter::repeat_n(0.5, 99));
+    a.add(99.5);
+    let differences = 99. * (99.5 - 0.5);
+    let sum = 99. * 0.5 + 99.5;
+    assert_almost_eq!(a.gini(), differences / (100. * sum), 1e-12);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., 2., 3., 5., 8., 13., 21., 34.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut full = Gini::with_log_bounds(1., 50., 20);
+        full.extend(sequence.iter().map(|x| *x));
+        let mut a = Gini::with_log_bounds(1., 50., 20);
+        a.extend(left.iter().map(|x| *x));
+        let mut b = Gini::with_log_bounds(1., 50., 20);
+        b.extend(right.iter().map(|x| *x));
+        a.merge(&b);
+        assert_eq!(a.len(), full.len());
+        assert_eq!(a.gini(), full.gini());
+    }
+}
+
+#[test]
+#[should_panic]
+fn negative_min() {
+    Gini::with_bounds(-1., 1., 10);
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+extern crate average;
+
+use average::GkSummary;
+
+/// Check that the rank of each estimated quantile of `1..=n` is within the
+/// error bound.
+fn check_ranks(a: &GkSummary, n: u32) {
+    let n = f64::from(n);
+    for i in 0..=100 {
+        let p = f64::from(i) / 100.;
+        // The observations are their own ranks.
+        let rank = a.quantile(p);
+        let expected = (p * n).ceil().max(1.);
+        assert!((rank - expected).abs() <= a.epsilon() * n,
+                "p = {}, rank = {}, expected = {}", p, rank, expected);
+    }
+}
+
+#[test]
+fn empty() {
+    let mut a = GkSummary::new(0.01);
+    assert!(a.is_empty());
+    assert_eq!(a.quantile(0.5), 0.);
+    a.add(3.);
+    a.add(f64::NAN);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.quantile(0.), 3.);
+    assert_eq!(a.quantile(1.), 3.);
+    a.clear();
+    assert!(a.is_empty());
+    assert_eq!(a.tuple_count(), 0);
+    assert_eq!(a.epsilon(), 0.01);
+}
+
+#[test]
+fn shuffled() {
+    let n = 100_000;
+    let mut a = GkSummary::new(0.001);
+    a.extend((0..n).map(|i| f64::from((i * 7919) % n + 1)));
+    assert_eq!(a.len(), u64::from(n));
+    check_ranks(&a, n);
+    assert!(a.tuple_count() < 10_000);
+}
+
+#[test]
+fn sorted() {
+    let n = 50_000;
+    let mut a = GkSummary::new(0.005);
+    a.extend((1..=n).map(f64::from));
+    check_ranks(&a, n);
+    let mut b = GkSummary::new(0.005);
+    b.extend((1..=n).rev().map(f64::from));
+    check_ranks(&b, n);
+}
+
+#[test]
+fn duplicates() {
+    let mut a = GkSummary::new(0.01);
+    a.extend((0..10_000).map(|i| f64::from(i % 3)));
+    assert_eq!(a.quantile(0.1), 0.);
+    assert_eq!(a.quantile(0.5), 1.);
+    assert_eq!(a.quantile(0.9), 2.);
+}
+
+#[test]
+#[should_panic]
+fn invalid_epsilon() {
+    GkSummary::new(0.);
+}
@@ -0,0 +1,49 @@
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+
+use average::{HarmonicMean, Estimate, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = HarmonicMean::new();
+    assert!(a.is_empty());
+    assert_eq!(a.len(), 0);
+    assert_eq!(a.mean(), 0.);
+    a.add(3.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean(), 3.);
+    a.add(6.);
+    assert_eq!(a.mean(), 4.);
+    a.clear();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn speeds() {
+    // Travelling the same distance at 60 and 40 results in an average of 48.
+    let a: HarmonicMean = [60., 40.].iter().map(|x| *x).collect();
+    assert_almost_eq!(a.mean(), 48., 1e-13);
+    assert_eq!(a.estimate(), a.mean());
+}
+
+#[test]
+fn zero() {
+    let a: HarmonicMean = [2., 0., 8.].iter().map(|x| *x).collect();
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.mean(), 0.);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let avg_total: HarmonicMean = sequence.iter().map(|x| *x).collect();
+        let mut avg_left: HarmonicMean = left.iter().map(|x| *x).collect();
+        let avg_right: HarmonicMean = right.iter().map(|x| *x).collect();
+        avg_left.merge(&avg_right);
+        assert_eq!(avg_total.len(), avg_left.len());
+        assert_almost_eq!(avg_total.mean(), avg_left.mean(), 1e-14);
+    }
+}
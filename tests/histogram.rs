@@ -0,0 +1,413 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+
+use average::{DynHistogram, Merge};
+
+#[test]
+fn with_bounds() {
+    let h = DynHistogram::with_bounds(0., 1., 4);
+    assert_eq!(h.edges(), &[0., 0.25, 0.5, 0.75, 1.]);
+    assert_eq!(h.n_bins(), 4);
+    assert!(h.is_empty());
+    assert_eq!(h.counts(), &[0, 0, 0, 0]);
+}
+
+#[test]
+#[should_panic]
+fn with_bounds_empty_range() {
+    DynHistogram::with_bounds(1., 1., 4);
+}
+
+#[test]
+#[should_panic]
+fn with_bounds_no_bins() {
+    DynHistogram::with_bounds(0., 1., 0);
+}
+
+#[test]
+fn add() {
+    let mut h = DynHistogram::with_bounds(0., 1., 4);
+    for &x in &[0., 0.1, 0.25, 0.6, 0.999, 1., -0.5, 1.5, 2., f64::NAN] {
+        h.add(x);
+    }
+    assert_eq!(h.counts(), &[2, 1, 1, 2]);
+    assert_eq!(h.underflow(), 1);
+    assert_eq!(h.overflow(), 2);
+    assert_eq!(h.len(), 6);
+    assert_eq!(h.total(), 9);
+}
+
+#[test]
+fn rounding() {
+    let mut h = DynHistogram::with_bounds(0., 1., 10);
+    let edges = h.edges().to_vec();
+    h.extend(edges.iter().map(|x| *x));
+    assert_eq!(h.counts(), &[1, 1, 1, 1, 1, 1, 1, 1, 1, 2]);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[-1., 0.5, 1.5, 2.5, 2.7, 3.5, 4., 7.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut total = DynHistogram::with_bounds(0., 4., 4);
+        total.extend(sequence.iter().map(|x| *x));
+        let mut h_left = DynHistogram::with_bounds(0., 4., 4);
+        h_left.extend(left.iter().map(|x| *x));
+        let mut h_right = DynHistogram::with_bounds(0., 4., 4);
+        h_right.extend(right.iter().map(|x| *x));
+        h_left.merge(&h_right);
+        assert_eq!(h_left.counts(), total.counts());
+        assert_eq!(h_left.underflow(), total.underflow());
+        assert_eq!(h_left.overflow(), total.overflow());
+        assert_eq!(h_left.len(), total.len());
+        assert_eq!(h_left.total(), total.total());
+    }
+}
+
+#[test]
+#[should_panic]
+fn merge_different_edges() {
+    let mut a = DynHistogram::with_bounds(0., 4., 4);
+    let b = DynHistogram::with_bounds(0., 4., 5);
+    a.merge(&b);
+}
+
+#[test]
+fn quantile() {
+    let mut h = DynHistogram::with_bounds(0., 10., 10);
+    h.extend([0.5, 1.5, 1.5, 2.5, 7.5, 20.].iter().map(|x| *x));
+    assert_eq!(h.quantile(0.), 0.);
+    assert_eq!(h.quantile(1.), 8.);
+    assert_eq!(h.quantile(0.5), 1.75);
+    assert_almost_eq!(h.quantile(0.3), 1.25, 1e-14);
+    assert_eq!(h.quantile(0.9), 7.5);
+}
+
+#[test]
+fn quantile_empty() {
+    let h = DynHistogram::with_bounds(0., 10., 10);
+    assert!(h.quantile(0.5).is_nan());
+}
+
+#[test]
+#[should_panic]
+fn quantile_invalid_p() {
+    let h = DynHistogram::with_bounds(0., 10., 10);
+    h.quantile(1.5);
+}
+
+#[test]
+fn percentile_rank() {
+    let mut h = DynHistogram::with_bounds(0., 10., 10);
+    assert!(h.percentile_rank(5.).is_nan());
+    h.extend([0.5, 1.5, 1.5, 2.5, 7.5, 20.].iter().map(|x| *x));
+    assert_eq!(h.percentile_rank(-1.), 0.);
+    assert_eq!(h.percentile_rank(0.), 0.);
+    assert_eq!(h.percentile_rank(1.), 0.2);
+    assert_eq!(h.percentile_rank(1.75), 0.5);
+    assert_eq!(h.percentile_rank(h.quantile(0.3)), 0.3);
+    assert_eq!(h.percentile_rank(5.), 0.8);
+    assert_eq!(h.percentile_rank(10.), 1.);
+    assert_eq!(h.percentile_rank(20.), 1.);
+    assert!(h.percentile_rank(f64::NAN).is_nan());
+}
+
+#[test]
+fn density_cdf() {
+    let mut h = DynHistogram::with_bounds(0., 2., 4);
+    assert_eq!(h.density(), vec![0.; 4]);
+    assert_eq!(h.cdf(), vec![0.; 4]);
+    h.extend([0.1, 0.2, 0.7, 1.9, 3.].iter().map(|x| *x));
+    assert_eq!(h.density(), vec![1., 0.5, 0., 0.5]);
+    assert_eq!(h.cdf(), vec![0.5, 0.75, 0.75, 1.]);
+    let integral: f64 = h.density().iter().map(|d| d * 0.5).sum();
+    assert_eq!(integral, 1.);
+}
+
+#[test]
+fn with_log_bounds() {
+    let mut h = DynHistogram::with_log_bounds(1., 1000., 3);
+    assert_eq!(h.edges()[0], 1.);
+    assert_almost_eq!(h.edges()[1], 10., 1e-12);
+    assert_almost_eq!(h.edges()[2], 100., 1e-12);
+    assert_eq!(h.edges()[3], 1000.);
+    for &x in &[1., 10., 100., 1000., 0.5, 1001.] {
+        h.add(x);
+    }
+    assert_eq!(h.counts(), &[1, 1, 2]);
+    assert_eq!(h.underflow(), 1);
+    assert_eq!(h.overflow(), 1);
+}
+
+#[test]
+fn log_rounding() {
+    let mut h = DynHistogram::with_log_bounds(0.1, 1e6, 7);
+    let edges = h.edges().to_vec();
+    h.extend(edges.iter().map(|x| *x));
+    assert_eq!(h.counts(), &[1, 1, 1, 1, 1, 1, 2]);
+}
+
+#[test]
+#[should_panic]
+fn with_log_bounds_nonpositive() {
+    DynHistogram::with_log_bounds(0., 1., 4);
+}
+
+#[test]
+fn from_edges() {
+    let mut h = DynHistogram::from_edges(&[200., 300., 400., 500., 600.]);
+    assert_eq!(h.n_bins(), 4);
+    for &x in &[200., 204., 299.9, 301., 404., 500., 503., 600., 100., 601.] {
+        h.add(x);
+    }
+    assert_eq!(h.counts(), &[3, 1, 1, 3]);
+    assert_eq!(h.underflow(), 1);
+    assert_eq!(h.overflow(), 1);
+}
+
+#[test]
+#[should_panic]
+fn from_edges_empty() {
+    DynHistogram::from_edges(&[]);
+}
+
+#[test]
+#[should_panic]
+fn from_edges_single() {
+    DynHistogram::from_edges(&[1.]);
+}
+
+#[test]
+#[should_panic]
+fn from_edges_not_increasing() {
+    DynHistogram::from_edges(&[1., 3., 2.]);
+}
+
+#[test]
+#[should_panic]
+fn from_edges_repeated() {
+    DynHistogram::from_edges(&[1., 2., 2.]);
+}
+
+#[test]
+fn bins() {
+    let mut h = DynHistogram::from_edges(&[0., 1., 3., 6.]);
+    h.extend([0.5, 2., 2.5, 7.].iter().map(|x| *x));
+    let bins: Vec<(f64, f64, u64)> = h.bins().collect();
+    assert_eq!(bins, vec![(0., 1., 1), (1., 3., 2), (3., 6., 0)]);
+}
+
+#[test]
+fn mean_variance() {
+    let mut h = DynHistogram::with_bounds(0., 4., 4);
+    assert_eq!(h.mean(), 0.);
+    assert_eq!(h.variance(), 0.);
+    h.extend([0.2, 1.1, 1.9, 3.5, 10.].iter().map(|x| *x));
+    // The observations are assumed to be at 0.5, 1.5, 1.5 and 3.5.
+    assert_eq!(h.mean(), 1.75);
+    assert_almost_eq!(h.variance(), 1.1875, 1e-14);
+}
+
+#[test]
+fn mean_variance_fine_bins() {
+    let mut h = DynHistogram::with_bounds(0., 1., 1000);
+    h.extend((0..1000).map(|i| (f64::from(i) + 0.3) / 1000.));
+    assert_almost_eq!(h.mean(), 0.5, 1e-3);
+    assert_almost_eq!(h.variance(), 1. / 12., 1e-3);
+}
+
+#[test]
+fn clear() {
+    let mut h = DynHistogram::with_log_bounds(1., 100., 2);
+    h.extend([0.5, 2., 20., 200.].iter().map(|x| *x));
+    assert_eq!(h.len(), 2);
+    assert_eq!(h.total(), 4);
+    h.clear();
+    assert!(h.is_empty());
+    assert_eq!(h.total(), 0);
+    assert_eq!(h.counts(), &[0, 0]);
+    assert_eq!(h.underflow(), 0);
+    assert_eq!(h.overflow(), 0);
+    assert_eq!(h.edges(), DynHistogram::with_log_bounds(1., 100., 2).edges());
+    h.add(50.);
+    assert_eq!(h.counts(), &[0, 1]);
+}
+
+#[test]
+fn entropy() {
+    let mut h = DynHistogram::with_bounds(0., 4., 4);
+    assert_eq!(h.entropy(), 0.);
+    h.add(0.5);
+    assert_eq!(h.entropy(), 0.);
+    h.extend([1.5, 2.5, 3.5, 5.].iter().map(|x| *x));
+    assert_eq!(h.entropy(), 2.);
+    h.extend([0.5, 0.5, 1.5, 1.5].iter().map(|x| *x));
+    // p = [3/8, 3/8, 1/8, 1/8]
+    assert_almost_eq!(h.entropy(), 1.811278124459133, 1e-14);
+}
+
+#[test]
+fn weighted() {
+    use average::WeightedHistogram;
+
+    let mut h = WeightedHistogram::with_bounds(0., 4., 4);
+    h.extend([(0.5, 1.), (1.5, 3.), (2.5, 0.), (-1., 2.), (3.5, 4.)].iter().map(|&x| x));
+    assert_eq!(h.len(), 4);
+    assert_eq!(h.total(), 5);
+    assert_eq!(h.weights(), &[1., 3., 0., 4.]);
+    assert_eq!(h.underflow(), 2.);
+    assert_eq!(h.sum_weights(), 10.);
+    assert_eq!(h.cdf(), vec![0.125, 0.5, 0.5, 1.]);
+    assert_eq!(h.density(), vec![0.125, 0.375, 0., 0.5]);
+    assert_eq!(h.quantile(0.5), 2.);
+    assert_eq!(h.mean(), (0.5 * 1. + 1.5 * 3. + 3.5 * 4.) / 8.);
+    assert_eq!(h.percentile_rank(-1.), 0.);
+    assert_eq!(h.percentile_rank(1.5), 0.3125);
+    assert_eq!(h.percentile_rank(2.5), 0.5);
+    assert_eq!(h.percentile_rank(h.quantile(0.75)), 0.75);
+    assert_eq!(h.percentile_rank(5.), 1.);
+
+    // Integer weights give the same statistics as repeated observations.
+    let mut d = DynHistogram::with_bounds(0., 4., 4);
+    for &(x, n) in &[(0.5, 1), (1.5, 3), (3.5, 4)] {
+        for _ in 0..n {
+            d.add(x);
+        }
+    }
+    assert_eq!(h.quantile(0.3), d.quantile(0.3));
+    assert_eq!(h.percentile_rank(1.2), d.percentile_rank(1.2));
+    assert_eq!(h.mean(), d.mean());
+    assert_almost_eq!(h.variance(), d.variance(), 1e-15);
+    assert_eq!(h.entropy(), d.entropy());
+
+    h.clear();
+    assert!(h.is_empty());
+    assert_eq!(h.sum_weights(), 0.);
+    assert!(h.percentile_rank(1.).is_nan());
+}
+
+#[test]
+#[should_panic]
+fn weighted_negative_weight() {
+    average::WeightedHistogram::with_bounds(0., 1., 2).add_weighted(0.5, -1.);
+}
+
+#[test]
+fn counter_saturation() {
+    let mut h = DynHistogram::with_bounds(0., 2., 2).with_counter::<u8>();
+    for _ in 0..300 {
+        h.add(0.5);
+    }
+    h.add(1.5);
+    h.add(-1.);
+    assert_eq!(h.counts(), &[u8::MAX, 1]);
+    assert_eq!(h.underflow(), 1);
+    assert_eq!(h.len(), 256);
+    assert_eq!(h.total(), 257);
+
+    let mut other = DynHistogram::with_bounds(0., 2., 2).with_counter::<u8>();
+    other.extend(std::iter::repeat_n(1.5, 255));
+    h.merge(&other);
+    assert_eq!(h.counts(), &[u8::MAX, u8::MAX]);
+    assert_eq!(h.quantile(0.5), 1.);
+
+    let mut c = DynHistogram::from_edges(&[0., 1., 2.]).with_counter::<u16>();
+    c.extend(std::iter::repeat_n(0.5, 70_000));
+    assert_eq!(c.counts(), &[u16::MAX, 0]);
+    c.clear();
+    assert!(c.is_empty());
+
+    let mut d = DynHistogram::with_bounds(0., 1., 2);
+    d.add_n(0.1, u64::MAX);
+    d.add_n(0.9, 1);
+    d.add(2.);
+    assert_eq!(d.len(), u64::MAX);
+    assert_eq!(d.total(), u64::MAX);
+
+    let mut w = DynHistogram::with_bounds(0., 1., 2).with_counter::<u128>();
+    w.add_n(0.1, u64::MAX);
+    w.add_n(0.1, u64::MAX);
+    w.add_n(0.9, 1);
+    assert_eq!(w.counts(), &[2 * u128::from(u64::MAX), 1]);
+    assert_eq!(w.len(), u64::MAX);
+    assert_eq!(w.total(), u64::MAX);
+    assert!(!w.render_ascii(20).is_empty());
+}
+
+#[test]
+fn with_counter() {
+    let mut h = DynHistogram::with_bounds(0., 4., 4);
+    h.extend([0.5, 1.5, 1.7, 5.].iter().map(|x| *x));
+    let c = h.clone().with_counter::<u32>();
+    assert_eq!(c.counts(), &[1, 2, 0, 0]);
+    assert_eq!(c.overflow(), 1);
+    assert_eq!(c.len(), h.len());
+    assert_eq!(c.total(), h.total());
+    assert_eq!(c.mean(), h.mean());
+    assert_eq!(c.cdf(), h.cdf());
+}
+
+#[test]
+fn add_n() {
+    let mut a = DynHistogram::with_bounds(0., 4., 4);
+    let mut b = DynHistogram::with_bounds(0., 4., 4);
+    for &(x, n) in &[(0.5, 3), (2.5, 0), (3.9, 7), (-1., 2), (4.5, 1), (f64::NAN, 4)] {
+        a.add_n(x, n);
+        for _ in 0..n {
+            b.add(x);
+        }
+    }
+    assert_eq!(a.counts(), b.counts());
+    assert_eq!(a.counts(), &[3, 0, 0, 7]);
+    assert_eq!(a.underflow(), 2);
+    assert_eq!(a.overflow(), 1);
+    assert_eq!(a.len(), 10);
+    assert_eq!(a.total(), 13);
+
+    let mut c = DynHistogram::with_bounds(0., 4., 4).with_counter::<u8>();
+    c.add_n(0.5, 200);
+    c.add_n(0.5, 200);
+    c.add_n(1.5, 1000);
+    assert_eq!(c.counts(), &[u8::MAX, u8::MAX, 0, 0]);
+}
+
+#[test]
+fn from_data_percentiles() {
+    let mut data: Vec<f64> = (1..100).map(f64::from).collect();
+    data.extend(&[-1e9, 1e9, f64::NAN]);
+    let h = DynHistogram::from_data_percentiles(&data, 4, 5., 95.);
+    assert_eq!(h.n_bins(), 4);
+    assert_eq!(h.edges(), &[5., 27.5, 50., 72.5, 95.]);
+    assert_eq!(h.len(), 91);
+    assert_eq!(h.total(), 101);
+    assert_eq!(h.underflow(), 5);
+    assert_eq!(h.overflow(), 5);
+    assert_eq!(h.counts().iter().sum::<u64>(), 91);
+
+    let h = DynHistogram::from_data_percentiles(&[0., 1., 2., 3.], 1, 10., 50.);
+    assert_almost_eq!(h.edges()[0], 0.3, 1e-15);
+    assert_almost_eq!(h.edges()[1], 1.5, 1e-15);
+
+    let h = DynHistogram::from_data_percentiles(&[3., 3., 3.], 2, 0., 100.);
+    assert_eq!(h.edges(), &[2.5, 3., 3.5]);
+    assert_eq!(h.counts(), &[0, 3]);
+}
+
+#[test]
+#[should_panic]
+fn from_data_percentiles_empty() {
+    DynHistogram::from_data_percentiles(&[f64::NAN], 2, 0., 100.);
+}
+
+#[test]
+fn render_ascii() {
+    let h = DynHistogram::with_bounds(0., 2., 2);
+    assert_eq!(h.render_ascii(10), "[0, 1) 0\n[1, 2] 0\n");
+
+    let mut h = DynHistogram::with_bounds(0., 2., 2).with_counter::<u8>();
+    h.extend([-5., 0.5, 1.5, 1.5, 1.5].iter().map(|x| *x));
+    assert_eq!(h.render_ascii(3), "< 0    1 #\n[0, 1) 1 #\n[1, 2] 3 ###\n");
+}
@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 #[macro_use] extern crate average;
 
@@ -6,11 +6,11 @@ extern crate core;
 
 use core::iter::Iterator;
 
-use average::{Kurtosis, Estimate, Merge};
+use average::{Kurtosis, Moments, Estimate, Merge};
 
 #[test]
 fn trivial() {
-    let mut a = Kurtosis::new();
+    let mut a: Kurtosis = Kurtosis::new();
     assert_eq!(a.len(), 0);
     a.add(1.0);
     assert_eq!(a.mean(), 1.0);
@@ -18,14 +18,14 @@ fn trivial() {
     assert_eq!(a.sample_variance(), 0.0);
     assert_eq!(a.population_variance(), 0.0);
     assert_eq!(a.error_mean(), 0.0);
-    assert_eq!(a.skewness(), 0.0);
+    assert!(a.skewness().is_nan());
     a.add(1.0);
     assert_eq!(a.mean(), 1.0);
     assert_eq!(a.len(), 2);
     assert_eq!(a.sample_variance(), 0.0);
     assert_eq!(a.population_variance(), 0.0);
     assert_eq!(a.error_mean(), 0.0);
-    assert_eq!(a.skewness(), 0.0);
+    assert!(a.skewness().is_nan());
 }
 
 #[test]
@@ -41,6 +41,68 @@ fn simple() {
     assert_almost_eq!(a.kurtosis(), -1.365, 1e-15);
 }
 
+#[test]
+fn single_precision() {
+    let mut a: Kurtosis<f32> = (1..6).map(|x| x as f32).collect();
+    assert_eq!(a.mean(), 3.0);
+    assert_eq!(a.sample_variance(), 2.5);
+    a.add(1.0);
+    assert_almost_eq!(a.skewness(), 0.2795085, 1e-6);
+    assert_almost_eq!(a.kurtosis(), -1.365, 1e-6);
+}
+
+#[test]
+fn add_slice() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let avg_total: Kurtosis = sequence.iter().map(|x| *x).collect();
+        let mut avg: Kurtosis = left.iter().map(|x| *x).collect();
+        avg.add_slice(right);
+        assert_eq!(avg_total.len(), avg.len());
+        assert_almost_eq!(avg_total.mean(), avg.mean(), 1e-14);
+        assert_almost_eq!(avg_total.sample_variance(), avg.sample_variance(), 1e-14);
+        assert_almost_eq!(avg_total.skewness(), avg.skewness(), 1e-14);
+        assert_almost_eq!(avg_total.kurtosis(), avg.kurtosis(), 1e-14);
+    }
+}
+
+#[test]
+fn partial_eq() {
+    let a: Kurtosis = (1..6).map(f64::from).collect();
+    let mut b: Kurtosis = (1..5).map(f64::from).collect();
+    assert_ne!(a, b);
+    b.add(5.);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn clear() {
+    let mut a: Kurtosis = (1..6).map(f64::from).collect();
+    a.add(1.);
+    a.clear();
+    assert!(a.is_empty());
+    assert_eq!(a.len(), 0);
+    assert_eq!(a.mean(), 0.);
+    assert_eq!(a.sample_variance(), 0.);
+    assert!(a.skewness().is_nan());
+    assert!(a.kurtosis().is_nan());
+}
+
+#[test]
+fn sum() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let avg_total: Kurtosis = sequence.iter().map(|x| *x).collect();
+    let avg_sum: Kurtosis = sequence.chunks(3)
+        .map(|c| c.iter().map(|x| *x).collect::<Kurtosis>())
+        .sum();
+    assert_eq!(avg_total.len(), avg_sum.len());
+    assert_almost_eq!(avg_total.mean(), avg_sum.mean(), 1e-14);
+    assert_almost_eq!(avg_total.sample_variance(), avg_sum.sample_variance(), 1e-14);
+    assert_almost_eq!(avg_total.skewness(), avg_sum.skewness(), 1e-14);
+    assert_almost_eq!(avg_total.kurtosis(), avg_sum.kurtosis(), 1e-14);
+}
+
 #[test]
 fn merge() {
     let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
@@ -57,3 +119,315 @@ fn merge() {
         assert_almost_eq!(avg_total.kurtosis(), avg_left.kurtosis(), 1e-14);
     }
 }
+
+#[test]
+fn merge_estimator_almost_eq() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let avg_total: Kurtosis = sequence.iter().map(|x| *x).collect();
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut avg_left: Kurtosis = left.iter().map(|x| *x).collect();
+        let avg_right: Kurtosis = right.iter().map(|x| *x).collect();
+        avg_left.merge(&avg_right);
+        assert_estimator_almost_eq!(avg_total, avg_left, 1e-14);
+
+        let mut avg: Kurtosis = left.iter().map(|x| *x).collect();
+        avg.add_slice(right);
+        assert_estimator_almost_eq!(avg_total, avg, 1e-14);
+    }
+    let avg_sum: Kurtosis = sequence.chunks(3)
+        .map(|c| c.iter().map(|x| *x).collect::<Kurtosis>())
+        .sum();
+    assert_estimator_almost_eq!(avg_total, avg_sum, 1e-14);
+}
+
+#[test]
+fn kurtosis_raw() {
+    // The deviations from the mean are -2, -1, 0, 1 and 2, so the fourth
+    // standardized moment is 5 * 34 / 10^2.
+    let a: Kurtosis = (1..6).map(f64::from).collect();
+    assert_almost_eq!(a.kurtosis_raw(), 1.7, 1e-15);
+    assert_almost_eq!(a.kurtosis(), -1.3, 1e-15);
+    assert_eq!(a.kurtosis(), a.kurtosis_raw() - 3.);
+}
+
+#[test]
+fn kurtosis_zero_variance() {
+    let mut a: Kurtosis = Kurtosis::new();
+    assert!(a.kurtosis().is_nan());
+    for _ in 0..10 {
+        a.add(42.);
+        assert!(a.kurtosis().is_nan());
+        assert!(a.kurtosis_raw().is_nan());
+    }
+}
+
+#[test]
+fn kurtosis_two_points() {
+    let a: Kurtosis = [1., 2.].iter().map(|x| *x).collect();
+    assert_eq!(a.kurtosis_raw(), 1.);
+    assert_eq!(a.kurtosis(), -2.);
+}
+
+#[test]
+fn central_moments() {
+    let a: Kurtosis = Kurtosis::new();
+    assert_eq!(a.central_moment_2(), 0.);
+    assert_eq!(a.central_moment_3(), 0.);
+    assert_eq!(a.central_moment_4(), 0.);
+
+    let a: Kurtosis = (1..6).map(f64::from).collect();
+    assert_eq!(a.central_moment_2(), 2.);
+    assert_eq!(a.central_moment_3(), 0.);
+    assert_eq!(a.central_moment_4(), 6.8);
+
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let a: Kurtosis = sequence.iter().map(|x| *x).collect();
+    let b: Moments<4> = sequence.iter().map(|x| *x).collect();
+    assert_almost_eq!(a.central_moment_2(), b.central_moment(2), 1e-13);
+    assert_almost_eq!(a.central_moment_3(), b.central_moment(3), 1e-12);
+    assert_almost_eq!(a.central_moment_4(), b.central_moment(4), 1e-11);
+    assert_eq!(a.central_moment_2(), a.population_variance());
+}
+
+#[test]
+fn errors() {
+    let mut a: Kurtosis = Kurtosis::new();
+    for i in 0..10 {
+        if i < 3 {
+            assert!(a.error_skewness().is_nan());
+        }
+        if i < 4 {
+            assert!(a.error_kurtosis().is_nan());
+        }
+        a.add(f64::from(i * i));
+    }
+    assert_almost_eq!(a.error_skewness(), 0.6870429186215167, 1e-15);
+    assert_almost_eq!(a.error_kurtosis(), 1.334248769989982, 1e-15);
+
+    let n: f64 = 1000.;
+    let a: Kurtosis = (0..1000).map(f64::from).collect();
+    let ses = (6. * n * (n - 1.) / ((n - 2.) * (n + 1.) * (n + 3.))).sqrt();
+    let sek = 2. * ses * ((n*n - 1.) / ((n - 3.) * (n + 5.))).sqrt();
+    assert_almost_eq!(a.error_skewness(), ses, 1e-15);
+    assert_almost_eq!(a.error_kurtosis(), sek, 1e-15);
+}
+
+#[test]
+fn sample_kurtosis() {
+    let mut a: Kurtosis = Kurtosis::new();
+    for x in &[1., 2., 4.] {
+        a.add(*x);
+        assert!(a.sample_kurtosis().is_nan());
+    }
+
+    // Reference values computed in exact arithmetic, using the definitions of
+    // `scipy.stats.kurtosis` with `bias=True` and `bias=False`.
+    let a: Kurtosis = [1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.]
+        .iter().map(|x| *x).collect();
+    assert_almost_eq!(a.kurtosis(), -0.352090813745394, 1e-14);
+    assert_almost_eq!(a.sample_kurtosis(), 0.34183945427153556, 1e-14);
+    assert_almost_eq!(a.sample_skewness(), -0.8027827183537348, 1e-14);
+
+    let a: Kurtosis = (0..10).map(|_| 1.).collect();
+    assert!(a.sample_kurtosis().is_nan());
+}
+
+#[test]
+fn downgrade() {
+    use average::{Mean, Variance, Skewness};
+
+    let k: Kurtosis = [1., 2., 3., 5., 8., 13.].iter().map(|x| *x).collect();
+    let s = Skewness::from(k.clone());
+    assert_eq!(s.len(), k.len());
+    assert_eq!(s.mean(), k.mean());
+    assert_eq!(s.sample_variance(), k.sample_variance());
+    assert_eq!(s.skewness(), k.skewness());
+    let v = Variance::from(s);
+    assert_eq!(v.mean(), k.mean());
+    assert_eq!(v.sample_variance(), k.sample_variance());
+    let m: Mean = v.into();
+    assert_eq!(m.len(), k.len());
+    assert_eq!(m.mean(), k.mean());
+}
+
+#[test]
+fn summary() {
+    let a: Kurtosis = [1., 2., 3., 5., 8., 13.].iter().map(|x| *x).collect();
+    let s = a.summary();
+    assert_eq!(s.n, a.len());
+    assert_eq!(s.mean, a.mean());
+    assert_eq!(s.variance, a.sample_variance());
+    assert_eq!(s.std_dev, a.sample_std_dev());
+    assert_eq!(s.skewness, a.skewness());
+    assert_eq!(s.kurtosis, a.kurtosis());
+    assert_almost_eq!(s.error_mean, a.error_mean(), 1e-15);
+
+    let e = Kurtosis::<f64>::new().summary();
+    assert_eq!(e.n, 0);
+    assert_eq!(e.error_mean, 0.);
+    assert!(e.kurtosis.is_nan());
+}
+
+#[test]
+fn display() {
+    use average::Skewness;
+
+    let a: Kurtosis = [1., 2., 3., 4.].iter().map(|x| *x).collect();
+    assert_eq!(format!("{:.2}", a),
+        "Kurtosis { n: 4, mean: 2.50, std: 1.29, skewness: 0.00, kurtosis: -1.36 }");
+    let s: Skewness = a.into();
+    assert_eq!(format!("{:.1}", s),
+        "Skewness { n: 4, mean: 2.5, std: 1.3, skewness: 0.0 }");
+}
+
+#[test]
+fn from_raw() {
+    // The deviations from the mean are -2, -1, 0, 1 and 2.
+    let a: Kurtosis = (1..6).map(f64::from).collect();
+    let b = Kurtosis::from_raw(5, 3., 10., 0., 34.);
+    assert_eq!(a, b);
+    assert_eq!(a.kurtosis(), b.kurtosis());
+
+    let mut b = Kurtosis::from_raw(0, 0., 0., 0., 0.);
+    assert!(b.is_empty());
+    b.extend((1..6).map(f64::from));
+    assert_eq!(a, b);
+}
+
+#[test]
+#[should_panic]
+fn from_raw_negative() {
+    Kurtosis::from_raw(5, 3., -10., 0., 34.);
+}
+
+#[test]
+#[should_panic]
+fn from_raw_empty() {
+    Kurtosis::from_raw(0, 1., 0., 0., 0.);
+}
+
+#[test]
+fn raw_moments() {
+    use average::{Mean, Variance, Skewness, RawMoments};
+
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let a: Kurtosis = sequence.iter().map(|x| *x).collect();
+    let r = a.raw_moments();
+    assert_eq!(r.count, 10);
+    assert_eq!(r.mean, a.mean());
+    let b = Kurtosis::from_raw(r.count, r.mean, r.sum_2, r.sum_3, r.sum_4);
+    assert_eq!(b.raw_moments(), r);
+    assert_eq!(b.sample_variance(), a.sample_variance());
+    assert_eq!(b.skewness(), a.skewness());
+    assert_eq!(b.kurtosis(), a.kurtosis());
+
+    let s: Skewness = a.clone().into();
+    assert_eq!(s.raw_moments(), RawMoments { sum_4: 0., ..r });
+    let v: Variance = sequence.iter().map(|x| *x).collect();
+    let r = v.raw_moments();
+    assert_eq!((r.sum_3, r.sum_4), (0., 0.));
+    assert_eq!(Variance::from_raw(r.count, r.mean, r.sum_2).raw_moments(), r);
+    let m: Mean = sequence.iter().map(|x| *x).collect();
+    let r = m.raw_moments();
+    assert_eq!(Mean::from_raw(r.count, r.mean).mean(), m.mean());
+
+    assert_eq!(Kurtosis::new().raw_moments(), RawMoments {
+        count: 0, mean: 0., sum_2: 0., sum_3: 0., sum_4: 0.,
+    });
+}
+
+#[test]
+#[should_panic(expected = "estimators differ in `standardized_moment(4)`")]
+fn assert_estimator_almost_eq_kurtosis() {
+    let a: Kurtosis = [1., 2., 3., 4.].iter().map(|x| *x).collect();
+    let b = Kurtosis::from_raw(4, 2.5, 5., 0., 10.);
+    assert_estimator_almost_eq!(a, b, 1e-14);
+}
+
+#[test]
+fn merge_lower() {
+    use average::Variance;
+
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let (left, right) = sequence.split_at(4);
+    let total: Kurtosis = sequence.iter().map(|x| *x).collect();
+    let mut a: Kurtosis = left.iter().map(|x| *x).collect();
+    let b: Variance = right.iter().map(|x| *x).collect();
+    a.merge_lower(&b);
+    assert_eq!(a.len(), total.len());
+    assert_almost_eq!(a.mean(), total.mean(), 1e-14);
+    assert_almost_eq!(a.sample_variance(), total.sample_variance(), 1e-13);
+
+    // The result is the same as for merging a `Kurtosis` with vanishing
+    // third and fourth central moments.
+    let r = b.raw_moments();
+    let mut c: Kurtosis = left.iter().map(|x| *x).collect();
+    c.merge(&Kurtosis::from_raw(r.count, r.mean, r.sum_2, 0., 0.));
+    assert_eq!(a, c);
+
+    // Merging only symmetric samples gives the exact skewness.
+    let mut a: Kurtosis = [1., 2., 3.].iter().map(|x| *x).collect();
+    a.merge_lower(&[7., 8., 9.].iter().map(|x| *x).collect());
+    let total: Kurtosis = [1., 2., 3., 7., 8., 9.].iter().map(|x| *x).collect();
+    assert_almost_eq!(a.skewness(), total.skewness(), 1e-14);
+
+    let mut a: Kurtosis = Kurtosis::new();
+    a.merge_lower(&Variance::new());
+    assert!(a.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "merge_lower only supports unweighted samples")]
+fn merge_lower_weighted() {
+    use average::Variance;
+
+    let mut a: Kurtosis = [1., 2., 3.].iter().map(|x| *x).collect();
+    let mut b = Variance::new();
+    b.merge_weighted(&[4., 5.].iter().map(|x| *x).collect(), 2.);
+    a.merge_lower(&b);
+}
+
+#[test]
+fn add_precise() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let mut a: Kurtosis = Kurtosis::new();
+    for &x in sequence {
+        a.add_precise(x);
+    }
+    let b: Kurtosis = sequence.iter().map(|x| *x).collect();
+    assert_estimator_almost_eq!(a, b, 1e-14);
+
+    // A long sequence with a large mean, for which the rounding errors of
+    // the fourth powers accumulate. The two-pass `add_slice` is the reference.
+    let sequence: Vec<f64> = (0..1_000_000u64)
+        .map(|i| 1e3 + (i * 7919 % 1000) as f64 / 997.)
+        .collect();
+    let mut standard: Kurtosis = Kurtosis::new();
+    let mut precise: Kurtosis = Kurtosis::new();
+    for &x in &sequence {
+        standard.add(x);
+        precise.add_precise(x);
+    }
+    let mut reference: Kurtosis = Kurtosis::new();
+    reference.add_slice(&sequence);
+    let sum_4 = reference.raw_moments().sum_4;
+    let error_standard = (standard.raw_moments().sum_4 - sum_4).abs();
+    let error_precise = (precise.raw_moments().sum_4 - sum_4).abs();
+    assert!(error_precise * 10. < error_standard);
+    assert_almost_eq!(precise.kurtosis(), reference.kurtosis(), 1e-12);
+
+    // The compensations are applied when merging.
+    let (left, right) = sequence.split_at(sequence.len() / 2);
+    let mut merged: Kurtosis = Kurtosis::new();
+    let mut other: Kurtosis = Kurtosis::new();
+    for &x in left {
+        merged.add_precise(x);
+    }
+    for &x in right {
+        other.add_precise(x);
+    }
+    merged.merge(&other);
+    assert_eq!(merged.len(), precise.len());
+    assert_almost_eq!(merged.kurtosis(), reference.kurtosis(), 1e-12);
+}
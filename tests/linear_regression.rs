@@ -0,0 +1,59 @@
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+
+use average::{LinearRegression, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = LinearRegression::new();
+    assert!(a.is_empty());
+    assert!(a.slope().is_nan());
+    assert!(a.intercept().is_nan());
+    a.add(1., 1.);
+    assert_eq!(a.len(), 1);
+    assert!(a.slope().is_nan());
+    a.add(2., 3.);
+    assert_eq!(a.slope(), 2.);
+    assert_eq!(a.intercept(), -1.);
+    a.clear();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn exact_line() {
+    let a: LinearRegression = (-5..20)
+        .map(|x| (f64::from(x), 0.25 * f64::from(x) - 3.))
+        .collect();
+    assert_eq!(a.len(), 25);
+    assert_almost_eq!(a.slope(), 0.25, 1e-15);
+    assert_almost_eq!(a.intercept(), -3., 1e-14);
+    assert_almost_eq!(a.r_squared(), 1., 1e-15);
+}
+
+#[test]
+fn noisy_line() {
+    let a: LinearRegression = [(-1., -1.), (0., 1.), (1., 1.), (2., 3.)]
+        .iter().map(|&p| p).collect();
+    assert_almost_eq!(a.slope(), 1.2, 1e-15);
+    assert_almost_eq!(a.intercept(), 0.4, 1e-15);
+    assert_almost_eq!(a.r_squared(), 0.9, 1e-15);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[(f64, f64)] = &[
+        (1., 2.), (2., -3.), (-3., 5.), (4., 4.1), (5.5, 6.),
+        (6., 8.), (-7., 7.), (8., 9.2), (9., 0.)];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let fit_total: LinearRegression = sequence.iter().map(|&p| p).collect();
+        let mut fit_left: LinearRegression = left.iter().map(|&p| p).collect();
+        let fit_right: LinearRegression = right.iter().map(|&p| p).collect();
+        fit_left.merge(&fit_right);
+        assert_eq!(fit_total.len(), fit_left.len());
+        assert_almost_eq!(fit_total.slope(), fit_left.slope(), 1e-14);
+        assert_almost_eq!(fit_total.intercept(), fit_left.intercept(), 1e-14);
+        assert_almost_eq!(fit_total.r_squared(), fit_left.r_squared(), 1e-14);
+    }
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
 #[macro_use] extern crate average;
 
 extern crate core;
@@ -52,3 +54,86 @@ fn concatenate_moments() {
     assert_eq!(e.sample_variance(), 2.5);
     assert_eq!(e.quantile(), 3.0);
 }
+
+#[test]
+fn concatenate_merge() {
+    use average::{Variance, Min, Max, Merge};
+
+    concatenate!(Estimator,
+        [Variance, variance, mean, sample_variance],
+        [Min, min, min],
+        [Max, max, max]);
+
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    let (left, right) = sequence.split_at(3);
+    let total: Estimator = sequence.iter().map(|x| *x).collect();
+    let mut e_left: Estimator = left.iter().map(|x| *x).collect();
+    let e_right: Estimator = right.iter().map(|x| *x).collect();
+    e_left.merge(&e_right);
+    assert_eq!(e_left.mean(), total.mean());
+    assert_eq!(e_left.sample_variance(), total.sample_variance());
+    assert_eq!(e_left.min(), total.min());
+    assert_eq!(e_left.max(), total.max());
+}
+
+#[test]
+fn concatenate_without_merge() {
+    use average::{ExpMovingAverage, Mean};
+
+    // `ExpMovingAverage` does not implement `Merge`, this should still compile.
+    struct Ema(ExpMovingAverage);
+
+    impl Default for Ema {
+        fn default() -> Ema {
+            Ema(ExpMovingAverage::new(0.5))
+        }
+    }
+
+    impl Ema {
+        fn add(&mut self, x: f64) {
+            self.0.add(x);
+        }
+
+        fn moving_mean(&self) -> f64 {
+            self.0.mean()
+        }
+    }
+
+    concatenate!(Estimator, [Mean, mean], [Ema, moving_mean]);
+
+    let e: Estimator = (1..4).map(Into::into).collect();
+    assert_eq!(e.mean(), 2.0);
+    assert_eq!(e.moving_mean(), 2.25);
+}
+
+#[test]
+fn derive_default() {
+    use average::{Mean, Variance, Skewness, Kurtosis, Min, Max, Quantile,
+                  WeightedMean, WeightedMeanWithError, WeightedVariance};
+
+    #[derive(Default)]
+    struct Estimators {
+        mean: Mean,
+        variance: Variance,
+        skewness: Skewness,
+        kurtosis: Kurtosis,
+        min: Min,
+        max: Max,
+        quantile: Quantile,
+        weighted_mean: WeightedMean,
+        weighted_mean_with_error: WeightedMeanWithError,
+        weighted_variance: WeightedVariance,
+    }
+
+    let e = Estimators::default();
+    assert!(e.mean.is_empty());
+    assert!(e.variance.is_empty());
+    assert!(e.skewness.is_empty());
+    assert!(e.kurtosis.is_empty());
+    assert!(e.min.is_empty());
+    assert!(e.max.is_empty());
+    assert_eq!(e.quantile.p(), 0.5);
+    assert!(e.weighted_mean.is_empty());
+    assert!(e.weighted_mean_with_error.is_empty());
+    assert!(e.weighted_variance.is_empty());
+}
@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 extern crate average;
 
@@ -34,3 +34,73 @@ fn merge() {
         assert_eq!(max_total.max(), max_left.max());
     }
 }
+
+#[test]
+fn extend() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    let (left, right) = sequence.split_at(4);
+    let max_total: Max = sequence.iter().map(|x| *x).collect();
+    let mut max: Max = left.iter().map(|x| *x).collect();
+    max.extend(right.iter().map(|x| *x));
+    assert_eq!(max_total.max(), max.max());
+}
+
+#[test]
+fn clear() {
+    let mut m: Max = (1..6).map(f64::from).collect();
+    m.clear();
+    assert_eq!(m.max(), f64::NEG_INFINITY);
+    m.add(3.);
+    assert_eq!(m.max(), 3.);
+}
+
+#[test]
+fn arg_max() {
+    let mut m = Max::new();
+    assert!(m.is_empty());
+    assert_eq!(m.arg_max(), None);
+    for &x in &[3., 5., -1., 5., 2., -1.] {
+        m.add(x);
+    }
+    assert_eq!(m.len(), 6);
+    assert_eq!(m.arg_max(), Some(1));
+    m.clear();
+    assert_eq!(m.len(), 0);
+    assert_eq!(m.arg_max(), None);
+
+    let m = Max::from_value(10.);
+    let m = [1., 2.].iter().fold(m, |mut m, &x| { m.add(x); m });
+    assert_eq!(m.arg_max(), None);
+}
+
+#[test]
+fn merge_arg_max() {
+    let sequence: &[f64] = &[3., 5., -1., 5., 2., -1., 4.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: Max = sequence.iter().map(|x| *x).collect();
+        let mut m_left: Max = left.iter().map(|x| *x).collect();
+        let m_right: Max = right.iter().map(|x| *x).collect();
+        m_left.merge(&m_right);
+        assert_eq!(m_left.len(), total.len());
+        assert_eq!(m_left.arg_max(), total.arg_max());
+    }
+}
+
+#[test]
+fn merge_empty() {
+    let sequence: &[f64] = &[3., 5., -1., 2.];
+    let mut m: Max = sequence.iter().map(|x| *x).collect();
+    m.merge(&Max::new());
+    assert_eq!(m.max(), 5.);
+    assert_eq!(m.arg_max(), Some(1));
+    assert_eq!(m.len(), 4);
+
+    let mut e = Max::new();
+    e.merge(&Max::new());
+    assert_eq!(e.max(), f64::NEG_INFINITY);
+    assert!(e.is_empty());
+    e.merge(&m);
+    assert_eq!(e.max(), m.max());
+    assert_eq!(e.arg_max(), m.arg_max());
+}
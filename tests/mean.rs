@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 #[macro_use] extern crate average;
 
@@ -6,7 +6,7 @@ extern crate core;
 
 use core::iter::Iterator;
 
-use average::{MeanWithError, Estimate, Merge};
+use average::{Mean, MeanWithError, Estimate, Merge};
 
 #[test]
 fn trivial() {
@@ -17,6 +17,8 @@ fn trivial() {
     assert_eq!(a.len(), 1);
     assert_eq!(a.sample_variance(), 0.0);
     assert_eq!(a.population_variance(), 0.0);
+    assert_eq!(a.sample_std_dev(), 0.0);
+    assert_eq!(a.std_dev(), 0.0);
     assert_eq!(a.error(), 0.0);
     a.add(1.0);
     assert_eq!(a.mean(), 1.0);
@@ -32,7 +34,35 @@ fn simple() {
     assert_eq!(a.mean(), 3.0);
     assert_eq!(a.len(), 5);
     assert_eq!(a.sample_variance(), 2.5);
+    assert_eq!(a.sample_std_dev(), f64::sqrt(2.5));
+    assert_eq!(a.std_dev(), f64::sqrt(2.0));
     assert_almost_eq!(a.error(), f64::sqrt(0.5), 1e-16);
+    assert_eq!(a.coefficient_of_variation(), f64::sqrt(2.0) / 3.0);
+    assert_eq!(a.sample_coefficient_of_variation(), f64::sqrt(2.5) / 3.0);
+}
+
+#[test]
+fn estimate() {
+    let a: MeanWithError = (1..6).map(f64::from).collect();
+    assert_eq!(a.estimate(), a.sample_variance());
+    assert_eq!(a.estimate(), 2.5);
+}
+
+#[test]
+fn coefficient_of_variation_zero_mean() {
+    let a: MeanWithError = [-1., 1.].iter().map(|x| *x).collect();
+    assert_eq!(a.mean(), 0.0);
+    assert!(a.coefficient_of_variation().is_nan());
+    assert!(a.sample_coefficient_of_variation().is_nan());
+}
+
+#[test]
+fn single_precision() {
+    let a: MeanWithError<f32> = (1..6).map(|x| x as f32).collect();
+    assert_eq!(a.mean(), 3.0f32);
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.sample_variance(), 2.5f32);
+    assert_almost_eq!(a.error(), 0.5f32.sqrt(), 1e-7);
 }
 
 #[test]
@@ -44,6 +74,26 @@ fn numerically_unstable() {
     assert_eq!(a.sample_variance(), 30.);
 }
 
+#[test]
+fn compensated_summation() {
+    // With naive summation, the increments of the running mean suffer from
+    // cancelation against the large mean.
+    let n = 1_000_000;
+    let sequence: Vec<f64> = (0..n).map(|i| 1e8 + f64::from(i % 3)).collect();
+    let exact = 1e8 + f64::from(n / 3 + 2 * (n / 3)) / f64::from(n);
+
+    let mut naive = 0.;
+    for (i, x) in sequence.iter().enumerate() {
+        naive += (x - naive) / (i + 1) as f64;
+    }
+    let a: Mean = sequence.iter().map(|x| *x).collect();
+    let error_naive = (naive - exact).abs();
+    let error = (a.mean() - exact).abs();
+    assert!(error * 100. < error_naive,
+            "error {:e} vs. naive error {:e}", error, error_naive);
+    assert!(error <= 1.5e-8);
+}
+
 #[test]
 fn merge() {
     let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
@@ -58,3 +108,429 @@ fn merge() {
         assert_eq!(avg_total.sample_variance(), avg_left.sample_variance());
     }
 }
+
+#[test]
+fn extend() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    let (left, right) = sequence.split_at(4);
+    let avg_total: MeanWithError = sequence.iter().map(|x| *x).collect();
+    let mut avg: MeanWithError = left.iter().map(|x| *x).collect();
+    avg.extend(right.iter().map(|x| *x));
+    assert_eq!(avg_total.len(), avg.len());
+    assert_eq!(avg_total.mean(), avg.mean());
+    assert_eq!(avg_total.sample_variance(), avg.sample_variance());
+}
+
+#[test]
+fn add_slice() {
+    let sequence: Vec<f64> = (0..1000).map(|x| f64::from(x).sin() * 1e3 + 1e6).collect();
+    let (left, right) = sequence.split_at(300);
+    let avg_total: MeanWithError = sequence.iter().map(|x| *x).collect();
+    let mut avg: MeanWithError = left.iter().map(|x| *x).collect();
+    avg.add_slice(right);
+    avg.add_slice(&[]);
+    assert_eq!(avg_total.len(), avg.len());
+    assert_almost_eq!(avg_total.mean(), avg.mean(), 1e-9);
+    assert_almost_eq!(avg_total.sample_variance(), avg.sample_variance(), 1e-7);
+
+    let mut avg = Mean::new();
+    avg.add_slice(&sequence);
+    assert_eq!(avg.len(), 1000);
+    assert_almost_eq!(avg_total.mean(), avg.mean(), 1e-9);
+}
+
+#[test]
+fn sum() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    let avg_total: MeanWithError = sequence.iter().map(|x| *x).collect();
+    let parts: Vec<MeanWithError> = sequence.chunks(2)
+        .map(|c| c.iter().map(|x| *x).collect())
+        .collect();
+    let avg_ref: MeanWithError = parts.iter().sum();
+    let avg_owned: MeanWithError = parts.into_iter().sum();
+    for a in &[avg_ref, avg_owned] {
+        assert_eq!(avg_total.len(), a.len());
+        assert_eq!(avg_total.mean(), a.mean());
+        assert_eq!(avg_total.sample_variance(), a.sample_variance());
+    }
+
+    let empty: MeanWithError = vec![MeanWithError::new(); 3].into_iter().sum();
+    assert!(empty.is_empty());
+    assert_eq!(empty.mean(), 0.);
+    assert_eq!(empty.sample_variance(), 0.);
+
+    let avg: Mean = [Mean::new(), (1..4).map(f64::from).collect()].iter().sum();
+    assert_eq!(avg.mean(), 2.);
+}
+
+#[test]
+fn partial_eq() {
+    let a: MeanWithError = (1..6).map(f64::from).collect();
+    let mut b: MeanWithError = (1..6).map(f64::from).collect();
+    assert_eq!(a, b);
+    b.add(1.);
+    assert_ne!(a, b);
+    b.clear();
+    assert_eq!(b, MeanWithError::new());
+}
+
+#[test]
+fn clear() {
+    let mut a: MeanWithError = (1..6).map(f64::from).collect();
+    a.clear();
+    assert!(a.is_empty());
+    assert_eq!(a.len(), 0);
+    assert_eq!(a.mean(), 0.);
+    assert_eq!(a.sample_variance(), 0.);
+    assert_eq!(a.error(), 0.);
+    a.add(1.);
+    assert_eq!(a.mean(), 1.);
+    assert_eq!(a.len(), 1);
+}
+
+#[test]
+fn remove() {
+    let mut a: Mean = (1..6).map(f64::from).collect();
+    a.remove(5.);
+    assert_eq!(a.len(), 4);
+    assert_eq!(a.mean(), 2.5);
+    a.add(10.);
+    a.remove(1.);
+    assert_eq!(a.len(), 4);
+    assert_eq!(a.mean(), 4.75);
+    for x in &[2., 3., 4., 10.] {
+        a.remove(*x);
+    }
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.);
+}
+
+#[test]
+#[should_panic]
+fn remove_empty() {
+    let mut a = Mean::new();
+    a.remove(1.);
+}
+
+#[test]
+fn add_weighted() {
+    let mut a = Mean::new();
+    a.add_weighted(1., 3.);
+    a.add(5.);
+    assert_eq!(a.len(), 2);
+    assert_eq!(a.sum_weights(), 4.);
+    assert_eq!(a.mean(), 2.);
+
+    let mut b = Mean::new();
+    let mut c = Mean::new();
+    for x in &[1., 2., 3., 4.] {
+        b.add(*x);
+        c.add_weighted(*x, 1.);
+    }
+    assert_eq!(b.mean(), c.mean());
+    assert_eq!(b.sum_weights(), c.sum_weights());
+
+    b.merge(&a);
+    assert_eq!(b.len(), 6);
+    assert_eq!(b.sum_weights(), 8.);
+    assert_eq!(b.mean(), 2.25);
+}
+
+#[test]
+#[should_panic]
+fn add_weighted_zero() {
+    let mut a = Mean::new();
+    a.add_weighted(1., 0.);
+}
+
+#[test]
+fn remove_variance() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let mut a: MeanWithError = sequence.iter().map(|x| *x).collect();
+    for (i, x) in sequence.iter().enumerate().rev().take(4) {
+        a.remove(*x);
+        let b: MeanWithError = sequence[..i].iter().map(|x| *x).collect();
+        assert_eq!(a.len(), b.len());
+        assert_almost_eq!(a.mean(), b.mean(), 1e-14);
+        assert_almost_eq!(a.sample_variance(), b.sample_variance(), 1e-13);
+    }
+    for x in &sequence[..6] {
+        a.remove(*x);
+    }
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.);
+    assert_eq!(a.sample_variance(), 0.);
+    assert_eq!(a.population_variance(), 0.);
+}
+
+#[test]
+#[should_panic]
+fn remove_variance_empty() {
+    let mut a = MeanWithError::new();
+    a.remove(1.);
+}
+
+#[test]
+fn try_add() {
+    use average::NanError;
+
+    let mut a = MeanWithError::new();
+    assert_eq!(a.try_add(1.), Ok(()));
+    assert_eq!(a.try_add(f64::NAN), Err(NanError));
+    assert_eq!(a.try_add(3.), Ok(()));
+    assert_eq!(a.len(), 2);
+    assert_eq!(a.mean(), 2.);
+    assert_eq!(a.sample_variance(), 2.);
+
+    let mut b: Mean<f32> = Mean::new();
+    assert_eq!(b.try_add(f32::NAN), Err(NanError));
+    assert!(b.is_empty());
+    assert_eq!(b.try_add(f32::INFINITY), Ok(()));
+    assert_eq!(b.len(), 1);
+}
+
+#[test]
+fn skip_nan() {
+    use average::{Variance, Min, skip_nan};
+
+    let dirty: &[f64] = &[f64::NAN, 1., 2., f64::NAN, f64::NAN, 3., 4.5, f64::NAN];
+    let clean: &[f64] = &[1., 2., 3., 4.5];
+    let a: Variance = skip_nan(dirty.iter().map(|x| *x));
+    let b: Variance = clean.iter().map(|x| *x).collect();
+    assert_eq!(a, b);
+    let m: Min = skip_nan(dirty.iter().map(|x| *x));
+    assert_eq!(m.min(), 1.);
+    assert_eq!(m.len(), 4);
+}
+
+#[test]
+fn statistics() {
+    use average::{Statistics, Kurtosis, Max};
+
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    let a = sequence.iter().map(|x| *x).statistics::<Mean>();
+    assert_eq!(a.mean(), 5.);
+    let k: Kurtosis<f32> = (1..10).map(|i| i as f32).statistics();
+    assert_eq!(k.len(), 9);
+    let m = sequence.iter().map(|x| *x).statistics::<Max>();
+    assert_eq!(m.max(), 9.);
+}
+
+#[test]
+fn confidence_interval() {
+    let a: MeanWithError = (1..6).map(f64::from).collect();
+    assert_almost_eq!(a.error(), 0.5f64.sqrt(), 1e-15);
+    let (low, high) = a.confidence_interval(0.95);
+    assert_almost_eq!(low, 3. - 1.959_963_984_540_054 * a.error(), 1e-8);
+    assert_almost_eq!(high, 3. + 1.959_963_984_540_054 * a.error(), 1e-8);
+    // The critical value of the t-distribution with 4 degrees of freedom.
+    let (low, high) = a.confidence_interval_t(0.95);
+    assert_almost_eq!(low, 3. - 2.776_445_105_197_799 * a.error(), 1e-8);
+    assert_almost_eq!(high, 3. + 2.776_445_105_197_799 * a.error(), 1e-8);
+
+    let mut b: MeanWithError = MeanWithError::new();
+    assert!(b.confidence_interval(0.95).0.is_nan());
+    b.add(1.);
+    assert!(b.confidence_interval(0.95).1.is_nan());
+    assert!(b.confidence_interval_t(0.95).0.is_nan());
+}
+
+#[test]
+#[should_panic]
+fn confidence_interval_invalid_level() {
+    let a: MeanWithError = (1..6).map(f64::from).collect();
+    a.confidence_interval(1.);
+}
+
+#[test]
+fn welch_t() {
+    use average::{Variance, welch_t};
+
+    // Example from https://en.wikipedia.org/wiki/Welch%27s_t-test, where the
+    // rounded results t = -2.46 and df = 24.99 are given.
+    let a: Variance = [
+        27.5, 21.0, 19.0, 23.6, 17.0, 17.9, 16.9, 20.1,
+        21.9, 22.6, 23.1, 19.6, 19.0, 21.7, 21.4,
+    ].iter().map(|x| *x).collect();
+    let b: Variance = [
+        27.1, 22.0, 20.8, 23.4, 23.4, 23.5, 25.8, 22.0,
+        24.8, 20.2, 21.9, 22.1, 22.9, 20.5, 24.4,
+    ].iter().map(|x| *x).collect();
+    let (t, df) = welch_t(&a, &b);
+    assert_almost_eq!(t, -2.455_356_398_286_006, 1e-12);
+    assert_almost_eq!(df, 24.988_529_290_231_42, 1e-10);
+    let (t, df) = welch_t(&b, &a);
+    assert_almost_eq!(t, 2.455_356_398_286_006, 1e-12);
+    assert_almost_eq!(df, 24.988_529_290_231_42, 1e-10);
+
+    let c: Variance = [1.].iter().map(|x| *x).collect();
+    let (t, df) = welch_t(&a, &c);
+    assert!(t.is_nan() && df.is_nan());
+}
+
+#[test]
+fn welch_t_zero_variance() {
+    use average::{Variance, welch_t};
+
+    let a: Variance = [2., 2., 2.].iter().map(|x| *x).collect();
+    let b: Variance = [1., 1.].iter().map(|x| *x).collect();
+    let (t, df) = welch_t(&a, &b);
+    assert!(t.is_nan() && df.is_nan());
+    let (t, df) = welch_t(&a, &a);
+    assert!(t.is_nan() && df.is_nan());
+
+    // One sample with zero variance is fine.
+    let c: Variance = [1., 2., 3.].iter().map(|x| *x).collect();
+    let (t, df) = welch_t(&a, &c);
+    assert_eq!(t, 0.);
+    assert_eq!(df, 2.);
+}
+
+#[test]
+fn z_score() {
+    let a: MeanWithError = [2., 4., 4., 4., 5., 5., 7., 9.].iter().map(|x| *x).collect();
+    assert_eq!(a.std_dev(), 2.);
+    assert_eq!(a.z_score(5.), 0.);
+    assert_eq!(a.z_score(9.), 2.);
+    assert_eq!(a.z_score(1.), -2.);
+    let mut xs = [1., 5., 9.];
+    a.standardize_slice(&mut xs);
+    assert_eq!(xs, [-2., 0., 2.]);
+
+    let b: MeanWithError = [3., 3.].iter().map(|x| *x).collect();
+    assert!(b.z_score(3.).is_nan());
+    let mut ys = [3., 4.];
+    b.standardize_slice(&mut ys);
+    assert!(ys.iter().all(|y| y.is_nan()));
+}
+
+#[test]
+fn display() {
+    use average::Variance;
+
+    let a: Variance = [1., 2., 3.].iter().map(|x| *x).collect();
+    assert_eq!(format!("{}", a), "Variance { n: 3, mean: 2, std: 1 }");
+    assert_eq!(format!("{:.2}", a), "Variance { n: 3, mean: 2.00, std: 1.00 }");
+    let m: Mean = [1., 2.].iter().map(|x| *x).collect();
+    assert_eq!(format!("{:.3}", m), "Mean { n: 2, mean: 1.500 }");
+}
+
+#[test]
+fn merge_weighted() {
+    use average::Variance;
+
+    let left: &[f64] = &[1., 2., 4.];
+    let right: &[f64] = &[3., 8.];
+    let mut a: Variance = left.iter().map(|x| *x).collect();
+    let b: Variance = right.iter().map(|x| *x).collect();
+    a.merge_weighted(&b, 3.);
+    // Merging with weight 3 is like adding each observation three times.
+    let mut expected: Variance = left.iter().map(|x| *x).collect();
+    for _ in 0..3 {
+        expected.extend(right.iter().map(|x| *x));
+    }
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.sum_weights(), expected.sum_weights());
+    assert_almost_eq!(a.mean(), expected.mean(), 1e-14);
+    assert_almost_eq!(a.sample_variance(), expected.sample_variance(), 1e-13);
+
+    // Merging with weight 1 is the same as merging.
+    let mut c: Mean = left.iter().map(|x| *x).collect();
+    let mut d = c.clone();
+    let e: Mean = right.iter().map(|x| *x).collect();
+    c.merge(&e);
+    d.merge_weighted(&e, 1.);
+    assert_eq!(c, d);
+}
+
+#[test]
+#[should_panic]
+fn merge_weighted_zero() {
+    let mut a: Mean = [1.].iter().map(|x| *x).collect();
+    let b = a.clone();
+    a.merge_weighted(&b, 0.);
+}
+
+#[test]
+fn merge_all() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    let total: Mean = sequence.iter().map(|x| *x).collect();
+    let parts: Vec<Mean> = sequence.chunks(2)
+        .map(|chunk| chunk.iter().map(|x| *x).collect())
+        .collect();
+    let by_ref = Mean::merge_all(&parts);
+    assert_eq!(by_ref.len(), total.len());
+    assert_almost_eq!(by_ref.mean(), total.mean(), 1e-15);
+    let by_value = Mean::merge_all(parts);
+    assert_eq!(by_value, by_ref);
+    assert!(Mean::<f64>::merge_all(Vec::<Mean>::new()).is_empty());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "sample size exceeds the precision")]
+fn len_exceeds_precision() {
+    let mut a: Mean<f32> = Mean::from_raw(1 << 24, 1.);
+    a.add(1.);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "sample size exceeds the precision")]
+fn len_exceeds_precision_add_weighted() {
+    let mut a: Mean<f32> = Mean::from_raw(1 << 24, 1.);
+    a.add_weighted(1., 0.5);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "sample size exceeds the precision")]
+fn len_exceeds_precision_merge() {
+    let mut a: Mean<f32> = Mean::from_raw(1 << 24, 1.);
+    a.merge_weighted(&Mean::from_raw(1, 1.), 2.);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "sample size exceeds the precision")]
+fn len_exceeds_precision_from_raw() {
+    Mean::<f32>::from_raw((1 << 24) + 1, 1.);
+}
+
+#[test]
+fn len_within_precision() {
+    let mut a: Mean<f32> = Mean::from_raw((1 << 24) - 1, 1.);
+    a.add(1.);
+    assert_eq!(a.len(), 1 << 24);
+}
+
+#[test]
+fn large_weights_within_precision() {
+    let mut a = Mean::new();
+    a.add_weighted(1., 1e17);
+    a.add(2.);
+    assert_eq!(a.len(), 2);
+
+    let mut b = Mean::new();
+    b.add(1.);
+    b.merge_weighted(&a, 1e20);
+    b.add(3.);
+    assert_eq!(b.len(), 4);
+}
+
+#[test]
+fn add_iter() {
+    use average::Variance;
+
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    let mut a = Variance::new();
+    let mut consumed = 0;
+    for chunk in sequence.chunks(4) {
+        consumed += a.add_iter(chunk.iter().map(|x| *x));
+    }
+    assert_eq!(consumed, 9);
+    assert_eq!(a.add_iter(core::iter::empty()), 0);
+    let b: Variance = sequence.iter().map(|x| *x).collect();
+    assert_eq!(a.len(), b.len());
+    assert_almost_eq!(a.sample_variance(), b.sample_variance(), 1e-14);
+}
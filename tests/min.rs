@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 extern crate average;
 
@@ -34,3 +34,82 @@ fn merge() {
         assert_eq!(min_total.min(), min_left.min());
     }
 }
+
+#[test]
+fn extend() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    let (left, right) = sequence.split_at(4);
+    let min_total: Min = sequence.iter().map(|x| *x).collect();
+    let mut min: Min = left.iter().map(|x| *x).collect();
+    min.extend(right.iter().map(|x| *x));
+    assert_eq!(min_total.min(), min.min());
+}
+
+#[test]
+fn clear() {
+    let mut m: Min = (1..6).map(f64::from).collect();
+    m.clear();
+    assert_eq!(m.min(), f64::INFINITY);
+    m.add(3.);
+    assert_eq!(m.min(), 3.);
+}
+
+#[test]
+fn arg_min() {
+    let mut m = Min::new();
+    assert!(m.is_empty());
+    assert_eq!(m.arg_min(), None);
+    for &x in &[3., 5., -1., 5., 2., -1.] {
+        m.add(x);
+    }
+    assert_eq!(m.len(), 6);
+    assert_eq!(m.arg_min(), Some(2));
+    m.clear();
+    assert_eq!(m.len(), 0);
+    assert_eq!(m.arg_min(), None);
+
+    let m = Min::from_value(-10.);
+    let m = [1., 2.].iter().fold(m, |mut m, &x| { m.add(x); m });
+    assert_eq!(m.arg_min(), None);
+}
+
+#[test]
+fn merge_arg_min() {
+    let sequence: &[f64] = &[3., 5., -1., 5., 2., -1., 4.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: Min = sequence.iter().map(|x| *x).collect();
+        let mut m_left: Min = left.iter().map(|x| *x).collect();
+        let m_right: Min = right.iter().map(|x| *x).collect();
+        m_left.merge(&m_right);
+        assert_eq!(m_left.len(), total.len());
+        assert_eq!(m_left.arg_min(), total.arg_min());
+    }
+}
+
+#[test]
+fn merge_empty() {
+    let sequence: &[f64] = &[3., 5., -1., 2.];
+    let mut m: Min = sequence.iter().map(|x| *x).collect();
+    m.merge(&Min::new());
+    assert_eq!(m.min(), -1.);
+    assert_eq!(m.arg_min(), Some(2));
+    assert_eq!(m.len(), 4);
+
+    let mut e = Min::new();
+    e.merge(&Min::new());
+    assert_eq!(e.min(), f64::INFINITY);
+    assert!(e.is_empty());
+    e.merge(&m);
+    assert_eq!(e.min(), m.min());
+    assert_eq!(e.arg_min(), m.arg_min());
+}
+
+#[test]
+fn try_add() {
+    let mut m = Min::new();
+    assert!(m.try_add(f64::NAN).is_err());
+    assert!(m.try_add(f64::NEG_INFINITY).is_ok());
+    assert_eq!(m.min(), f64::NEG_INFINITY);
+    assert_eq!(m.len(), 1);
+}
@@ -0,0 +1,89 @@
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+
+use average::{Moments, Kurtosis, Estimate, Merge};
+
+/// Calculate the `k`th central moment using the two-pass algorithm.
+fn two_pass_central_moment(xs: &[f64], k: i32) -> f64 {
+    let n = xs.len() as f64;
+    let mean = xs.iter().sum::<f64>() / n;
+    xs.iter().map(|x| (x - mean).powi(k)).sum::<f64>() / n
+}
+
+const SEQUENCE: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+
+#[test]
+fn trivial() {
+    let mut a: Moments<4> = Moments::new();
+    assert!(a.is_empty());
+    assert_eq!(a.central_moment(0), 1.);
+    assert_eq!(a.central_moment(2), 0.);
+    assert!(a.standardized_moment(3).is_nan());
+    a.add(1.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean(), 1.);
+    assert_eq!(a.central_moment(1), 0.);
+    assert_eq!(a.central_moment(4), 0.);
+    assert!(a.standardized_moment(4).is_nan());
+    a.add(3.);
+    assert_eq!(a.mean(), 2.);
+    assert_eq!(a.central_moment(2), 1.);
+    assert_eq!(a.central_moment(3), 0.);
+    assert_eq!(a.central_moment(4), 1.);
+    a.clear();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn agrees_with_kurtosis() {
+    let a: Moments<4> = SEQUENCE.iter().map(|x| *x).collect();
+    let b: Kurtosis = SEQUENCE.iter().map(|x| *x).collect();
+    assert_eq!(a.len(), b.len());
+    assert_almost_eq!(a.mean(), b.mean(), 1e-14);
+    assert_eq!(a.estimate(), a.mean());
+    assert_almost_eq!(a.central_moment(2), b.population_variance(), 1e-13);
+    assert_almost_eq!(a.standardized_moment(2), 1., 1e-15);
+    assert_almost_eq!(a.standardized_moment(3), b.skewness(), 1e-14);
+    assert_almost_eq!(a.standardized_moment(4), b.kurtosis_raw(), 1e-14);
+}
+
+#[test]
+fn higher_moments() {
+    let a: Moments<8> = SEQUENCE.iter().map(|x| *x).collect();
+    for k in 2..9 {
+        let expected = two_pass_central_moment(SEQUENCE, k as i32);
+        assert_almost_eq!(a.central_moment(k) / expected, 1., 1e-13);
+    }
+}
+
+#[test]
+fn single_precision() {
+    let a: Moments<5, f32> = (1..6).map(|x| x as f32).collect();
+    assert_eq!(a.mean(), 3.);
+    assert_eq!(a.central_moment(4), 6.8);
+    assert_eq!(a.central_moment(5), 0.);
+}
+
+#[test]
+#[should_panic]
+fn order_too_large() {
+    let a: Moments<3> = SEQUENCE.iter().map(|x| *x).collect();
+    a.central_moment(4);
+}
+
+#[test]
+fn merge() {
+    for mid in 0..SEQUENCE.len() {
+        let (left, right) = SEQUENCE.split_at(mid);
+        let total: Moments<6> = SEQUENCE.iter().map(|x| *x).collect();
+        let mut avg_left: Moments<6> = left.iter().map(|x| *x).collect();
+        let avg_right: Moments<6> = right.iter().map(|x| *x).collect();
+        avg_left.merge(&avg_right);
+        assert_eq!(total.len(), avg_left.len());
+        assert_almost_eq!(total.mean(), avg_left.mean(), 1e-14);
+        for k in 2..7 {
+            assert_almost_eq!(total.central_moment(k) / avg_left.central_moment(k), 1., 1e-13);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "ndarray")]
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+extern crate ndarray;
+
+use average::{Mean, Variance, Skewness, Kurtosis, mean_axis};
+use ndarray::{arr1, arr2, Array2, Axis};
+
+#[test]
+fn from_array() {
+    let data: Vec<f64> = (0..1000).map(|i| f64::from(i % 97) * 0.5 - 3.).collect();
+    let a = arr1(&data);
+
+    let b: Mean = data.iter().map(|x| *x).collect();
+    assert_almost_eq!(Mean::from_array(a.view()).mean(), b.mean(), 1e-14);
+
+    let b: Variance = data.iter().map(|x| *x).collect();
+    assert_almost_eq!(Variance::from_array(a.view()).sample_variance(),
+                      b.sample_variance(), 1e-12);
+
+    let b: Skewness = data.iter().map(|x| *x).collect();
+    assert_almost_eq!(Skewness::from_array(a.view()).skewness(), b.skewness(), 1e-12);
+
+    let b: Kurtosis = data.iter().map(|x| *x).collect();
+    assert_estimator_almost_eq!(Kurtosis::from_array(a.view()), b, 1e-12);
+
+    assert!(Variance::<f64>::from_array(arr1(&[]).view()).is_empty());
+}
+
+#[test]
+fn from_array_strided() {
+    // Every other element, which is not contiguous.
+    let a = arr1(&[1., 10., 2., 20., 3., 30.]);
+    let view = a.slice(ndarray::s![..;2]);
+    assert!(view.as_slice().is_none());
+    let v = Variance::from_array(view);
+    assert_eq!(v.len(), 3);
+    assert_eq!(v.mean(), 2.);
+    assert_eq!(v.sample_variance(), 1.);
+}
+
+#[test]
+fn axis() {
+    let a = arr2(&[[1., 10., 100.],
+                   [2., 20., 200.],
+                   [3., 30., 300.],
+                   [4., 40., 400.]]);
+
+    let columns = mean_axis::<Mean, _>(a.view(), Axis(0));
+    assert_eq!(columns.len(), 3);
+    assert_eq!(columns[0].mean(), 2.5);
+    assert_eq!(columns[1].mean(), 25.);
+    assert_eq!(columns[2].mean(), 250.);
+    assert_eq!(columns[2].len(), 4);
+
+    let rows = mean_axis::<Variance, _>(a.view(), Axis(1));
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[0].mean(), 37.);
+    assert_eq!(rows[3].len(), 3);
+
+    // The result agrees with estimating each column separately, also for a
+    // transposed view.
+    let t = a.t();
+    let columns = mean_axis::<Variance, _>(t, Axis(1));
+    for (i, e) in columns.iter().enumerate() {
+        let column = Variance::from_array(a.column(i));
+        assert_eq!(e.len(), column.len());
+        assert_almost_eq!(e.sample_variance(), column.sample_variance(), 1e-10);
+    }
+
+    let empty = Array2::<f64>::zeros((0, 2));
+    let columns = mean_axis::<Mean, _>(empty.view(), Axis(0));
+    assert_eq!(columns.len(), 2);
+    assert!(columns[0].is_empty());
+}
+
+#[test]
+#[should_panic]
+fn axis_out_of_bounds() {
+    let a = arr2(&[[1., 2.]]);
+    mean_axis::<Mean, _>(a.view(), Axis(2));
+}
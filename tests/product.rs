@@ -0,0 +1,58 @@
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+
+use average::{Product, Estimate, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = Product::new();
+    assert!(a.is_empty());
+    assert_eq!(a.product(), 1.);
+    a.add(3.);
+    assert_eq!(a.len(), 1);
+    assert_almost_eq!(a.product(), 3., 1e-15);
+    a.add(-4.);
+    assert_almost_eq!(a.product(), -12., 1e-14);
+    assert!(a.is_negative());
+    assert_eq!(a.estimate(), a.product());
+    a.clear();
+    assert!(a.is_empty());
+    assert_eq!(a.product(), 1.);
+}
+
+#[test]
+fn no_overflow() {
+    let a: Product = (0..1000).map(|i| if i % 2 == 0 { 1e300 } else { -1e-300 }).collect();
+    assert_almost_eq!(a.product(), 1., 1e-10);
+    let b: Product = (0..1000).map(|_| 1e300).collect();
+    assert_eq!(b.product(), f64::INFINITY);
+    assert_almost_eq!(b.ln_abs_product(), 1000. * 1e300f64.ln(), 1e-6);
+}
+
+#[test]
+fn zero_and_nan() {
+    let a: Product = [2., 0., -8.].iter().map(|x| *x).collect();
+    assert_eq!(a.product(), 0.);
+    assert_eq!(a.ln_abs_product(), f64::NEG_INFINITY);
+    let b: Product = [2., f64::NAN].iter().map(|x| *x).collect();
+    assert!(b.product().is_nan());
+    assert!(b.ln_abs_product().is_nan());
+    let c: Product = [0., f64::INFINITY].iter().map(|x| *x).collect();
+    assert!(c.product().is_nan());
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., -2., 3., -4., 5., 0.5, 7., -8., 9.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: Product = sequence.iter().map(|x| *x).collect();
+        let mut prod_left: Product = left.iter().map(|x| *x).collect();
+        let prod_right: Product = right.iter().map(|x| *x).collect();
+        prod_left.merge(&prod_right);
+        assert_eq!(total.len(), prod_left.len());
+        assert_almost_eq!(total.product(), prod_left.product(), 1e-9);
+        assert_almost_eq!(total.product(), -30240., 1e-9);
+    }
+}
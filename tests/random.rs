@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 #[macro_use] extern crate average;
 
@@ -17,6 +17,8 @@ fn normal_distribution() {
     assert_almost_eq!(a.mean(), 2.0, 1e-2);
     assert_almost_eq!(a.sample_variance().sqrt(), 3.0, 1e-2);
     assert_almost_eq!(a.population_variance().sqrt(), 3.0, 1e-2);
+    assert_almost_eq!(a.sample_std_dev(), a.sample_variance().sqrt(), 1e-15);
+    assert_almost_eq!(a.std_dev(), a.population_variance().sqrt(), 1e-15);
     assert_almost_eq!(a.error_mean(), 0.0, 1e-2);
     assert_almost_eq!(a.skewness(), 0.0, 1e-2);
     assert_almost_eq!(a.kurtosis(), 0.0, 4e-2);
@@ -34,7 +36,85 @@ fn exponential_distribution() {
     assert_almost_eq!(a.mean(), 1./lambda, 1e-2);
     assert_almost_eq!(a.sample_variance().sqrt(), 1./lambda, 1e-2);
     assert_almost_eq!(a.population_variance().sqrt(), 1./lambda, 1e-2);
+    assert_almost_eq!(a.sample_std_dev(), a.sample_variance().sqrt(), 1e-15);
+    assert_almost_eq!(a.std_dev(), a.population_variance().sqrt(), 1e-15);
     assert_almost_eq!(a.error_mean(), 0.0, 1e-2);
     assert_almost_eq!(a.skewness(), 2.0, 1e-2);
     assert_almost_eq!(a.kurtosis(), 6.0, 1e-1);
 }
+
+#[test]
+fn merge_uneven_splits() {
+    use average::{Variance, Merge};
+    use rand::Rng;
+
+    let mut rng = ::rand::thread_rng();
+    // The offset makes the variance ill-conditioned.
+    let xs: Vec<f64> = (0..200_000).map(|_| 1e6 + rng.gen::<f64>()).collect();
+    let mut reference = Variance::new();
+    reference.add_slice(&xs);
+    for _ in 0..50 {
+        // Split into parts of widely varying sizes, from 1 to most of the
+        // sequence.
+        let mut merged = Variance::new();
+        let mut start = 0;
+        while start < xs.len() {
+            let max_len = 10usize.pow(rng.gen_range(0, 6));
+            let end = ::std::cmp::min(xs.len(), start + rng.gen_range(1, max_len + 1));
+            let part: Variance = xs[start..end].iter().map(|x| *x).collect();
+            if rng.gen() {
+                merged.merge(&part);
+            } else {
+                let mut part = part;
+                part.merge(&merged);
+                merged = part;
+            }
+            start = end;
+        }
+        assert_eq!(merged.len(), reference.len());
+        assert_almost_eq!(merged.mean(), reference.mean(), 1e-9);
+        assert_almost_eq!(merged.sample_variance() / reference.sample_variance(), 1., 1e-8);
+        assert_almost_eq!(merged.error() / reference.error(), 1., 1e-8);
+    }
+}
+
+#[test]
+fn add_matches_reference() {
+    use rand::Rng;
+    // Terriberry's updates with the sample size converted from `u64` for
+    // every observation, as the estimators did before caching it as `f64`.
+    // The mean is compensated like in `Mean`.
+    let mut rng = ::rand::thread_rng();
+    for len in &[1, 2, 3, 10, 1000, 100_000] {
+        let scale = rng.gen_range(1e-3, 1e3);
+        let offset = rng.gen_range(-1e6, 1e6);
+        let mut a = Kurtosis::new();
+        let (mut n, mut avg, mut compensation) = (0u64, 0f64, 0f64);
+        let (mut sum_2, mut sum_3, mut sum_4) = (0f64, 0f64, 0f64);
+        for _ in 0..*len {
+            let x = offset + scale * rng.gen::<f64>();
+            a.add(x);
+            n += 1;
+            let n_f = n as f64;
+            let delta = x - (avg + compensation);
+            let delta_n = delta / n_f;
+            let delta_n_sq = delta_n * delta_n;
+            let term = delta * delta_n * (n_f - 1.);
+            let t = avg + delta_n;
+            if avg.abs() >= delta_n.abs() {
+                compensation += (avg - t) + delta_n;
+            } else {
+                compensation += (delta_n - t) + avg;
+            }
+            avg = t;
+            sum_4 = sum_4 + term * delta_n_sq * (n_f * n_f - 3. * n_f + 3.)
+                + 6. * delta_n_sq * sum_2 - 4. * delta_n * sum_3;
+            sum_3 = sum_3 + term * delta_n * (n_f - 2.) - 3. * delta_n * sum_2;
+            sum_2 += delta_n * delta_n * n_f * (n_f - 1.);
+        }
+        let r = a.raw_moments();
+        assert_eq!(r.count, n);
+        assert_eq!(r.mean, avg + compensation);
+        assert_eq!((r.sum_2, r.sum_3, r.sum_4), (sum_2, sum_3, sum_4));
+    }
+}
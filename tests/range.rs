@@ -0,0 +1,66 @@
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+extern crate average;
+
+use average::{Range, Estimate, Merge};
+
+#[test]
+fn trivial() {
+    let mut r = Range::new();
+    assert!(r.is_empty());
+    assert_eq!(r.len(), 0);
+    assert_eq!(r.min(), f64::INFINITY);
+    assert_eq!(r.max(), f64::NEG_INFINITY);
+    assert!(r.range().is_nan());
+    r.add(2.);
+    assert!(!r.is_empty());
+    assert_eq!(r.range(), 0.);
+    r.add(-1.);
+    r.add(3.);
+    assert_eq!(r.min(), -1.);
+    assert_eq!(r.max(), 3.);
+    assert_eq!(r.range(), 4.);
+    assert_eq!(r.estimate(), 4.);
+    assert_eq!(r.len(), 3);
+    r.clear();
+    assert!(r.is_empty());
+}
+
+#[test]
+fn nan() {
+    let mut r = Range::new();
+    r.add(f64::NAN);
+    assert!(!r.is_empty());
+    assert_eq!(r.len(), 1);
+    assert!(r.range().is_nan());
+    r.add(1.);
+    r.add(3.);
+    assert_eq!(r.len(), 3);
+    assert_eq!(r.range(), 2.);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., -2., 3., 4., 5., -6., 7., 8., 9.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let range_total: Range = sequence.iter().map(|x| *x).collect();
+        let mut range_left: Range = left.iter().map(|x| *x).collect();
+        let range_right: Range = right.iter().map(|x| *x).collect();
+        range_left.merge(&range_right);
+        assert_eq!(range_left.len(), 9);
+        assert_eq!(range_total.min(), range_left.min());
+        assert_eq!(range_total.max(), range_left.max());
+        assert_eq!(range_left.range(), 15.);
+    }
+}
+
+#[test]
+fn merge_empty() {
+    let mut r: Range = (1..6).map(f64::from).collect();
+    r.merge(&Range::new());
+    assert_eq!(r.range(), 4.);
+    let mut r = Range::new();
+    r.merge(&Range::new());
+    assert!(r.range().is_nan());
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "rayon")]
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+extern crate rayon;
+
+use average::{Mean, Variance, Skewness, Kurtosis};
+use rayon::prelude::*;
+
+#[test]
+fn from_par_iter() {
+    let data: Vec<f64> = (0..10_000).map(|i| f64::from(i % 97) * 0.5 - 3.).collect();
+
+    let a: Mean = data.par_iter().map(|x| *x).collect();
+    let b: Mean = data.iter().map(|x| *x).collect();
+    assert_eq!(a.len(), b.len());
+    assert_almost_eq!(a.mean(), b.mean(), 1e-12);
+
+    let a: Variance = data.par_iter().map(|x| *x).collect();
+    let b: Variance = data.iter().map(|x| *x).collect();
+    assert_almost_eq!(a.sample_variance(), b.sample_variance(), 1e-10);
+
+    let a: Skewness = data.par_iter().map(|x| *x).collect();
+    let b: Skewness = data.iter().map(|x| *x).collect();
+    assert_almost_eq!(a.skewness(), b.skewness(), 1e-10);
+
+    let a: Kurtosis = data.par_iter().map(|x| *x).collect();
+    let b: Kurtosis = data.iter().map(|x| *x).collect();
+    assert_eq!(a.len(), 10_000);
+    assert_almost_eq!(a.kurtosis(), b.kurtosis(), 1e-10);
+}
+
+#[test]
+fn from_par_iter_empty() {
+    let a: Variance<f32> = Vec::<f32>::new().into_par_iter().collect();
+    assert!(a.is_empty());
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+extern crate average;
+
+use average::{ReservoirSample, Merge};
+
+#[test]
+fn few_observations() {
+    let mut a = ReservoirSample::new(5);
+    assert!(a.is_empty());
+    assert_eq!(a.capacity(), 5);
+    a.extend([1., 2., 3.].iter().map(|x| *x));
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.samples(), &[1., 2., 3.]);
+    a.clear();
+    assert!(a.is_empty());
+    assert!(a.samples().is_empty());
+}
+
+#[test]
+fn reproducible() {
+    let mut a = ReservoirSample::with_seed(10, 7);
+    let mut b = ReservoirSample::with_seed(10, 7);
+    a.extend((0..1000).map(f64::from));
+    b.extend((0..1000).map(f64::from));
+    assert_eq!(a.samples(), b.samples());
+    assert_eq!(a.samples().len(), 10);
+    assert!(a.samples().iter().all(|&x| (0. ..1000.).contains(&x)));
+}
+
+/// Count how often each of `0..n` is sampled.
+fn frequencies<F: Fn(u64) -> ReservoirSample>(n: usize, sample: F) -> Vec<u64> {
+    let mut counts = vec![0; n];
+    for seed in 0..10_000 {
+        for &x in sample(seed).samples() {
+            counts[x as usize] += 1;
+        }
+    }
+    counts
+}
+
+#[test]
+fn uniform() {
+    let counts = frequencies(20, |seed| {
+        let mut a = ReservoirSample::with_seed(5, seed);
+        a.extend((0..20).map(f64::from));
+        a
+    });
+    // Every observation is sampled with probability 1/4.
+    for &c in &counts {
+        assert!((2200..2800).contains(&c), "{:?}", counts);
+    }
+}
+
+#[test]
+fn merge_uniform() {
+    let counts = frequencies(20, |seed| {
+        let mut a = ReservoirSample::with_seed(5, seed);
+        let mut b = ReservoirSample::with_seed(5, seed + 1_000_000);
+        a.extend((0..4).map(f64::from));
+        b.extend((4..20).map(f64::from));
+        a.merge(&b);
+        assert_eq!(a.len(), 20);
+        assert_eq!(a.samples().len(), 5);
+        a
+    });
+    for &c in &counts {
+        assert!((2200..2800).contains(&c), "{:?}", counts);
+    }
+}
+
+#[test]
+fn merge_few_observations() {
+    let mut a = ReservoirSample::new(5);
+    let mut b = ReservoirSample::new(5);
+    a.extend([1., 2.].iter().map(|x| *x));
+    b.extend([3.].iter().map(|x| *x));
+    a.merge(&b);
+    let mut samples = a.samples().to_vec();
+    samples.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    assert_eq!(samples, vec![1., 2., 3.]);
+    a.merge(&ReservoirSample::new(5));
+    assert_eq!(a.len(), 3);
+}
+
+#[test]
+#[should_panic]
+fn merge_different_capacity() {
+    let mut a = ReservoirSample::new(5);
+    let mut b = ReservoirSample::new(4);
+    b.add(1.);
+    a.merge(&b);
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "serde")]
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+extern crate average;
+extern crate serde_json;
+
+use average::{Mean, Variance, Skewness, Kurtosis, Moments, Min, Max, Quantile, Quantiles,
+              WarmupQuantile, WeightedMeanWithError, Estimate, Merge};
+
+#[test]
+fn moments() {
+    let a: Mean = (1..6).map(f64::from).collect();
+    let b: Mean = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+    assert_eq!(a, b);
+
+    let a: Variance = (1..6).map(f64::from).collect();
+    let b: Variance = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+    assert_eq!(a, b);
+
+    let a: Skewness = (1..6).map(f64::from).collect();
+    let b: Skewness = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+    assert_eq!(a, b);
+
+    let a: Kurtosis = (1..6).map(f64::from).collect();
+    let b: Kurtosis = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+    assert_eq!(a, b);
+
+    let a: Moments<6> = (1..6).map(f64::from).collect();
+    let b: Moments<6> = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+    assert_eq!(a, b);
+    assert!(serde_json::from_str::<Moments<7>>(&serde_json::to_string(&a).unwrap()).is_err());
+}
+
+#[test]
+fn min_max() {
+    let a: Min = (1..6).map(f64::from).collect();
+    let b: Min = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+    assert_eq!(a.min(), b.min());
+    assert_eq!(a.arg_min(), b.arg_min());
+
+    let a: Max = (1..6).map(f64::from).collect();
+    let b: Max = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+    assert_eq!(a.max(), b.max());
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.arg_max(), b.arg_max());
+}
+
+#[test]
+fn quantile() {
+    let mut a = Quantile::new(0.9);
+    for i in 0..20 {
+        a.add(f64::from(i));
+    }
+    let b: Quantile = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+    assert_eq!(a.p(), b.p());
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.quantile(), b.quantile());
+
+    let mut a = Quantiles::new(&[0.1, 0.9]);
+    for i in 0..20 {
+        a.add(f64::from(i));
+    }
+    let b: Quantiles = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+    assert_eq!(a.ps(), b.ps());
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.quantiles(), b.quantiles());
+
+    let mut a = Quantile::with_warmup(0.9, 10);
+    for i in 0..5 {
+        a.add(f64::from(i));
+    }
+    let b: WarmupQuantile = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.quantile(), b.quantile());
+}
+
+#[test]
+fn weighted_mean() {
+    let a: WeightedMeanWithError = (1..6).map(|x| (f64::from(x), 0.5)).collect();
+    let b: WeightedMeanWithError =
+        serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.sum_weights(), b.sum_weights());
+    assert_eq!(a.weighted_mean(), b.weighted_mean());
+    assert_eq!(a.error(), b.error());
+}
+
+#[test]
+fn merge_deserialized() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let (left, right) = sequence.split_at(4);
+    let total: Kurtosis = sequence.iter().map(|x| *x).collect();
+    let mut partial: Kurtosis = left.iter().map(|x| *x).collect();
+    let remote: Kurtosis = right.iter().map(|x| *x).collect();
+    let json = serde_json::to_string(&remote).unwrap();
+    partial.merge(&serde_json::from_str(&json).unwrap());
+    assert_eq!(total.len(), partial.len());
+    assert!((total.mean() - partial.mean()).abs() < 1e-14);
+    assert!((total.kurtosis() - partial.kurtosis()).abs() < 1e-14);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn histogram() {
+    use average::DynHistogram;
+
+    let mut a = DynHistogram::with_bounds(0., 4., 4);
+    a.extend([-1., 0.5, 1.5, 1.7, 3.5, 5.].iter().map(|x| *x));
+    let b: DynHistogram = serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+    assert_eq!(a.edges(), b.edges());
+    assert_eq!(a.counts(), b.counts());
+    assert_eq!(a.underflow(), b.underflow());
+    assert_eq!(a.overflow(), b.overflow());
+}
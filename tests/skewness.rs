@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 #[macro_use] extern crate average;
 
@@ -10,7 +10,7 @@ use average::{Skewness, Estimate, Merge};
 
 #[test]
 fn trivial() {
-    let mut a = Skewness::new();
+    let mut a: Skewness = Skewness::new();
     assert_eq!(a.len(), 0);
     a.add(1.0);
     assert_eq!(a.mean(), 1.0);
@@ -18,14 +18,14 @@ fn trivial() {
     assert_eq!(a.sample_variance(), 0.0);
     assert_eq!(a.population_variance(), 0.0);
     assert_eq!(a.error_mean(), 0.0);
-    assert_eq!(a.skewness(), 0.0);
+    assert!(a.skewness().is_nan());
     a.add(1.0);
     assert_eq!(a.mean(), 1.0);
     assert_eq!(a.len(), 2);
     assert_eq!(a.sample_variance(), 0.0);
     assert_eq!(a.population_variance(), 0.0);
     assert_eq!(a.error_mean(), 0.0);
-    assert_eq!(a.skewness(), 0.0);
+    assert!(a.skewness().is_nan());
 }
 
 #[test]
@@ -55,3 +55,54 @@ fn merge() {
         assert_almost_eq!(avg_total.skewness(), avg_left.skewness(), 1e-14);
     }
 }
+
+#[test]
+fn merge_estimator_almost_eq() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5., 6., 7., 8., 9., 1.];
+    let avg_total: Skewness = sequence.iter().map(|x| *x).collect();
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut avg_left: Skewness = left.iter().map(|x| *x).collect();
+        let avg_right: Skewness = right.iter().map(|x| *x).collect();
+        avg_left.merge(&avg_right);
+        assert_estimator_almost_eq!(avg_total, avg_left, 1e-14);
+    }
+}
+
+#[test]
+fn skewness_zero_variance() {
+    let mut a: Skewness = Skewness::new();
+    assert!(a.skewness().is_nan());
+    for _ in 0..10 {
+        a.add(-3.5);
+        assert!(a.skewness().is_nan());
+    }
+}
+
+#[test]
+fn error_skewness() {
+    let mut a: Skewness = Skewness::new();
+    a.add(1.);
+    a.add(2.);
+    assert!(a.error_skewness().is_nan());
+    a.add(4.);
+    assert_almost_eq!(a.error_skewness(), 1.224744871391589, 1e-15);
+}
+
+#[test]
+fn sample_skewness() {
+    let mut a: Skewness = Skewness::new();
+    a.add(1.);
+    a.add(2.);
+    assert!(a.sample_skewness().is_nan());
+
+    // Reference values computed in exact arithmetic, using the definitions of
+    // `scipy.stats.skew` with `bias=True` and `bias=False`.
+    let a: Skewness = [1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.]
+        .iter().map(|x| *x).collect();
+    assert_almost_eq!(a.skewness(), -0.6769658283251359, 1e-14);
+    assert_almost_eq!(a.sample_skewness(), -0.8027827183537348, 1e-14);
+
+    let a: Skewness = (0..10).map(|_| 1.).collect();
+    assert!(a.sample_skewness().is_nan());
+}
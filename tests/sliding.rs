@@ -0,0 +1,68 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+
+use average::{Sliding, Mean, Variance, Estimate};
+
+#[test]
+fn trivial() {
+    let mut a: Sliding<Variance> = Sliding::new(3);
+    assert!(a.is_empty());
+    a.add(1.);
+    a.add(2.);
+    assert_eq!(a.len(), 2);
+    assert!(!a.is_full());
+    assert_eq!(a.estimator().mean(), 1.5);
+    a.add(3.);
+    assert!(a.is_full());
+    a.add(10.);
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.window(), 3);
+    assert_eq!(a.iter().collect::<Vec<_>>(), vec![2., 3., 10.]);
+    assert_almost_eq!(a.estimator().mean(), 5., 1e-15);
+    assert_eq!(a.estimate(), a.estimator().sample_variance());
+}
+
+#[test]
+fn window() {
+    let sequence: Vec<f64> = (0..200).map(|i| f64::from(i % 17) * 1.5 - 3.).collect();
+    let window = 10;
+    let mut a: Sliding<Variance> = Sliding::new(window);
+    for (i, &x) in sequence.iter().enumerate() {
+        a.add(x);
+        let start = (i + 1).saturating_sub(window);
+        let expected: Variance = sequence[start..=i].iter().map(|x| *x).collect();
+        assert_eq!(a.len() as u64, expected.len());
+        assert_almost_eq!(a.estimator().mean(), expected.mean(), 1e-12);
+        assert_almost_eq!(a.estimator().sample_variance(), expected.sample_variance(), 1e-11);
+    }
+
+    let before = a.estimator().sample_variance();
+    a.recalculate();
+    assert_almost_eq!(a.estimator().sample_variance(), before, 1e-11);
+}
+
+#[test]
+fn f32() {
+    let mut a: Sliding<Mean<f32>, f32> = Sliding::new(2);
+    a.extend([1f32, 2., 3.].iter().map(|x| *x));
+    assert_eq!(a.estimator().mean(), 2.5);
+}
+
+#[test]
+fn clear() {
+    let mut a: Sliding<Mean> = Sliding::new(2);
+    a.extend([1., 2., 3.].iter().map(|x| *x));
+    a.clear();
+    assert!(a.is_empty());
+    assert!(a.estimator().is_empty());
+    a.add(5.);
+    assert_eq!(a.estimator().mean(), 5.);
+}
+
+#[test]
+#[should_panic]
+fn empty_window() {
+    let _: Sliding<Mean> = Sliding::new(0);
+}
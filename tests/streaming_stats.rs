@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 #[macro_use] extern crate average;
 
@@ -23,7 +23,8 @@ fn average_vs_streaming_stats_small() {
     let values = initialize_vec(100);
     let a: average::MeanWithError = values.iter().map(|x| *x).collect();
     let b: stats::OnlineStats = values.iter().map(|x| *x).collect();
-    assert_almost_eq!(a.mean(), b.mean(), 1e-16);
+    // `streaming-stats` does not compensate for rounding errors in the mean.
+    assert_almost_eq!(a.mean(), b.mean(), 1e-15);
     assert_almost_eq!(a.population_variance(), b.variance(), 1e-14);
 }
 
@@ -32,6 +33,7 @@ fn average_vs_streaming_stats_large() {
     let values = initialize_vec(1_000_000);
     let a: average::MeanWithError = values.iter().map(|x| *x).collect();
     let b: stats::OnlineStats = values.iter().map(|x| *x).collect();
-    assert_almost_eq!(a.mean(), b.mean(), 1e-16);
+    // `streaming-stats` does not compensate for rounding errors in the mean.
+    assert_almost_eq!(a.mean(), b.mean(), 1e-12);
     assert_almost_eq!(a.population_variance(), b.variance(), 1e-13);
 }
@@ -0,0 +1,42 @@
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+
+use average::{Sum, Estimate, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = Sum::new();
+    assert!(a.is_empty());
+    assert_eq!(a.sum(), 0.);
+    a.add(3.);
+    a.add(-1.5);
+    assert_eq!(a.len(), 2);
+    assert_eq!(a.sum(), 1.5);
+    assert_eq!(a.estimate(), a.sum());
+    a.clear();
+    assert!(a.is_empty());
+    assert_eq!(a.sum(), 0.);
+}
+
+#[test]
+fn ill_conditioned() {
+    let sequence = [1e16, 1., -1e16];
+    let naive: f64 = sequence.iter().cycle().take(3000).sum();
+    assert_eq!(naive, 0.);
+    let a: Sum = sequence.iter().cycle().take(3000).map(|x| *x).collect();
+    assert_eq!(a.sum(), 1000.);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1e16, 1., 2., -1e16, 3., 1e-3, 1e16, -1e16];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut sum_left: Sum = left.iter().map(|x| *x).collect();
+        let sum_right: Sum = right.iter().map(|x| *x).collect();
+        sum_left.merge(&sum_right);
+        assert_eq!(sum_left.len(), 8);
+        assert_almost_eq!(sum_left.sum(), 6.001, 1e-12);
+    }
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+extern crate average;
+
+use average::{TDigest, Merge};
+
+/// A permutation of `0..n` for `n` coprime to 7919.
+fn shuffled(n: u32) -> impl Iterator<Item=f64> {
+    (0..n).map(move |i| f64::from((i * 7919) % n))
+}
+
+#[test]
+fn empty() {
+    let mut a = TDigest::new(100.);
+    assert!(a.is_empty());
+    assert_eq!(a.quantile(0.5), 0.);
+    a.add(3.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.quantile(0.), 3.);
+    assert_eq!(a.quantile(0.5), 3.);
+    assert_eq!(a.quantile(1.), 3.);
+    a.clear();
+    assert!(a.is_empty());
+    assert_eq!(a.compression(), 100.);
+}
+
+#[test]
+fn few_observations() {
+    let mut a = TDigest::new(100.);
+    a.extend([3., 1., 2., 4.].iter().map(|x| *x));
+    assert_eq!(a.quantile(0.), 1.);
+    assert_eq!(a.quantile(1.), 4.);
+    assert_eq!(a.quantile(0.5), 2.5);
+    assert_eq!(a.centroid_count(), 4);
+}
+
+#[test]
+fn uniform() {
+    let n = 100_000;
+    let mut a = TDigest::new(100.);
+    a.extend(shuffled(n));
+    assert_eq!(a.len(), u64::from(n));
+    assert!(a.centroid_count() < 200);
+    for &p in &[0.001, 0.01, 0.1, 0.25, 0.5, 0.75, 0.9, 0.99, 0.999] {
+        // The error of the rank is smaller in the tails.
+        let rank_error = (a.quantile(p) / f64::from(n) - p).abs();
+        let tolerance = if p <= 0.01 || p >= 0.99 { 1e-3 } else { 5e-3 };
+        assert!(rank_error < tolerance, "p = {}, quantile = {}", p, a.quantile(p));
+    }
+    assert_eq!(a.quantile(0.), 0.);
+    assert_eq!(a.quantile(1.), f64::from(n - 1));
+}
+
+#[test]
+fn heavy_tail() {
+    // Quantiles of the Pareto distribution with shape 1: `1/(1 - p)`.
+    let n = 100_000;
+    let mut a = TDigest::new(200.);
+    a.extend(shuffled(n).map(|i| f64::from(n) / (f64::from(n) - i)));
+    for &p in &[0.5, 0.9, 0.99, 0.999] {
+        let rank_error = (1. - 1. / a.quantile(p) - p).abs();
+        assert!(rank_error < 1e-3, "p = {}, quantile = {}", p, a.quantile(p));
+    }
+}
+
+#[test]
+fn merge() {
+    let n = 100_000;
+    let mut total = TDigest::new(100.);
+    let mut shards: Vec<TDigest> = (0..10).map(|_| TDigest::new(100.)).collect();
+    for (i, x) in shuffled(n).enumerate() {
+        total.add(x);
+        shards[i % 10].add(x);
+    }
+    let mut merged = TDigest::new(100.);
+    for shard in &shards {
+        merged.merge(shard);
+    }
+    assert_eq!(merged.len(), total.len());
+    assert!(merged.centroid_count() < 200);
+    for &p in &[0.001, 0.01, 0.5, 0.99, 0.999] {
+        let rank_error = (merged.quantile(p) / f64::from(n) - p).abs();
+        let tolerance = if p <= 0.01 || p >= 0.99 { 1e-3 } else { 5e-3 };
+        assert!(rank_error < tolerance, "p = {}, quantile = {}", p, merged.quantile(p));
+    }
+}
+
+#[test]
+#[should_panic]
+fn invalid_p() {
+    TDigest::new(100.).quantile(1.1);
+}
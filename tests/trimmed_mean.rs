@@ -0,0 +1,124 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+#[macro_use] extern crate average;
+
+use average::{TrimmedMean, WinsorizedMean, Estimate, Merge};
+
+#[test]
+fn trivial() {
+    let mut a = TrimmedMean::new(0.1);
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.);
+    a.add(3.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean(), 3.);
+    a.clear();
+    assert!(a.is_empty());
+    assert_eq!(a.alpha(), 0.1);
+}
+
+#[test]
+fn untrimmed() {
+    let mut a = TrimmedMean::new(0.);
+    a.extend([1., 2., 3., 10.].iter().map(|x| *x));
+    assert_eq!(a.mean(), 4.);
+}
+
+#[test]
+fn outliers() {
+    let mut a = TrimmedMean::new(0.1);
+    for i in 0..1000 {
+        // Every 50th observation is an outlier.
+        a.add(if i % 50 == 0 { 1e9 } else { f64::from(i % 10) });
+    }
+    assert_eq!(a.len(), 1000);
+    assert_almost_eq!(a.mean(), 4.7, 1e-12);
+}
+
+#[test]
+fn rounding_down() {
+    // floor(0.25 * 7) = 1 observation is discarded from each tail.
+    let mut a = TrimmedMean::new(0.25);
+    a.extend([7., 1., 2., 6., 3., 5., 4.].iter().map(|x| *x));
+    assert_eq!(a.mean(), 4.);
+    a.add(100.);
+    // Now 2 observations are discarded from each tail.
+    assert_eq!(a.mean(), 4.5);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[5., -100., 1., 2., 3., 4., 100., 6.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut total = TrimmedMean::new(0.2);
+        total.extend(sequence.iter().map(|x| *x));
+        let mut a = TrimmedMean::new(0.2);
+        a.extend(left.iter().map(|x| *x));
+        let mut b = TrimmedMean::new(0.2);
+        b.extend(right.iter().map(|x| *x));
+        a.merge(&b);
+        assert_eq!(a.len(), total.len());
+        assert_eq!(a.mean(), total.mean());
+    }
+}
+
+#[test]
+#[should_panic]
+fn invalid_alpha() {
+    TrimmedMean::new(0.5);
+}
+
+#[test]
+fn winsorized_trivial() {
+    let mut a = WinsorizedMean::new(0.1);
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.);
+    a.add(3.);
+    assert_eq!(a.mean(), 3.);
+    a.clear();
+    assert!(a.is_empty());
+    assert_eq!(a.alpha(), 0.1);
+}
+
+#[test]
+fn winsorized() {
+    let mut a = WinsorizedMean::new(0.);
+    a.extend([1., 2., 3., 10.].iter().map(|x| *x));
+    assert_eq!(a.mean(), 4.);
+
+    // floor(0.25 * 8) = 2 observations are clamped in each tail.
+    let mut a = WinsorizedMean::new(0.25);
+    a.extend([5., -100., 1., 2., 3., 4., 100., 6.].iter().map(|x| *x));
+    // The sample is Winsorized to [2, 2, 2, 3, 4, 5, 5, 5].
+    assert_eq!(a.mean(), 3.5);
+}
+
+#[test]
+fn winsorized_nan() {
+    let mut a = WinsorizedMean::new(0.);
+    a.extend([1., f64::NAN, 2.].iter().map(|x| *x));
+    assert_eq!(a.len(), 3);
+    assert!(a.mean().is_nan());
+
+    let mut a = WinsorizedMean::new(0.4);
+    a.extend([1., f64::NAN, 2., 3., 4.].iter().map(|x| *x));
+    assert!(a.mean().is_nan());
+}
+
+#[test]
+fn winsorized_merge() {
+    let sequence: &[f64] = &[5., -100., 1., 2., 3., 4., 100., 6.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut total = WinsorizedMean::new(0.2);
+        total.extend(sequence.iter().map(|x| *x));
+        let mut a = WinsorizedMean::new(0.2);
+        a.extend(left.iter().map(|x| *x));
+        let mut b = WinsorizedMean::new(0.2);
+        b.extend(right.iter().map(|x| *x));
+        a.merge(&b);
+        assert_eq!(a.mean(), total.mean());
+    }
+}
@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 #[macro_use] extern crate average;
 
@@ -6,7 +6,7 @@ extern crate core;
 
 use core::iter::Iterator;
 
-use average::{WeightedMeanWithError, Merge};
+use average::{WeightedMean, WeightedMeanWithError, WeightedVariance, Merge};
 
 #[test]
 fn trivial() {
@@ -106,3 +106,271 @@ fn merge_weighted() {
         assert_almost_eq!(avg_total.sample_variance(), avg_left.sample_variance(), 1e-14);
     }
 }
+
+#[test]
+fn extend() {
+    let sequence: &[(f64, f64)] = &[
+        (1., 0.1), (2., 0.2), (3., 0.3), (4., 0.4), (5., 0.5),
+        (6., 0.6), (7., 0.7), (8., 0.8), (9., 0.9)];
+    let (left, right) = sequence.split_at(4);
+    let avg_total: WeightedMeanWithError = sequence.iter().map(|&x| x).collect();
+    let mut avg: WeightedMeanWithError = left.iter().map(|&x| x).collect();
+    avg.extend(right.iter().map(|&x| x));
+    assert_eq!(avg_total.len(), avg.len());
+    assert_eq!(avg_total.sum_weights(), avg.sum_weights());
+    assert_eq!(avg_total.weighted_mean(), avg.weighted_mean());
+    assert_eq!(avg_total.error(), avg.error());
+}
+
+#[test]
+fn clear() {
+    let mut a: WeightedMeanWithError = (1..6).map(|x| (f64::from(x), 1.0)).collect();
+    a.clear();
+    assert!(a.is_empty());
+    assert_eq!(a.len(), 0);
+    assert_eq!(a.sum_weights(), 0.);
+    assert_eq!(a.sum_weights_sq(), 0.);
+    assert_eq!(a.weighted_mean(), 0.);
+    assert_eq!(a.error(), 0.);
+}
+
+#[test]
+fn variance_trivial() {
+    let mut a = WeightedVariance::new();
+    assert!(a.is_empty());
+    assert_eq!(a.population_variance(), 0.);
+    assert_eq!(a.frequency_sample_variance(), 0.);
+    assert_eq!(a.reliability_sample_variance(), 0.);
+    a.add(1., 2.);
+    assert_eq!(a.mean(), 1.);
+    assert_eq!(a.population_variance(), 0.);
+    assert_eq!(a.reliability_sample_variance(), 0.);
+    a.clear();
+    assert!(a.is_empty());
+    assert_eq!(a.sum_weights(), 0.);
+    assert_eq!(a.sum_weights_sq(), 0.);
+}
+
+#[test]
+fn variance_frequency_weights() {
+    // Equivalent to the unweighted sample [1, 2, 2, 3].
+    let a: WeightedVariance = [(1., 1.), (2., 2.), (3., 1.)].iter().map(|&x| x).collect();
+    assert_eq!(a.sum_weights(), 4.);
+    assert_eq!(a.sum_weights_sq(), 6.);
+    assert_eq!(a.mean(), 2.);
+    assert_almost_eq!(a.population_variance(), 0.5, 1e-15);
+    assert_almost_eq!(a.frequency_sample_variance(), 2. / 3., 1e-15);
+    assert_almost_eq!(a.reliability_sample_variance(), 0.8, 1e-15);
+}
+
+#[test]
+fn variance_reliability_weights() {
+    // Scaling the weights does not change the reliability weighted variance.
+    let a: WeightedVariance = [(1., 1.), (2., 2.), (3., 1.)].iter().map(|&x| x).collect();
+    let b: WeightedVariance = [(1., 0.1), (2., 0.2), (3., 0.1)].iter().map(|&x| x).collect();
+    assert_almost_eq!(a.reliability_sample_variance(), b.reliability_sample_variance(), 1e-14);
+    assert_almost_eq!(a.population_variance(), b.population_variance(), 1e-15);
+}
+
+#[test]
+fn variance_unweighted() {
+    let a: WeightedVariance = (1..6).map(|x| (f64::from(x), 1.0)).collect();
+    assert_eq!(a.mean(), 3.0);
+    assert_eq!(a.population_variance(), 2.0);
+    assert_eq!(a.frequency_sample_variance(), 2.5);
+    assert_eq!(a.reliability_sample_variance(), 2.5);
+}
+
+#[test]
+fn variance_merge() {
+    let sequence: &[(f64, f64)] = &[
+        (1., 0.1), (2., 0.2), (3., 0.3), (4., 0.4), (5., 0.5),
+        (6., 0.6), (7., 0.7), (8., 0.8), (9., 0.9)];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let var_total: WeightedVariance = sequence.iter().map(|&x| x).collect();
+        let mut var_left: WeightedVariance = left.iter().map(|&x| x).collect();
+        let var_right: WeightedVariance = right.iter().map(|&x| x).collect();
+        var_left.merge(&var_right);
+        assert_almost_eq!(var_total.sum_weights(), var_left.sum_weights(), 1e-14);
+        assert_almost_eq!(var_total.sum_weights_sq(), var_left.sum_weights_sq(), 1e-14);
+        assert_almost_eq!(var_total.mean(), var_left.mean(), 1e-14);
+        assert_almost_eq!(var_total.population_variance(), var_left.population_variance(), 1e-14);
+        assert_almost_eq!(var_total.reliability_sample_variance(),
+                          var_left.reliability_sample_variance(), 1e-14);
+    }
+}
+
+#[test]
+fn variance_error() {
+    let mut a = WeightedVariance::new();
+    assert_eq!(a.effective_len(), 0.);
+    assert_eq!(a.error(), 0.);
+    a.add(1., 0.5);
+    assert_eq!(a.effective_len(), 1.);
+    assert_eq!(a.error(), 0.);
+
+    // For unit weights, this agrees with the unweighted standard error.
+    let a: WeightedVariance = (1..6).map(|x| (f64::from(x), 1.0)).collect();
+    assert_eq!(a.effective_len(), 5.);
+    assert_almost_eq!(a.error(), f64::sqrt(0.5), 1e-16);
+
+    let b: WeightedMeanWithError = (1..6).map(|x| (f64::from(x), 1.0)).collect();
+    assert_almost_eq!(a.error(), b.error(), 1e-16);
+
+    // Otherwise, the estimators of the variance of the population differ.
+    let data: &[(f64, f64)] = &[(1., 1.), (2., 2.), (3., 1.)];
+    let a: WeightedVariance = data.iter().map(|&x| x).collect();
+    assert_almost_eq!(a.effective_len(), 16. / 6., 1e-15);
+    assert_almost_eq!(a.error(), f64::sqrt(0.8 * 6. / 16.), 1e-15);
+    let b: WeightedMeanWithError = data.iter().map(|&x| x).collect();
+    assert_almost_eq!(b.error(), f64::sqrt(6. / 16.), 1e-15);
+}
+
+#[test]
+fn zero_and_negative_weights() {
+    let a: WeightedMean = [(1., 1.), (100., 0.), (3., 1.)].iter().map(|&x| x).collect();
+    assert_eq!(a.mean(), 2.);
+    assert_eq!(a.sum_weights(), 2.);
+
+    // A negative weight removes an observation.
+    let b: WeightedMean = [(1., 1.), (3., 1.), (7., 2.), (7., -2.)]
+        .iter().map(|&x| x).collect();
+    assert_almost_eq!(b.mean(), 2., 1e-15);
+
+    let c: WeightedMean = [(1., 0.), (3., 1.)].iter().map(|&x| x).collect();
+    assert!(c.mean().is_nan());
+}
+
+#[test]
+fn negative_weights() {
+    // A negative weight still adds its square and counts as an observation
+    // for the unweighted statistics.
+    let c: WeightedMean = [(1., 2.), (2., 3.), (2., -3.)].iter().map(|&x| x).collect();
+    assert_eq!(c.sum_weights(), 2.);
+    assert_eq!(c.sum_weights_sq(), 22.);
+    let d: WeightedMeanWithError = [(1., 1.), (2., 1.), (2., -1.)].iter().map(|&x| x).collect();
+    assert_eq!(d.len(), 3);
+    assert_eq!(d.unweighted_mean(), 5. / 3.);
+}
+
+#[test]
+fn remove() {
+    // Removing an observation restores all statistics of the remaining sample.
+    let data: &[(f64, f64)] = &[(1., 1.), (2., 1.), (3., 1.)];
+    let mut a: WeightedVariance = data.iter().map(|&x| x).collect();
+    a.remove(3., 1.);
+    assert_eq!(a.sum_weights_sq(), 2.);
+    assert_eq!(a.effective_len(), 2.);
+    assert_almost_eq!(a.mean(), 1.5, 1e-15);
+    assert_almost_eq!(a.reliability_sample_variance(), 0.5, 1e-15);
+    assert_almost_eq!(a.error(), 0.5, 1e-15);
+
+    let mut b: WeightedMeanWithError = data.iter().map(|&x| x).collect();
+    b.remove(3., 1.);
+    assert_eq!(b.len(), 2);
+    assert_eq!(b.effective_len(), 2.);
+    assert_almost_eq!(b.unweighted_mean(), 1.5, 1e-15);
+    assert_almost_eq!(b.sample_variance(), 0.5, 1e-15);
+    assert_almost_eq!(b.error(), 0.5, 1e-15);
+
+    let mut c: WeightedMean = [(1., 2.), (2., 3.)].iter().map(|&x| x).collect();
+    c.remove(2., 3.);
+    assert_almost_eq!(c.mean(), 1., 1e-15);
+    assert_eq!(c.effective_len(), 1.);
+    c.remove(1., 2.);
+    assert!(c.is_empty());
+    assert_eq!(c.mean(), 0.);
+    assert_eq!(c.sum_weights_sq(), 0.);
+
+    let mut d = WeightedVariance::new();
+    d.add(5., 2.);
+    d.remove(5., 2.);
+    assert!(d.is_empty());
+    assert_eq!(d.population_variance(), 0.);
+}
+
+#[test]
+#[should_panic]
+fn remove_empty() {
+    WeightedMeanWithError::new().remove(1., 1.);
+}
+
+#[test]
+fn effective_len() {
+    let data: &[(f64, f64)] = &[(1., 1.), (2., 2.), (3., 3.)];
+    let a: WeightedMean = data.iter().map(|&x| x).collect();
+    let b: WeightedMeanWithError = data.iter().map(|&x| x).collect();
+    let c: WeightedVariance = data.iter().map(|&x| x).collect();
+    assert_eq!(a.sum_weights_sq(), 14.);
+    assert_eq!(a.effective_len(), 36. / 14.);
+    assert_eq!(b.effective_len(), a.effective_len());
+    assert_eq!(c.effective_len(), a.effective_len());
+    assert_eq!(b.len(), 3);
+
+    // For equal weights, the effective sample size is the sample size.
+    let d: WeightedMean = (1..6).map(|x| (f64::from(x), 0.5)).collect();
+    assert_eq!(d.effective_len(), 5.);
+    assert_eq!(WeightedMean::new().effective_len(), 0.);
+
+    let mut left: WeightedMean = data[..1].iter().map(|&x| x).collect();
+    let right: WeightedMean = data[1..].iter().map(|&x| x).collect();
+    left.merge(&right);
+    assert_eq!(left.effective_len(), a.effective_len());
+
+    assert_almost_eq!(c.error(),
+        (c.reliability_sample_variance() / c.effective_len()).sqrt(), 1e-15);
+}
+
+#[test]
+fn add_slice_weighted() {
+    let samples = [1., 2., 3., 4.];
+    let weights = [0.5, 2., 1., 4.];
+    let mut a = WeightedVariance::new();
+    a.add_slice_weighted(&samples, &weights);
+    let b: WeightedVariance = samples.iter().map(|x| *x)
+        .zip(weights.iter().map(|x| *x)).collect();
+    assert_eq!(a.mean(), b.mean());
+    assert_eq!(a.population_variance(), b.population_variance());
+    assert_eq!(a.sum_weights(), 7.5);
+}
+
+#[test]
+#[should_panic]
+fn add_slice_weighted_length_mismatch() {
+    WeightedMean::new().add_slice_weighted(&[1., 2.], &[1.]);
+}
+
+#[test]
+fn normalized() {
+    let samples = [1., 2., 3., 4.];
+    let weights = [0.5, 2., 1., 4.];
+    let mut raw = WeightedVariance::new();
+    raw.add_slice_weighted(&samples, &weights);
+    let total: f64 = weights.iter().sum();
+    let mut scaled = WeightedVariance::new();
+    for (&x, &w) in samples.iter().zip(&weights) {
+        scaled.add(x, w / total);
+    }
+
+    let a = raw.normalized();
+    assert_almost_eq!(a.sum_weights(), 1., 1e-15);
+    assert_almost_eq!(a.sum_weights_sq(), scaled.sum_weights_sq(), 1e-15);
+    assert_almost_eq!(a.mean(), scaled.mean(), 1e-15);
+    assert_almost_eq!(a.population_variance(), scaled.population_variance(), 1e-14);
+
+    // These do not depend on the scale of the weights.
+    assert_eq!(a.mean(), raw.mean());
+    assert_almost_eq!(a.population_variance(), raw.population_variance(), 1e-14);
+    assert_almost_eq!(a.reliability_sample_variance(),
+        raw.reliability_sample_variance(), 1e-14);
+    assert_almost_eq!(a.effective_len(), raw.effective_len(), 1e-14);
+    assert_almost_eq!(a.error(), raw.error(), 1e-14);
+    // The frequency interpretation changes.
+    assert_eq!(a.frequency_sample_variance(), 0.);
+    assert!(raw.frequency_sample_variance() > 0.);
+
+    let b: WeightedMeanWithError = samples.iter().map(|x| *x)
+        .zip(weights.iter().map(|x| *x)).collect();
+    assert_almost_eq!(b.normalized().error(), b.error(), 1e-14);
+}